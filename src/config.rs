@@ -1,43 +1,1031 @@
 use std::{
-    fs::{create_dir_all, File},
-    io::{BufWriter, Write},
+    cmp::Ordering,
+    env,
+    ffi::OsString,
+    fmt::Display,
+    fs::{self, create_dir_all, File},
+    io::{BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::OnceLock,
 };
 
+use clap::ValueEnum;
 use homedir::get_my_home;
+use regex::RegexBuilder;
 use serde::{Deserialize, Serialize};
-use time::OffsetDateTime;
+use time::{format_description::well_known::Rfc3339, macros::datetime, OffsetDateTime};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::{
+    days_since, days_since_at, parse_date, Achievement, BadgeStyle, Interval, Stats, Tiers,
+    Upcoming,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Day {
     pub label: String,
     #[serde(with = "time::serde::rfc3339")]
     pub date: OffsetDateTime,
+    /// End of the period, for closed periods (e.g. a finished job)
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "time::serde::rfc3339::option"
+    )]
+    pub end: Option<OffsetDateTime>,
+    /// Free text about the day
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Tags to group days, e.g. "health" or "work"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Archived days are kept in the config but hidden from the milestones
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+    /// Emoji (or short text) for the day in compact outputs, e.g. "🏃"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+impl Day {
+    /// Builds an open, not archived, `Day` without description, tags or
+    /// icon
+    pub fn new(label: impl Into<String>, date: OffsetDateTime) -> Self {
+        Self {
+            label: label.into(),
+            date,
+            end: None,
+            description: None,
+            tags: Vec::new(),
+            archived: false,
+            icon: None,
+        }
+    }
+
+    /// The icon of the day, or the first character of its label if it has
+    /// none
+    pub fn icon_or_initial(&self) -> String {
+        match &self.icon {
+            Some(icon) if !icon.trim().is_empty() => icon.trim().to_string(),
+            _ => self.label.trim().chars().take(1).collect(),
+        }
+    }
+
+    /// Whether the day has the given tag (case-insensitive)
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim()))
+    }
+
+    /// Whether the day is a closed period, i.e. it has an end date
+    pub fn is_closed(&self) -> bool {
+        self.end.is_some()
+    }
+
+    /// Returns the interval from the day to now, see `days_since()`
+    ///
+    /// A day in the future is zero days ago.
+    pub fn elapsed(&self) -> Interval {
+        Interval::from_days(days_since(self.date))
+    }
+
+    /// Returns the interval from the day to `now`, see `days_since_at()`
+    pub fn elapsed_at(&self, now: OffsetDateTime) -> Interval {
+        Interval::from_days(days_since_at(self.date, now))
+    }
+}
+
+impl PartialOrd for Day {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Day {
+    /// Days are ordered chronologically by date
+    ///
+    /// Days with the same date are ordered by label, then by the other
+    /// fields so that the order is consistent with `Eq`.
+    ///
+    /// ```
+    /// use achievements::config::Day;
+    /// use time::macros::datetime;
+    ///
+    /// let mut days = vec![
+    ///     Day::new("Berlin Wall Fall", datetime!(1989-11-09 18:53 +01:00)),
+    ///     Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC)),
+    /// ];
+    /// days.sort();
+    /// assert_eq!("Moon landing", days[0].label);
+    /// ```
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.date
+            .cmp(&other.date)
+            .then_with(|| self.label.cmp(&other.label))
+            .then_with(|| self.end.cmp(&other.end))
+            .then_with(|| self.description.cmp(&other.description))
+            .then_with(|| self.tags.cmp(&other.tags))
+            .then_with(|| self.archived.cmp(&other.archived))
+            .then_with(|| self.icon.cmp(&other.icon))
+    }
+}
+
+/// Default oldest date of the days, older ones are reported by
+/// `Config::validate()` as likely typos
+pub const MIN_DATE: OffsetDateTime = datetime!(1900-01-01 0:00 UTC);
+
+/// Fields of the config JSON, see `Config::unknown_fields()`
+const CONFIG_FIELDS: [&str; 4] = ["version", "days", "template", "badge_style"];
+
+/// Fields of the days in the config JSON, see `Config::unknown_fields()`
+const DAY_FIELDS: [&str; 7] = [
+    "label",
+    "date",
+    "end",
+    "description",
+    "tags",
+    "archived",
+    "icon",
+];
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default)]
     pub days: Vec<Day>,
+    /// Default template for the achievements output, see
+    /// `render::template`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Style of the badges in the achievements output, emoji if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub badge_style: Option<BadgeStyle>,
+}
+
+/// Errors reading, parsing or writing the config
+#[derive(Debug)]
+pub enum ConfigError {
+    /// Failed to read/write the config
+    Io(std::io::Error),
+    /// Failed to parse/serialize the config JSON
+    Json(serde_json::Error),
+    /// Failed to parse/serialize the config TOML
+    Toml(String),
+    /// Failed to parse/write CSV days
+    Csv(csv::Error),
+    /// A day is invalid, e.g. it has an empty label or an invalid date
+    InvalidDay { row: usize, message: String },
+    /// A day with the label is already in the config
+    Conflict(String),
+    /// Another day already has the label a day is renamed to
+    DuplicateLabel(String),
+    /// No day with the label in the config
+    NotFound(String),
+    /// A pattern is empty or only whitespace, it would match every day
+    EmptyPattern,
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "config I/O error: {e}"),
+            ConfigError::Json(e) => write!(f, "invalid config JSON: {e}"),
+            ConfigError::Toml(e) => write!(f, "invalid config TOML: {e}"),
+            ConfigError::Csv(e) => write!(f, "invalid CSV: {e}"),
+            ConfigError::InvalidDay { row, message } => {
+                write!(f, "invalid day on row {row}: {message}")
+            }
+            ConfigError::Conflict(label) => {
+                write!(f, "a day with label '{label}' is already in the config")
+            }
+            ConfigError::DuplicateLabel(label) => {
+                write!(f, "can't rename to '{label}', another day has that label")
+            }
+            ConfigError::NotFound(label) => {
+                write!(f, "no day with label '{label}' in the config")
+            }
+            ConfigError::EmptyPattern => {
+                write!(f, "the pattern is empty, it would match every day")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io(e) => Some(e),
+            ConfigError::Json(e) => Some(e),
+            ConfigError::Csv(e) => Some(e),
+            ConfigError::Toml(_)
+            | ConfigError::InvalidDay { .. }
+            | ConfigError::Conflict(_)
+            | ConfigError::DuplicateLabel(_)
+            | ConfigError::NotFound(_)
+            | ConfigError::EmptyPattern => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(e: std::io::Error) -> Self {
+        ConfigError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ConfigError {
+    fn from(e: serde_json::Error) -> Self {
+        ConfigError::Json(e)
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(e: toml::de::Error) -> Self {
+        ConfigError::Toml(e.to_string())
+    }
+}
+
+impl From<toml::ser::Error> for ConfigError {
+    fn from(e: toml::ser::Error) -> Self {
+        ConfigError::Toml(e.to_string())
+    }
+}
+
+impl From<csv::Error> for ConfigError {
+    fn from(e: csv::Error) -> Self {
+        ConfigError::Csv(e)
+    }
+}
+
+/// What `Config::merge()` does with days whose label is already in the
+/// config
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OnConflict {
+    /// Keep the day in the config
+    #[default]
+    Skip,
+    /// Replace the day in the config with the merged one
+    Overwrite,
+    /// Don't merge anything
+    Abort,
+}
+
+/// Labels of the days added, updated and skipped by `Config::merge()`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MergeSummary {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+/// A problem with the days found by `Config::validate()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// The day at the index has an empty (or whitespace only) label
+    EmptyLabel(usize),
+    /// More than one day has the label (ignoring case and whitespace)
+    DuplicateLabel(String),
+    /// The day with the label has a date in the future
+    FutureDate(String),
+    /// The day with the label has a date before the minimum date
+    OldDate(String, OffsetDateTime),
+    /// The field at the path isn't a config field and is ignored, e.g.
+    /// `days[2].colour`
+    UnknownField(String),
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::EmptyLabel(index) => write!(f, "day {} has an empty label", index + 1),
+            ValidationIssue::DuplicateLabel(label) => write!(f, "duplicate label '{label}'"),
+            ValidationIssue::FutureDate(label) => write!(f, "'{label}' has a date in the future"),
+            ValidationIssue::OldDate(label, min_date) => write!(
+                f,
+                "'{label}' has a date before {}",
+                crate::render::format_day(*min_date)
+            ),
+            ValidationIssue::UnknownField(path) => write!(f, "unknown field '{path}' is ignored"),
+        }
+    }
+}
+
+/// Labels of the days changed by `Config::fix()`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FixSummary {
+    /// Labels trimmed of leading/trailing whitespace
+    pub trimmed: Vec<String>,
+    /// Labels of the exact duplicates removed
+    pub deduped: Vec<String>,
+}
+
+impl FixSummary {
+    /// Whether nothing was fixed
+    pub fn is_empty(&self) -> bool {
+        self.trimmed.is_empty() && self.deduped.is_empty()
+    }
+}
+
+/// Columns of the CSV written by `days_to_csv()`
+const CSV_HEADER: [&str; 4] = ["label", "date", "description", "tags"];
+
+/// Parses days from CSV with a header row
+///
+/// The `label` and `date` columns are required (the first two columns if
+/// the header doesn't name them), `description` and `tags` (comma
+/// separated) are optional, i.e. it reads both `label,date` files and the
+/// ones written by `days_to_csv()`.
+///
+/// Dates are parsed with `parse_date()`, i.e. RFC3339 or `YYYY-MM-DD`.
+/// Rows are numbered from 1, the header excluded, in the errors.
+pub fn days_from_csv<R: Read>(reader: R) -> Result<Vec<Day>, ConfigError> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.trim().eq_ignore_ascii_case(name))
+    };
+    let label_column = column("label").unwrap_or(0);
+    let date_column = column("date").unwrap_or(1);
+    let description_column = column("description");
+    let tags_column = column("tags");
+
+    let mut days = Vec::new();
+    for (i, record) in reader.records().enumerate() {
+        let row = i + 1;
+        let record = record?;
+        let (Some(label), Some(date)) = (record.get(label_column), record.get(date_column)) else {
+            return Err(ConfigError::InvalidDay {
+                row,
+                message: "expected label and date columns".to_string(),
+            });
+        };
+        let date = parse_date(date).map_err(|e| ConfigError::InvalidDay {
+            row,
+            message: format!("invalid date '{date}': {e}"),
+        })?;
+
+        let mut day = Day::new(label, date);
+        day.description = description_column
+            .and_then(|column| record.get(column))
+            .filter(|description| !description.is_empty())
+            .map(String::from);
+        day.tags = tags_column
+            .and_then(|column| record.get(column))
+            .map(|tags| {
+                tags.split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        days.push(day);
+    }
+
+    Ok(days)
+}
+
+/// Writes the days as CSV with a `label,date,description,tags` header
+///
+/// Dates are in RFC3339 and tags comma separated, `days_from_csv()` reads
+/// them back.
+pub fn days_to_csv<W: Write>(days: &[Day], writer: W) -> Result<(), ConfigError> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(CSV_HEADER)?;
+    for (i, day) in days.iter().enumerate() {
+        let date = day
+            .date
+            .format(&Rfc3339)
+            .map_err(|e| ConfigError::InvalidDay {
+                row: i + 1,
+                message: format!("invalid date: {e}"),
+            })?;
+        writer.write_record([
+            day.label.as_str(),
+            &date,
+            day.description.as_deref().unwrap_or_default(),
+            &day.tags.join(","),
+        ])?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Key used to sort the days in the config
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SortKey {
+    /// Alphabetically by label (case-insensitive)
+    Label,
+    /// Chronologically by date, oldest first
+    Date,
+    /// By number of days elapsed, fewest first
+    #[value(alias = "elapsed")]
+    Days,
+}
+
+impl SortKey {
+    /// Returns the comparison of two days by this key, for one sort
+    ///
+    /// The number of days (for `SortKey::Days`) is computed against `now`,
+    /// the labels are compared with a collator built once for the sort.
+    pub(crate) fn comparator(
+        self,
+        reverse: bool,
+        now: OffsetDateTime,
+    ) -> impl FnMut(&Day, &Day) -> Ordering {
+        let mut labels = LabelCollator::new();
+
+        move |a: &Day, b: &Day| {
+            let ordering = match self {
+                SortKey::Label => labels.compare(&a.label, &b.label),
+                SortKey::Date => a.date.cmp(&b.date),
+                SortKey::Days => days_since_at(a.date, now).cmp(&days_since_at(b.date, now)),
+            };
+
+            if reverse {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        }
+    }
+}
+
+/// Compares labels in the Unicode collation order with the unicode
+/// feature, case-insensitively by code point without it
+///
+/// Building the collator isn't cheap, build one per sort rather than per
+/// comparison.
+#[cfg(feature = "unicode")]
+struct LabelCollator(feruca::Collator);
+
+#[cfg(feature = "unicode")]
+impl LabelCollator {
+    fn new() -> Self {
+        Self(feruca::Collator::default())
+    }
+
+    fn compare(&mut self, a: &str, b: &str) -> Ordering {
+        self.0.collate(a, b)
+    }
+}
+
+#[cfg(not(feature = "unicode"))]
+struct LabelCollator;
+
+#[cfg(not(feature = "unicode"))]
+impl LabelCollator {
+    fn new() -> Self {
+        Self
+    }
+
+    fn compare(&mut self, a: &str, b: &str) -> Ordering {
+        a.to_lowercase().cmp(&b.to_lowercase())
+    }
+}
+
+/// Field of the days matched by `Config::search()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SearchField {
+    Label,
+    /// The description of the day
+    Notes,
+    Tags,
+}
+
+/// Options of `Config::search()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Fields to search, a day matches if any of them does
+    pub fields: Vec<SearchField>,
+    /// Whether the query is a regular expression instead of a substring
+    pub regex: bool,
+}
+
+impl Default for SearchOptions {
+    /// Searches the labels for a substring
+    fn default() -> Self {
+        Self {
+            fields: vec![SearchField::Label],
+            regex: false,
+        }
+    }
 }
 
 impl Config {
+    /// Parses a config from a JSON string
+    ///
+    /// See `read()` for the format.
+    pub fn from_json_str(json: &str) -> Result<Config, ConfigError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serializes the config to a pretty-printed JSON string
+    ///
+    /// This is the same content `write()` writes to the config file.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use achievements::config::Config;
+    ///
+    /// let config = Config::default();
+    /// assert_eq!("{\n  \"days\": []\n}", config.write_to_string().unwrap());
+    /// ```
+    pub fn write_to_string(&self) -> Result<String, ConfigError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Serializes the config as pretty-printed JSON to the given writer
+    pub fn write_to_writer<W: Write>(&self, writer: W) -> Result<(), ConfigError> {
+        Ok(serde_json::to_writer_pretty(writer, self)?)
+    }
+
+    /// Parses a config from a TOML string, same schema as the JSON one
+    pub fn from_toml_str(toml: &str) -> Result<Config, ConfigError> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    /// Serializes the config as TOML, with a `[[days]]` table per day
+    pub fn write_to_toml_string(&self) -> Result<String, ConfigError> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    /// Reads the config from the JSON file at the given path
+    ///
+    /// If the file doesn't exist an empty `Config` with no days is returned,
+    /// see `read()` for the format.
+    pub fn read_from_path(path: &Path) -> Result<Config, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(json) => Config::from_json_str(&json),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes the config as pretty-printed JSON to the given path
+    ///
+    /// The parent directory and the file are created if they don't exist,
+    /// an existing file is overwritten.
+    pub fn write_to_path(&self, path: &Path) -> Result<(), ConfigError> {
+        create_config_dir(path)?;
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to_writer(&mut writer)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Writes a copy of the config to `dst`, e.g. to keep a backup before
+    /// changing it
+    ///
+    /// Unlike `backup()` this writes the config as it is in memory, not the
+    /// config file on disk.
+    pub fn backup_to_path(&self, dst: &Path) -> Result<(), ConfigError> {
+        self.write_to_path(dst)
+    }
+
     /// Add/update a day to the config
     ///
     /// The day is added if days doesn't contain a day with the given label.
     ///
-    /// If a day with the same (trimmed) label is found its date is updated
-    /// with the given date
-    pub fn set_day(&mut self, label: &str, date: OffsetDateTime) {
+    /// If a day with the same label is found its date is updated with the
+    /// given date, keeping its label. Labels are compared ignoring case and
+    /// leading/trailing whitespace, like `find_day()`.
+    ///
+    /// Returns the added or updated day.
+    pub fn set_day(&mut self, label: &str, date: OffsetDateTime) -> &mut Day {
         let label = label.trim();
-        match self.days.iter_mut().find(|day| day.label == label) {
-            Some(d) => {
-                d.date = date;
-            }
-            None => self.days.push(Day {
-                label: label.to_string(),
-                date,
-            }),
+        let key = label.to_lowercase();
+        match self
+            .days
+            .iter()
+            .position(|day| day.label.trim().to_lowercase() == key)
+        {
+            Some(index) => {
+                self.days[index].date = date;
+                &mut self.days[index]
+            }
+            None => {
+                self.days.push(Day::new(label, date));
+                self.days.last_mut().expect("Day was just added")
+            }
+        }
+    }
+
+    /// Adds a day to the config, unless a day has the same label
+    ///
+    /// Labels are compared ignoring case and leading/trailing whitespace,
+    /// see `find_day()`. Returns `true` if the day was added, `false` if
+    /// the config already has it (left untouched, unlike `set_day()`).
+    pub fn set_day_if_absent(&mut self, label: &str, date: OffsetDateTime) -> bool {
+        if self.contains(label) {
+            return false;
+        }
+        self.days.push(Day::new(label.trim(), date));
+        true
+    }
+
+    /// Adds a copy of the day with `label` named `new_label`, same date and
+    /// other fields
+    ///
+    /// Labels are compared ignoring case and leading/trailing whitespace.
+    /// Returns a `ConfigError::NotFound` error if there's no day with
+    /// `label` and a `ConfigError::Conflict` one if a day with `new_label`
+    /// is already in the config.
+    pub fn duplicate_day(&mut self, label: &str, new_label: &str) -> Result<&Day, ConfigError> {
+        let new_label = new_label.trim();
+        if self.contains(new_label) {
+            return Err(ConfigError::Conflict(new_label.to_string()));
+        }
+        let Some(day) = self.find_day(label) else {
+            return Err(ConfigError::NotFound(label.trim().to_string()));
         };
+
+        let mut copy = day.clone();
+        copy.label = new_label.to_string();
+        self.days.push(copy);
+
+        Ok(&self.days[self.days.len() - 1])
+    }
+
+    /// Renames the day with label `old` to `new` (trimmed), keeping its
+    /// date and other fields, unlike `set_day()` it never adds a day
+    ///
+    /// Labels are compared ignoring case and leading/trailing whitespace.
+    /// Returns `false` if there's no day with `old` and a
+    /// `ConfigError::DuplicateLabel` error if another day has `new`, changing
+    /// only the case of the label is fine.
+    pub fn update_label(&mut self, old: &str, new: &str) -> Result<bool, ConfigError> {
+        let key = |label: &str| label.trim().to_lowercase();
+        let Some(index) = self.days.iter().position(|day| key(&day.label) == key(old)) else {
+            return Ok(false);
+        };
+        let new = new.trim();
+        let taken = self
+            .days
+            .iter()
+            .enumerate()
+            .any(|(i, day)| i != index && key(&day.label) == key(new));
+        if taken {
+            return Err(ConfigError::DuplicateLabel(new.to_string()));
+        }
+
+        self.days[index].label = new.to_string();
+        Ok(true)
+    }
+
+    /// Merges the days into the config, e.g. to import them
+    ///
+    /// New labels (trimmed) are added at the end, labels already in the
+    /// config are handled according to `on_conflict`. Labels are compared
+    /// ignoring case and leading/trailing whitespace, like `find_day()`,
+    /// the summary and an updated day keep the label of the config.
+    ///
+    /// All the days are validated before changing the config: on error,
+    /// e.g. an empty label or a conflict with `OnConflict::Abort`, the
+    /// config is left untouched.
+    pub fn merge(
+        &mut self,
+        days: Vec<Day>,
+        on_conflict: OnConflict,
+    ) -> Result<MergeSummary, ConfigError> {
+        let mut merged = self.days.clone();
+        let mut summary = MergeSummary::default();
+
+        for (i, mut day) in days.into_iter().enumerate() {
+            day.label = day.label.trim().to_string();
+            if day.label.is_empty() {
+                return Err(ConfigError::InvalidDay {
+                    row: i + 1,
+                    message: "empty label".to_string(),
+                });
+            }
+
+            let key = day.label.to_lowercase();
+            match merged
+                .iter_mut()
+                .find(|d| d.label.trim().to_lowercase() == key)
+            {
+                None => {
+                    summary.added.push(day.label.clone());
+                    merged.push(day);
+                }
+                Some(existing) if on_conflict == OnConflict::Abort => {
+                    return Err(ConfigError::Conflict(existing.label.clone()));
+                }
+                Some(existing) if on_conflict == OnConflict::Skip => {
+                    summary.skipped.push(existing.label.clone())
+                }
+                Some(existing) => {
+                    summary.updated.push(existing.label.clone());
+                    day.label = existing.label.clone();
+                    *existing = day;
+                }
+            }
+        }
+
+        self.days = merged;
+        Ok(summary)
+    }
+
+    /// Checks the days for empty labels, duplicate labels, dates after
+    /// `now` and dates before `min_date` (e.g. `MIN_DATE`)
+    ///
+    /// Duplicate labels are reported once, with the label of the first day.
+    pub fn validate(&self, now: OffsetDateTime, min_date: OffsetDateTime) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let mut seen: Vec<&str> = Vec::new();
+
+        for (i, day) in self.days.iter().enumerate() {
+            let label = day.label.trim();
+            if label.is_empty() {
+                issues.push(ValidationIssue::EmptyLabel(i));
+                continue;
+            }
+
+            match seen
+                .iter()
+                .find(|seen| seen.to_lowercase() == label.to_lowercase())
+            {
+                Some(first) => {
+                    let duplicate = ValidationIssue::DuplicateLabel(first.to_string());
+                    if !issues.contains(&duplicate) {
+                        issues.push(duplicate);
+                    }
+                }
+                None => seen.push(label),
+            }
+
+            if day.date > now {
+                issues.push(ValidationIssue::FutureDate(label.to_string()));
+            }
+            if day.date < min_date {
+                issues.push(ValidationIssue::OldDate(label.to_string(), min_date));
+            }
+        }
+
+        issues
+    }
+
+    /// Returns the fields of the config JSON that are neither config nor
+    /// day fields, silently ignored when reading the config
+    ///
+    /// Fields of the days are reported with their index, e.g.
+    /// `days[2].colour`.
+    ///
+    /// ```
+    /// use achievements::config::Config;
+    ///
+    /// let json = r#"{"days": [{"label": "Run", "date": "2024-01-01T00:00:00Z", "colour": "red"}]}"#;
+    /// assert_eq!(Config::unknown_fields(json).unwrap(), ["days[0].colour"]);
+    /// ```
+    pub fn unknown_fields(json: &str) -> Result<Vec<String>, ConfigError> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let Some(fields) = value.as_object() else {
+            return Ok(Vec::new());
+        };
+
+        let mut unknown = Vec::new();
+        for (field, value) in fields {
+            if !CONFIG_FIELDS.contains(&field.as_str()) {
+                unknown.push(field.clone());
+            }
+            if field != "days" {
+                continue;
+            }
+            let days = value.as_array().into_iter().flatten();
+            for (i, day) in days.enumerate() {
+                let fields = day.as_object().into_iter().flat_map(|day| day.keys());
+                for field in fields.filter(|field| !DAY_FIELDS.contains(&field.as_str())) {
+                    unknown.push(format!("days[{i}].{field}"));
+                }
+            }
+        }
+
+        Ok(unknown)
+    }
+
+    /// Applies the fixes that don't lose information: trims the labels and
+    /// removes the exact duplicates of a day, keeping the first one
+    pub fn fix(&mut self) -> FixSummary {
+        let mut summary = FixSummary::default();
+        let mut days: Vec<Day> = Vec::with_capacity(self.days.len());
+
+        for mut day in self.days.drain(..) {
+            let label = day.label.trim();
+            if label != day.label {
+                day.label = label.to_string();
+                summary.trimmed.push(day.label.clone());
+            }
+
+            if days.contains(&day) {
+                summary.deduped.push(day.label);
+            } else {
+                days.push(day);
+            }
+        }
+
+        self.days = days;
+        summary
+    }
+
+    /// Returns the first day with the given label, `None` if there's none
+    ///
+    /// The label comparison ignores case and leading/trailing whitespace,
+    /// same as `remove_day()`.
+    pub fn find_day(&self, label: &str) -> Option<&Day> {
+        let label = label.trim().to_lowercase();
+        self.days
+            .iter()
+            .find(|day| day.label.trim().to_lowercase() == label)
+    }
+
+    /// Returns `true` if a day has the given label, see `find_day()`
+    pub fn contains(&self, label: &str) -> bool {
+        self.find_day(label).is_some()
+    }
+
+    /// Returns the days with labels matching the glob pattern, ignoring
+    /// case and leading/trailing whitespace
+    ///
+    /// `*` matches any sequence of characters, `?` any single character.
+    ///
+    /// ```
+    /// use achievements::config::Config;
+    /// use time::macros::datetime;
+    ///
+    /// let mut config = Config::default();
+    /// config.set_day("Old project Alpha", datetime!(2015-03-01 0:00 UTC));
+    /// config.set_day("Old project Beta", datetime!(2017-06-01 0:00 UTC));
+    /// config.set_day("New project", datetime!(2024-01-01 0:00 UTC));
+    ///
+    /// let days = config.find_days_glob("old project *");
+    /// assert_eq!(2, days.len());
+    /// ```
+    pub fn find_days_glob(&self, pattern: &str) -> Vec<&Day> {
+        let pattern: Vec<char> = pattern.trim().to_lowercase().chars().collect();
+        self.days
+            .iter()
+            .filter(|day| {
+                let label: Vec<char> = day.label.trim().to_lowercase().chars().collect();
+                glob_matches(&pattern, &label)
+            })
+            .collect()
+    }
+
+    /// Returns the days with labels containing the pattern, or matching it
+    /// when it's a glob pattern (with `*` or `?`), ignoring case and
+    /// leading/trailing whitespace
+    ///
+    /// ```
+    /// use achievements::config::Config;
+    /// use time::macros::datetime;
+    ///
+    /// let mut config = Config::default();
+    /// config.set_day("Morning workout", datetime!(2024-01-01 7:00 UTC));
+    /// config.set_day("Workout (evening)", datetime!(2024-01-01 19:00 UTC));
+    /// config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    ///
+    /// assert_eq!(2, config.find_days_matching("workout").unwrap().len());
+    /// assert_eq!(1, config.find_days_matching("workout*").unwrap().len());
+    /// assert!(config.find_days_matching("  ").is_err());
+    /// ```
+    ///
+    /// Returns `ConfigError::EmptyPattern` if the pattern is empty or only
+    /// whitespace, rather than every day.
+    pub fn find_days_matching(&self, pattern: &str) -> Result<Vec<&Day>, ConfigError> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            Err(ConfigError::EmptyPattern)
+        } else if pattern.contains(['*', '?']) {
+            Ok(self.find_days_glob(pattern))
+        } else {
+            Ok(self.find_days_glob(&format!("*{pattern}*")))
+        }
+    }
+
+    /// Removes the days matching the pattern, see `find_days_matching()`
+    ///
+    /// Returns the number of days removed.
+    pub fn remove_all_matching(&mut self, pattern: &str) -> Result<usize, ConfigError> {
+        let labels: Vec<String> = self
+            .find_days_matching(pattern)?
+            .iter()
+            .map(|day| day.label.clone())
+            .collect();
+        let count = self.days.len();
+        self.days.retain(|day| !labels.contains(&day.label));
+
+        Ok(count - self.days.len())
+    }
+
+    /// Returns the days matching the query in any of the fields of the
+    /// options, ignoring case
+    ///
+    /// The query is a substring unless `options.regex` is set, returns an
+    /// error if it's not a valid regular expression.
+    ///
+    /// ```
+    /// use achievements::config::{Config, SearchOptions};
+    /// use time::macros::datetime;
+    ///
+    /// let mut config = Config::default();
+    /// config.set_day("Berlin Wall Fall", datetime!(1989-11-09 18:53 +01:00));
+    /// config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    ///
+    /// let days = config.search("WALL", &SearchOptions::default()).unwrap();
+    /// assert_eq!("Berlin Wall Fall", days[0].label);
+    /// ```
+    pub fn search(&self, query: &str, options: &SearchOptions) -> Result<Vec<&Day>, regex::Error> {
+        let matches: Box<dyn Fn(&str) -> bool> = if options.regex {
+            let regex = RegexBuilder::new(query).case_insensitive(true).build()?;
+            Box::new(move |text| regex.is_match(text))
+        } else {
+            let query = query.to_lowercase();
+            Box::new(move |text| text.to_lowercase().contains(&query))
+        };
+
+        Ok(self
+            .days
+            .iter()
+            .filter(|day| {
+                options.fields.iter().any(|field| match field {
+                    SearchField::Label => matches(&day.label),
+                    SearchField::Notes => day.description.as_deref().is_some_and(&matches),
+                    SearchField::Tags => day.tags.iter().any(|tag| matches(tag)),
+                })
+            })
+            .collect())
+    }
+
+    /// Returns up to `max` labels similar to the given one, most similar
+    /// first, e.g. to suggest them when a label is not found
+    ///
+    /// A label is similar if it contains the given one, if it's a few
+    /// edits away from it or if it starts with a few edits away from it
+    /// (e.g. "berln wall" for "Berlin Wall Fall"), ignoring case and
+    /// leading/trailing whitespace like the lookups.
+    ///
+    /// ```
+    /// use achievements::config::Config;
+    /// use time::macros::datetime;
+    ///
+    /// let mut config = Config::default();
+    /// config.set_day("Berlin Wall Fall", datetime!(1989-11-09 18:53 +01:00));
+    /// config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    /// assert_eq!(config.closest_labels("berlin wal fall", 3), ["Berlin Wall Fall"]);
+    /// assert_eq!(config.closest_labels("berln wall", 3), ["Berlin Wall Fall"]);
+    /// assert_eq!(config.closest_labels("moon", 3), ["Moon landing"]);
+    /// assert!(config.closest_labels("Graduation", 3).is_empty());
+    /// ```
+    pub fn closest_labels(&self, label: &str, max: usize) -> Vec<&str> {
+        let label = label.trim().to_lowercase();
+        let max_distance = label.chars().count() / 4 + 1;
+
+        let mut similar: Vec<((usize, usize), &str)> = self
+            .days
+            .iter()
+            .filter_map(|day| {
+                let other = day.label.trim().to_lowercase();
+                let (distance, prefix_distance) = edit_distances(&label, &other);
+                let contains = !label.is_empty() && other.contains(&label);
+                let similar =
+                    distance <= max_distance || prefix_distance < max_distance || contains;
+                similar.then_some((
+                    (prefix_distance.min(distance), distance),
+                    day.label.as_str(),
+                ))
+            })
+            .collect();
+        similar.sort_by_key(|(distance, _)| *distance);
+        similar.dedup_by_key(|(_, label)| *label);
+
+        similar
+            .into_iter()
+            .take(max)
+            .map(|(_, label)| label)
+            .collect()
+    }
+
+    /// Archives (or unarchives) the day with the given label
+    ///
+    /// The label comparison ignores case and leading/trailing whitespace,
+    /// returns a `ConfigError::NotFound` error if there's no such day.
+    pub fn set_archived(&mut self, label: &str, archived: bool) -> Result<(), ConfigError> {
+        let trimmed = label.trim().to_lowercase();
+        match self
+            .days
+            .iter_mut()
+            .find(|day| day.label.trim().to_lowercase() == trimmed)
+        {
+            Some(day) => {
+                day.archived = archived;
+                Ok(())
+            }
+            None => Err(ConfigError::NotFound(label.trim().to_string())),
+        }
     }
 
     /// Removes a day with the given label from the config
@@ -55,6 +1043,139 @@ impl Config {
 
         self.days = other_days;
     }
+
+    /// Returns the day with the earliest date, `None` if there are no days
+    ///
+    /// If several days have the earliest date the first one is returned.
+    pub fn earliest(&self) -> Option<&Day> {
+        self.days.iter().min_by_key(|day| day.date)
+    }
+
+    /// Returns the day with the latest date, `None` if there are no days
+    ///
+    /// If several days have the latest date the last one is returned.
+    pub fn latest(&self) -> Option<&Day> {
+        self.days.iter().max_by_key(|day| day.date)
+    }
+
+    /// Returns the achievements for the days in the config, in config order
+    ///
+    /// All intervals are computed against the same `now`.
+    pub fn achievements(&self, now: OffsetDateTime) -> Vec<Achievement> {
+        self.days
+            .iter()
+            .map(|day| Achievement::new(day.clone(), now))
+            .collect()
+    }
+
+    /// Returns aggregated numbers about the days, computed at `now`
+    ///
+    /// Oldest and newest are the same days as `earliest()` and `latest()`.
+    pub fn stats(&self, now: OffsetDateTime) -> Stats {
+        let achievements = self.achievements(now);
+        let total_days: usize = achievements
+            .iter()
+            .map(|achievement| achievement.interval.as_days())
+            .sum();
+        let mut tiers = Tiers::default();
+        for achievement in achievements.iter() {
+            tiers.add(&achievement.interval);
+        }
+
+        Stats {
+            count: self.days.len(),
+            oldest: self
+                .earliest()
+                .map(|day| Achievement::new(day.clone(), now)),
+            newest: self.latest().map(|day| Achievement::new(day.clone(), now)),
+            total_days,
+            average_days: total_days.checked_div(self.days.len()).unwrap_or(0),
+            tiers,
+        }
+    }
+
+    /// Returns the next milestone at `now` of each day, soonest first
+    ///
+    /// Archived days, closed periods and days after `now` have no next
+    /// milestone and are skipped. Days with the milestone on the same day
+    /// keep their config order.
+    pub fn next_milestones(&self, now: OffsetDateTime) -> Vec<Upcoming> {
+        let mut upcoming: Vec<Upcoming> = self
+            .days
+            .iter()
+            .filter(|day| !day.archived && !day.is_closed() && day.date <= now)
+            .map(|day| Upcoming::new(day.clone(), now))
+            .collect();
+        upcoming.sort_by_key(Upcoming::in_days);
+
+        upcoming
+    }
+
+    /// Returns the days whose next milestone is within `days` days of `now`
+    ///
+    /// Same as `next_milestones()`, milestones reached today included.
+    pub fn upcoming(&self, now: OffsetDateTime, days: usize) -> Vec<Upcoming> {
+        self.next_milestones(now)
+            .into_iter()
+            .filter(|upcoming| upcoming.in_days() <= days)
+            .collect()
+    }
+
+    /// Sorts the days in the config by the given key
+    ///
+    /// The sort is stable, days comparing equal (e.g. same date) keep their
+    /// config-relative order, also when `reverse` is `true`.
+    ///
+    /// When sorting by `SortKey::Days` each day count is computed against the
+    /// same `now`, so the order is consistent within one run.
+    pub fn sort_days(&mut self, key: SortKey, reverse: bool, now: OffsetDateTime) {
+        self.days.sort_by(key.comparator(reverse, now));
+    }
+
+    /// Sorts the days by label in the Unicode collation order (the CLDR
+    /// root one), e.g. "Été", "Été fini" then "Fête", where comparing the
+    /// code points would put "Fête" first
+    ///
+    /// Needs the unicode feature, which also makes `SortKey::Label` use
+    /// this order. The sort is stable.
+    #[cfg(feature = "unicode")]
+    pub fn sort_by_label_locale_aware(&mut self) {
+        let mut labels = LabelCollator::new();
+        self.days.sort_by(|a, b| labels.compare(&a.label, &b.label));
+    }
+
+    /// Returns the days sorted by the given key, leaving the config untouched
+    ///
+    /// See `Config::sort_days` for the sorting rules.
+    pub fn sorted_days(&self, key: SortKey, reverse: bool, now: OffsetDateTime) -> Vec<&Day> {
+        let mut days: Vec<&Day> = self.days.iter().collect();
+        let mut compare = key.comparator(reverse, now);
+        days.sort_by(|a, b| compare(a, b));
+        days
+    }
+
+    /// Returns the days sorted by the number of days elapsed at `reference`,
+    /// fewest first, leaving the config untouched
+    ///
+    /// Same as `sorted_days(SortKey::Days, false, reference)`: the sort is
+    /// stable, days with the same number of days elapsed keep their config
+    /// order.
+    ///
+    /// ```
+    /// use achievements::config::Config;
+    /// use time::macros::datetime;
+    ///
+    /// let mut config = Config::default();
+    /// config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    /// config.set_day("Berlin Wall Fall", datetime!(1989-11-09 18:53 +01:00));
+    ///
+    /// let days = config.days_sorted_by_elapsed(datetime!(2024-01-01 0:00 UTC));
+    /// assert_eq!("Berlin Wall Fall", days[0].label);
+    /// assert_eq!("Moon landing", config.days[0].label);
+    /// ```
+    pub fn days_sorted_by_elapsed(&self, reference: OffsetDateTime) -> Vec<&Day> {
+        self.sorted_days(SortKey::Days, false, reference)
+    }
 }
 
 /// Read config file
@@ -72,86 +1193,190 @@ impl Config {
 ///       "label": "Berlin Wall Fall",
 ///       "date": "1989-11-09T18:53:00+01:00"
 ///     }
-///   ]
+///   ],
+///   "template": "{label} — {days}d {badges}"
 /// }
 /// ```
 ///
-/// Config file is at `~/.config/achievements/config.json`.
+/// The optional `template` is the default for the achievements text output,
+/// see `render::template` for the placeholders.
+///
+/// Config file is at the path returned by `resolve_path()`, by default
+/// `~/.config/achievements/config.json`.
 /// If the file doesn't exist an empty `Config` with no days is returned.
 ///
+/// Same as `Config::read_from_path(&resolve_path())`, creating the config
+/// directory if it doesn't exist.
+///
+/// # Panics
+/// Currently panics if it can't find the home directory, see
+/// `resolve_path()`.
+pub fn read() -> Result<Config, ConfigError> {
+    let config_file = resolve_path();
+    create_config_dir(&config_file)?;
+
+    Config::read_from_path(&config_file)
+}
+
+/// Write the config to the path returned by `resolve_path()`
+///
+/// The file is created if it doesn't exist, updated otherwise. Same as
+/// `config.write_to_path(&resolve_path())`.
+///
+/// # Panics
+/// Currently panics if it can't find the home directory, see
+/// `resolve_path()`.
+pub fn write(config: &Config) -> Result<(), ConfigError> {
+    config.write_to_path(&resolve_path())
+}
+
+/// Config path set with `set_path()`, e.g. from `--config`
+static PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Sets the path of the config file for the rest of the process
+///
+/// Takes precedence over the environment, see `resolve_path()`. Only the
+/// first call has an effect.
+pub fn set_path(path: impl Into<PathBuf>) {
+    let _ = PATH.set(path.into());
+}
+
+/// Resolves the path of the config file
+///
+/// In order of precedence:
+/// - the path set with `set_path()`, e.g. `--config`
+/// - `$ACHIEVEMENTS_CONFIG`, when set
+/// - `$XDG_CONFIG_HOME/achievements/config.json`, when `XDG_CONFIG_HOME` is set
+/// - `~/.config/achievements/config.json`
+///
+/// Empty environment variables are ignored.
+///
 /// # Panics
-/// Currently panics if it can't create the config directory (should return
+/// Currently panics if it can't find the home directory (should return
 /// a `Result::Err`).
+pub fn resolve_path() -> PathBuf {
+    if let Some(path) = PATH.get() {
+        return path.clone();
+    }
+    if let Some(config_file) = non_empty_env("ACHIEVEMENTS_CONFIG") {
+        return PathBuf::from(config_file);
+    }
+
+    let config_home = match non_empty_env("XDG_CONFIG_HOME") {
+        Some(config_home) => PathBuf::from(config_home),
+        None => {
+            let home = get_my_home().expect("Failed to get home directory");
+            let home = home.expect("No home directory");
+            home.join(".config")
+        }
+    };
+
+    config_home.join("achievements").join("config.json")
+}
+
+/// Path of the config backup: the config path with `.bak` appended,
+/// by default `~/.config/achievements/config.json.bak`
+pub fn backup_path() -> PathBuf {
+    let mut backup_path = resolve_path().into_os_string();
+    backup_path.push(".bak");
+    PathBuf::from(backup_path)
+}
+
+/// Copies the config file to `backup_path()`, overwriting any previous
+/// backup
 ///
-/// Currently panics if the config file isn't valid JSON (should return a
-/// `Result::Err`)
-pub fn read() -> Result<Config, ()> {
-    let config_dir = config_dir();
-    create_config_dir(&config_dir).expect("Failed to create config directory");
-
-    let config_file = format!("{config_dir}/config.json");
-    let config = if let Ok(reader) = File::open(config_file) {
-        serde_json::from_reader(reader).expect("Failed to parse config file")
-    } else {
-        Config::default()
-    };
+/// Does nothing if the config file doesn't exist yet.
+pub fn backup() -> Result<(), ConfigError> {
+    let config_file = resolve_path();
+    if config_file.exists() {
+        fs::copy(config_file, backup_path())?;
+    }
 
-    Ok(config)
+    Ok(())
 }
 
-/// Write the config to `~/.config/achievements/config.json`
-///
-/// The file is created if it doesn't exist, updated otherwise.
-///
-/// # Panics
-/// Currently panics if it can't create the config directory (should return
-/// a `Result::Err`).
+/// Restores the config file from `backup_path()`
 ///
-/// Currently panics if it can't create/open the config file (should return
-/// a `Result::Err`).
-///
-/// Currently panics if it can't write the config file (should return
-/// a `Result::Err`).
-///
-/// Currently panics if it can't flush the config file (should return
-/// a `Result::Err`).
-pub fn write(config: &Config) -> Result<(), ()> {
-    let config_dir = config_dir();
-    create_config_dir(&config_dir).expect("Failed to create config directory");
-
-    let config_file = format!("{config_dir}/config.json");
-    let file = File::create(config_file).expect("Failed to create config file");
+/// The backup is parsed before replacing the config file, so an invalid
+/// backup returns an error and leaves the config untouched. Returns a
+/// `ConfigError::Io` error if there is no backup.
+pub fn restore_backup() -> Result<Config, ConfigError> {
+    let backup_file = backup_path();
+    let config = Config::from_json_str(&fs::read_to_string(&backup_file)?)?;
+    fs::copy(backup_file, resolve_path())?;
 
-    let mut writer = BufWriter::new(file);
-    serde_json::to_writer_pretty(&mut writer, &config)
-        .expect("Failed to write JSON to config file");
-    writer.flush().expect("Failed to flush config file content");
+    Ok(config)
+}
 
-    Ok(())
+fn non_empty_env(key: &str) -> Option<OsString> {
+    env::var_os(key).filter(|value| !value.is_empty())
 }
 
-/// Creates the config directory if it doesn't exist
-fn create_config_dir(config_dir: &str) -> Result<(), std::io::Error> {
-    create_dir_all(config_dir)
+/// Whether the text matches the glob pattern, where `*` matches any
+/// sequence of characters and `?` any single character
+fn glob_matches(pattern: &[char], text: &[char]) -> bool {
+    match (pattern.first(), text.first()) {
+        (None, _) => text.is_empty(),
+        (Some('*'), _) => {
+            glob_matches(&pattern[1..], text)
+                || (!text.is_empty() && glob_matches(pattern, &text[1..]))
+        }
+        (Some('?'), Some(_)) => glob_matches(&pattern[1..], &text[1..]),
+        (Some(p), Some(t)) if p == t => glob_matches(&pattern[1..], &text[1..]),
+        _ => false,
+    }
 }
 
-fn config_dir() -> String {
-    let home = get_my_home().expect("Failed to get home directory");
-    let home = home.expect("No home directory");
-    let config_dir = home.join(".config").join("achievements");
+/// Levenshtein distances, in chars, between `a` and `b` and between `a`
+/// and the closest prefix of `b`
+fn edit_distances(a: &str, b: &str) -> (usize, usize) {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    let prefix = previous.iter().copied().min().unwrap_or_default();
+    (previous[b.len()], prefix)
+}
 
-    if let Some(config_dir) = config_dir.to_str() {
-        config_dir.to_string()
-    } else {
-        panic!("Failed to get config dir");
+/// Creates the directory containing the config file if it doesn't exist
+fn create_config_dir(config_file: &Path) -> Result<(), std::io::Error> {
+    match config_file.parent() {
+        Some(config_dir) => create_dir_all(config_dir),
+        None => Ok(()),
     }
 }
 
+#[test]
+fn set_day_if_absent_test() {
+    use time::macros::datetime;
+
+    let mut config = Config::default();
+    let date = datetime!(1944-04-25 12:00 +02:00);
+
+    assert!(config.set_day_if_absent(" Festa della liberazione\n", date));
+    assert_eq!(config.days, [Day::new("Festa della liberazione", date)]);
+
+    let other_date = datetime!(2000-01-31 12:00 +02:00);
+    assert!(!config.set_day_if_absent("festa della LIBERAZIONE", other_date));
+    assert_eq!(config.days, [Day::new("Festa della liberazione", date)]);
+
+    assert!(config.set_day_if_absent("A random day", other_date));
+    assert_eq!(config.days.len(), 2);
+}
+
 #[test]
 fn set_day_test() {
     use time::macros::datetime;
 
-    let mut config = Config { days: vec![] };
+    let mut config = Config::default();
 
     // add to empty config
     let first_label = "Festa della liberazione";
@@ -190,6 +1415,12 @@ fn set_day_test() {
     let second_day = config.days.last().expect("Should have a day in it");
     assert_eq!(second_day.label, second_label.trim());
     assert_eq!(second_day.date, second_date);
+
+    // labels differing only in case are the same day, which keeps its label
+    let day = config.set_day(" a RANDOM day", first_date);
+    assert_eq!(day.label, "A random day");
+    assert_eq!(config.days.len(), 2);
+    assert_eq!(config.days[1].date, first_date);
 }
 
 #[test]
@@ -201,15 +1432,10 @@ fn remove_day_test() {
 
     let mut config = Config {
         days: vec![
-            Day {
-                label: first_label.to_string(),
-                date: first_date,
-            },
-            Day {
-                label: "something".to_string(),
-                date: datetime!(2000-01-31 12:00 +02:00),
-            },
+            Day::new(first_label, first_date),
+            Day::new("something", datetime!(2000-01-31 12:00 +02:00)),
         ],
+        ..Default::default()
     };
 
     // Remove day with given label (case-insensitive, trimmed)
@@ -228,3 +1454,979 @@ fn remove_day_test() {
     let something_is_found = config.days.iter().find(|day| day.label == "something");
     assert!(something_is_found.is_none());
 }
+
+#[test]
+fn closest_labels_test() {
+    use time::macros::datetime;
+
+    let mut config = Config::default();
+    for label in [
+        "Moon landing",
+        "Moon base",
+        "Mars landing",
+        "Berlin Wall Fall",
+    ] {
+        config.set_day(label, datetime!(2000-01-01 0:00 UTC));
+    }
+
+    assert_eq!(config.closest_labels("moon landin", 3), ["Moon landing"]);
+    assert_eq!(
+        config.closest_labels("Moon", 3),
+        ["Moon base", "Moon landing"]
+    );
+    assert_eq!(
+        config.closest_labels("Mxxn landing", 3),
+        ["Moon landing", "Mars landing"]
+    );
+    assert_eq!(config.closest_labels("landing", 1), ["Moon landing"]);
+    assert!(config.closest_labels("Graduation", 3).is_empty());
+
+    assert_eq!(edit_distances("", "abc").0, 3);
+    assert_eq!(edit_distances("kitten", "sitting").0, 3);
+    assert_eq!(edit_distances("über", "uber").0, 1);
+    assert_eq!(edit_distances("berln wall", "berlin wall fall"), (6, 1));
+    assert_eq!(edit_distances("moon", "moon"), (0, 0));
+    assert_eq!(edit_distances("", "moon"), (4, 0));
+
+    // prefix matches
+    assert_eq!(config.closest_labels("berln wal", 3), ["Berlin Wall Fall"]);
+    assert_eq!(
+        config.closest_labels("moon lan", 3),
+        ["Moon landing", "Moon base"]
+    );
+}
+
+#[test]
+fn contains_test() {
+    use time::macros::datetime;
+
+    let mut config = Config::default();
+    config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+
+    assert!(config.contains("Moon landing"));
+    assert!(config.contains("  moon LANDING "));
+    assert!(!config.contains("Moon"));
+}
+
+#[test]
+fn remove_all_matching_test() {
+    use time::macros::datetime;
+
+    let mut config = Config::default();
+    config.set_day("Morning workout", datetime!(2024-01-01 7:00 UTC));
+    config.set_day("WORKOUT (evening)", datetime!(2024-01-01 19:00 UTC));
+    config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+
+    assert!(matches!(
+        config.remove_all_matching(""),
+        Err(ConfigError::EmptyPattern)
+    ));
+    assert!(matches!(
+        config.remove_all_matching(" \t "),
+        Err(ConfigError::EmptyPattern)
+    ));
+    assert_eq!(config.days.len(), 3);
+    assert_eq!(config.remove_all_matching("nothing").unwrap(), 0);
+    assert_eq!(config.remove_all_matching("?orning*").unwrap(), 1);
+    assert_eq!(config.remove_all_matching(" Workout ").unwrap(), 1);
+    assert_eq!(config.days.len(), 1);
+    assert_eq!(config.days[0].label, "Moon landing");
+}
+
+#[test]
+fn find_days_glob_test() {
+    use time::macros::datetime;
+
+    let matches = |pattern: &str, text: &str| {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let text: Vec<char> = text.chars().collect();
+        glob_matches(&pattern, &text)
+    };
+    assert!(matches("", ""));
+    assert!(matches("*", ""));
+    assert!(matches("a*c", "abbbc"));
+    assert!(matches("a?c", "abc"));
+    assert!(matches("**", "abc"));
+    assert!(!matches("a?c", "ac"));
+    assert!(!matches("abc", "abcd"));
+    assert!(!matches("", "a"));
+
+    let mut config = Config::default();
+    for label in [
+        "Old project Alpha",
+        "old project beta",
+        "Project Old",
+        "Old projects",
+    ] {
+        config.set_day(label, datetime!(2000-01-01 0:00 UTC));
+    }
+    let labels = |pattern| -> Vec<&str> {
+        config
+            .find_days_glob(pattern)
+            .iter()
+            .map(|day| day.label.as_str())
+            .collect()
+    };
+    assert_eq!(
+        labels(" OLD PROJECT * "),
+        ["Old project Alpha", "old project beta"]
+    );
+    assert_eq!(
+        labels("*old*"),
+        [
+            "Old project Alpha",
+            "old project beta",
+            "Project Old",
+            "Old projects"
+        ]
+    );
+    assert_eq!(labels("old project?"), ["Old projects"]);
+    assert_eq!(labels("project old"), ["Project Old"]);
+    assert!(labels("new *").is_empty());
+}
+
+#[test]
+fn search_test() {
+    use time::macros::datetime;
+
+    let mut config = Config::default();
+    config.days.push(Day {
+        description: Some("Fell on a Thursday".to_string()),
+        tags: vec!["history".to_string()],
+        ..Day::new("Berlin Wall Fall", datetime!(1989-11-09 18:53 +01:00))
+    });
+    config.days.push(Day {
+        tags: vec!["space".to_string(), "History".to_string()],
+        ..Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC))
+    });
+    config.set_day("Walled garden", datetime!(2000-01-01 0:00 UTC));
+
+    let search = |query: &str, fields: &[SearchField], regex: bool| -> Vec<String> {
+        let options = SearchOptions {
+            fields: fields.to_vec(),
+            regex,
+        };
+        config
+            .search(query, &options)
+            .expect("Valid query")
+            .iter()
+            .map(|day| day.label.clone())
+            .collect()
+    };
+    let label = &[SearchField::Label][..];
+
+    // substring, case-insensitive
+    assert_eq!(
+        search("wall", label, false),
+        ["Berlin Wall Fall", "Walled garden"]
+    );
+    assert_eq!(search("LANDING", label, false), ["Moon landing"]);
+    assert!(search("mars", label, false).is_empty());
+    assert_eq!(search("", label, false).len(), 3);
+
+    // regex
+    assert_eq!(search(r"^wall\w*", label, true), ["Walled garden"]);
+    assert_eq!(
+        search("fall$|moon", label, true),
+        ["Berlin Wall Fall", "Moon landing"]
+    );
+    assert!(search("wall.", label, false).is_empty());
+    assert!(config
+        .search(
+            "(unclosed",
+            &SearchOptions {
+                regex: true,
+                ..Default::default()
+            }
+        )
+        .is_err());
+
+    // fields
+    assert_eq!(search("thursday", label, false), Vec::<String>::new());
+    assert_eq!(
+        search("thursday", &[SearchField::Notes], false),
+        ["Berlin Wall Fall"]
+    );
+    assert_eq!(
+        search("history", &[SearchField::Tags], false),
+        ["Berlin Wall Fall", "Moon landing"]
+    );
+    assert_eq!(
+        search(
+            "^(space|moon)",
+            &[SearchField::Label, SearchField::Tags],
+            true
+        ),
+        ["Moon landing"]
+    );
+    assert!(search("garden", &[SearchField::Notes, SearchField::Tags], false).is_empty());
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn sort_by_label_locale_aware_test() {
+    use time::macros::datetime;
+
+    let labels = [
+        "Fête",
+        "été fini",
+        "Zürich",
+        "Été",
+        "Ångström",
+        "zoo",
+        "apple",
+    ];
+    let mut config = Config::default();
+    for label in labels {
+        config.set_day(label, datetime!(2020-01-01 12:00 UTC));
+    }
+
+    config.sort_by_label_locale_aware();
+    let sorted: Vec<&str> = config.days.iter().map(|day| day.label.as_str()).collect();
+    assert_eq!(
+        sorted,
+        [
+            "Ångström",
+            "apple",
+            "Été",
+            "été fini",
+            "Fête",
+            "zoo",
+            "Zürich"
+        ]
+    );
+
+    // Same order with SortKey::Label
+    config.days.reverse();
+    config.sort_days(SortKey::Label, false, datetime!(2024-01-01 12:00 UTC));
+    let sorted: Vec<&str> = config.days.iter().map(|day| day.label.as_str()).collect();
+    assert_eq!(sorted[..3], ["Ångström", "apple", "Été"]);
+}
+
+#[test]
+fn sort_days_test() {
+    use time::macros::datetime;
+
+    let day = |label: &str, date| Day::new(label, date);
+    let labels = |days: &[Day]| -> Vec<String> { days.iter().map(|d| d.label.clone()).collect() };
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let mut config = Config {
+        days: vec![
+            day("b", datetime!(2020-01-01 12:00 UTC)),
+            day("C", datetime!(2010-01-01 12:00 UTC)),
+            day("a", datetime!(2020-01-01 12:00 UTC)),
+        ],
+        ..Default::default()
+    };
+
+    config.sort_days(SortKey::Label, false, now);
+    assert_eq!(labels(&config.days), ["a", "b", "C"]);
+
+    config.sort_days(SortKey::Label, true, now);
+    assert_eq!(labels(&config.days), ["C", "b", "a"]);
+
+    // "b" and "a" have the same date, they keep their relative order
+    config.sort_days(SortKey::Date, false, now);
+    assert_eq!(labels(&config.days), ["C", "b", "a"]);
+
+    config.sort_days(SortKey::Days, false, now);
+    assert_eq!(labels(&config.days), ["b", "a", "C"]);
+
+    // ties keep their relative order also when reversed
+    config.sort_days(SortKey::Days, true, now);
+    assert_eq!(labels(&config.days), ["C", "b", "a"]);
+
+    // sorted_days() leaves the config untouched
+    let sorted: Vec<&str> = config
+        .sorted_days(SortKey::Label, false, now)
+        .iter()
+        .map(|d| d.label.as_str())
+        .collect();
+    assert_eq!(sorted, ["a", "b", "C"]);
+    assert_eq!(labels(&config.days), ["C", "b", "a"]);
+}
+
+#[test]
+fn days_sorted_by_elapsed_test() {
+    use time::macros::datetime;
+
+    let reference = datetime!(2024-01-01 12:00 UTC);
+    let mut config = Config::default();
+    config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    config.set_day("Same day, morning", datetime!(2023-12-31 14:00 UTC));
+    config.set_day("Same day, evening", datetime!(2023-12-31 20:00 UTC));
+    config.set_day("Future", datetime!(2030-01-01 0:00 UTC));
+    let original = config.days.clone();
+
+    let labels: Vec<&str> = config
+        .days_sorted_by_elapsed(reference)
+        .iter()
+        .map(|day| day.label.as_str())
+        .collect();
+    // ties (same number of days elapsed, 0 for future dates) keep the
+    // config order
+    assert_eq!(
+        labels,
+        [
+            "Same day, morning",
+            "Same day, evening",
+            "Future",
+            "Moon landing"
+        ]
+    );
+    assert_eq!(config.days, original);
+}
+
+#[test]
+fn write_to_string_test() {
+    use time::macros::datetime;
+
+    let mut config = Config::default();
+    config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+
+    let json = config
+        .write_to_string()
+        .expect("Failed to serialize config");
+    assert_eq!(
+        json,
+        r#"{
+  "days": [
+    {
+      "label": "Moon landing",
+      "date": "1969-07-20T20:17:40Z"
+    }
+  ]
+}"#
+    );
+
+    let mut written = Vec::new();
+    config
+        .write_to_writer(&mut written)
+        .expect("Failed to write config");
+    assert_eq!(String::from_utf8(written).unwrap(), json);
+
+    // round-trip
+    let parsed = Config::from_json_str(&json).expect("Failed to parse config");
+    assert_eq!(parsed.days.len(), 1);
+    assert_eq!(parsed.days[0].label, "Moon landing");
+    assert_eq!(parsed.days[0].date, datetime!(1969-07-20 20:17:40 UTC));
+
+    assert!(matches!(
+        Config::from_json_str("{\"days\": 42}"),
+        Err(ConfigError::Json(_))
+    ));
+}
+
+#[test]
+fn read_from_path_test() {
+    use time::macros::datetime;
+
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("profiles").join("work.json");
+
+    // missing file
+    let config = Config::read_from_path(&path).expect("Failed to read config");
+    assert_eq!(config, Config::default());
+
+    let mut config = Config::default();
+    config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    config.write_to_path(&path).expect("Failed to write config");
+    assert_eq!(Config::read_from_path(&path).unwrap(), config);
+
+    fs::write(&path, "not JSON").unwrap();
+    assert!(matches!(
+        Config::read_from_path(&path),
+        Err(ConfigError::Json(_))
+    ));
+    assert!(matches!(
+        Config::read_from_path(dir.path()),
+        Err(ConfigError::Io(_))
+    ));
+}
+
+#[test]
+fn validate_test() {
+    use time::macros::datetime;
+
+    let now = datetime!(2024-01-01 0:00 UTC);
+    let mut config = Config::default();
+    config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    assert_eq!(config.validate(now, MIN_DATE), vec![]);
+
+    config
+        .days
+        .push(Day::new(" ", datetime!(1989-11-09 18:53 +01:00)));
+    config.days.push(Day::new(
+        "moon landing ",
+        datetime!(1969-07-20 20:17:40 UTC),
+    ));
+    config
+        .days
+        .push(Day::new("Moon landing", datetime!(1970-01-01 0:00 UTC)));
+    config
+        .days
+        .push(Day::new("Launch", datetime!(2030-01-01 0:00 UTC)));
+    config
+        .days
+        .push(Day::new("Typo", datetime!(1024-01-01 0:00 UTC)));
+    assert_eq!(
+        config.validate(now, MIN_DATE),
+        vec![
+            ValidationIssue::EmptyLabel(1),
+            ValidationIssue::DuplicateLabel("Moon landing".to_string()),
+            ValidationIssue::FutureDate("Launch".to_string()),
+            ValidationIssue::OldDate("Typo".to_string(), MIN_DATE),
+        ]
+    );
+    assert_eq!(
+        ValidationIssue::OldDate("Typo".to_string(), MIN_DATE).to_string(),
+        "'Typo' has a date before 1900-01-01"
+    );
+
+    let min_date = datetime!(1970-01-01 0:00 UTC);
+    assert!(config
+        .validate(now, min_date)
+        .contains(&ValidationIssue::OldDate(
+            "Moon landing".to_string(),
+            min_date
+        )));
+}
+
+#[test]
+fn unknown_fields_test() {
+    let json = r#"{
+  "version": 1,
+  "theme": "dark",
+  "days": [
+    {"label": "Moon landing", "date": "1969-07-20T20:17:40Z", "tags": ["space"]},
+    {"label": "Run", "date": "2024-01-01T00:00:00Z", "colour": "red", "emoji": "🏃"}
+  ],
+  "badge_style": "ascii"
+}"#;
+    assert_eq!(
+        Config::unknown_fields(json).expect("Valid JSON"),
+        ["days[1].colour", "days[1].emoji", "theme"]
+    );
+    assert_eq!(
+        Config::unknown_fields(r#"{"days": []}"#).expect("Valid JSON"),
+        Vec::<String>::new()
+    );
+    assert!(Config::unknown_fields("not JSON").is_err());
+}
+
+#[test]
+fn fix_test() {
+    use time::macros::datetime;
+
+    let moon_landing = datetime!(1969-07-20 20:17:40 UTC);
+    let mut config = Config::default();
+    config.days.push(Day::new("Moon landing", moon_landing));
+    config.days.push(Day::new(" Moon landing ", moon_landing));
+    config
+        .days
+        .push(Day::new("Moon landing", datetime!(1970-01-01 0:00 UTC)));
+    config.days.push(Day::new(
+        "Berlin Wall Fall ",
+        datetime!(1989-11-09 18:53 +01:00),
+    ));
+
+    let summary = config.fix();
+    assert_eq!(
+        summary,
+        FixSummary {
+            trimmed: vec!["Moon landing".to_string(), "Berlin Wall Fall".to_string()],
+            deduped: vec!["Moon landing".to_string()],
+        }
+    );
+    // same label but different date is not an exact duplicate
+    let labels: Vec<&str> = config.days.iter().map(|day| day.label.as_str()).collect();
+    assert_eq!(labels, ["Moon landing", "Moon landing", "Berlin Wall Fall"]);
+
+    assert!(config.fix().is_empty());
+}
+
+#[test]
+fn duplicate_day_test() {
+    use time::macros::datetime;
+
+    let mut config = Config::default();
+    config.days.push(Day {
+        tags: vec!["work".to_string()],
+        ..Day::new("Meeting Bob", datetime!(2020-02-02 10:00 UTC))
+    });
+
+    let copy = config
+        .duplicate_day("meeting bob ", " Meeting Carol")
+        .expect("Failed to duplicate day");
+    assert_eq!(
+        copy,
+        &Day {
+            tags: vec!["work".to_string()],
+            ..Day::new("Meeting Carol", datetime!(2020-02-02 10:00 UTC))
+        }
+    );
+    assert_eq!(config.days.len(), 2);
+
+    assert!(matches!(
+        config.duplicate_day("Meeting Bob", "meeting carol"),
+        Err(ConfigError::Conflict(label)) if label == "meeting carol"
+    ));
+    assert!(matches!(
+        config.duplicate_day("Meeting Dave", "Meeting Eve"),
+        Err(ConfigError::NotFound(label)) if label == "Meeting Dave"
+    ));
+    assert_eq!(config.days.len(), 2);
+}
+
+#[test]
+fn backup_to_path_test() {
+    use time::macros::datetime;
+
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let dst = dir.path().join("backups").join("config.json");
+
+    let mut config = Config::default();
+    config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    config
+        .backup_to_path(&dst)
+        .expect("Failed to backup config");
+
+    let backup = fs::read_to_string(&dst).expect("Failed to read backup");
+    assert_eq!(backup, config.write_to_string().unwrap());
+
+    // overwrites a previous backup
+    config.set_day("Berlin Wall Fall", datetime!(1989-11-09 18:53 +01:00));
+    config
+        .backup_to_path(&dst)
+        .expect("Failed to backup config");
+    let backup = fs::read_to_string(&dst).expect("Failed to read backup");
+    assert_eq!(Config::from_json_str(&backup).unwrap().days.len(), 2);
+
+    // can't write to a directory
+    assert!(matches!(
+        config.backup_to_path(dir.path()),
+        Err(ConfigError::Io(_))
+    ));
+}
+
+#[test]
+fn stats_test() {
+    use time::macros::datetime;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let mut config = Config::default();
+
+    let stats = config.stats(now);
+    assert_eq!(stats.count, 0);
+    assert!(stats.oldest.is_none());
+    assert!(stats.newest.is_none());
+    assert_eq!(stats.total_days, 0);
+    assert_eq!(stats.average_days, 0);
+    assert_eq!(stats.tiers, Tiers::default());
+
+    // 20 years, 2 years, 45 days, 10 days and 0 days
+    config.set_day("Decades", datetime!(2004-01-06 12:00 UTC));
+    config.set_day("Years", datetime!(2022-01-01 12:00 UTC));
+    config.set_day("Month", datetime!(2023-11-17 12:00 UTC));
+    config.set_day("Week", datetime!(2023-12-22 12:00 UTC));
+    config.set_day("Today", datetime!(2024-01-01 11:00 UTC));
+
+    let stats = config.stats(now);
+    assert_eq!(stats.count, 5);
+    let oldest = stats.oldest.expect("Should have an oldest day");
+    assert_eq!(oldest.day.label, "Decades");
+    assert_eq!(oldest.interval.as_days(), 20 * 365);
+    let newest = stats.newest.expect("Should have a newest day");
+    assert_eq!(newest.day.label, "Today");
+    assert_eq!(newest.interval.as_days(), 0);
+    assert_eq!(stats.total_days, 7300 + 730 + 45 + 10);
+    assert_eq!(stats.average_days, 8085 / 5);
+    assert_eq!(
+        stats.tiers,
+        Tiers {
+            decade: 1,
+            year: 1,
+            month: 1,
+            week: 1,
+            day: 1,
+        }
+    );
+}
+
+#[test]
+fn merge_test() {
+    use time::macros::datetime;
+
+    let moon_landing = datetime!(1969-07-20 20:17:40 UTC);
+    let wall_fall = datetime!(1989-11-09 18:53 +01:00);
+    let config = || {
+        let mut config = Config::default();
+        config.set_day("Moon landing", moon_landing);
+        config
+    };
+    let days = || {
+        vec![
+            Day::new(" Berlin Wall Fall ", wall_fall),
+            Day::new("Moon landing", wall_fall),
+        ]
+    };
+
+    let mut skip = config();
+    let summary = skip.merge(days(), OnConflict::Skip).unwrap();
+    assert_eq!(summary.added, ["Berlin Wall Fall"]);
+    assert!(summary.updated.is_empty());
+    assert_eq!(summary.skipped, ["Moon landing"]);
+    assert_eq!(skip.days.len(), 2);
+    assert_eq!(skip.days[0].date, moon_landing);
+    assert_eq!(skip.days[1].label, "Berlin Wall Fall");
+
+    let mut overwrite = config();
+    let summary = overwrite.merge(days(), OnConflict::Overwrite).unwrap();
+    assert_eq!(summary.added, ["Berlin Wall Fall"]);
+    assert_eq!(summary.updated, ["Moon landing"]);
+    assert!(summary.skipped.is_empty());
+    assert_eq!(overwrite.days[0].date, wall_fall);
+
+    let mut abort = config();
+    assert!(matches!(
+        abort.merge(days(), OnConflict::Abort),
+        Err(ConfigError::Conflict(label)) if label == "Moon landing"
+    ));
+    assert_eq!(abort.days.len(), 1);
+
+    let mut invalid = config();
+    let mut days = days();
+    days.push(Day::new("  ", wall_fall));
+    assert!(matches!(
+        invalid.merge(days, OnConflict::Overwrite),
+        Err(ConfigError::InvalidDay { row: 3, .. })
+    ));
+    assert_eq!(invalid.days.len(), 1);
+    assert_eq!(invalid.days[0].date, moon_landing);
+
+    // Labels differing only in case and whitespace are the same day
+    let same_day = || vec![Day::new("  moon LANDING ", wall_fall)];
+    let mut skip = config();
+    let summary = skip.merge(same_day(), OnConflict::Skip).unwrap();
+    assert!(summary.added.is_empty());
+    assert_eq!(summary.skipped, ["Moon landing"]);
+    assert_eq!(skip.days.len(), 1);
+
+    let mut overwrite = config();
+    let summary = overwrite.merge(same_day(), OnConflict::Overwrite).unwrap();
+    assert_eq!(summary.updated, ["Moon landing"]);
+    assert_eq!(overwrite.days.len(), 1);
+    assert_eq!(overwrite.days[0].label, "Moon landing");
+    assert_eq!(overwrite.days[0].date, wall_fall);
+
+    let mut twice = Config::default();
+    let days = vec![Day::new("Run", wall_fall), Day::new("run ", moon_landing)];
+    let summary = twice.merge(days, OnConflict::Skip).unwrap();
+    assert_eq!(summary.added, ["Run"]);
+    assert_eq!(twice.days.len(), 1);
+}
+
+#[test]
+fn days_from_csv_test() {
+    use time::macros::datetime;
+
+    let csv = "label,date
+Moon landing,1969-07-20T20:17:40Z
+\"Berlin, Wall Fall\",1989-11-09
+";
+    let days = days_from_csv(csv.as_bytes()).expect("Failed to parse CSV");
+    assert_eq!(days.len(), 2);
+    assert_eq!(days[0].label, "Moon landing");
+    assert_eq!(days[0].date, datetime!(1969-07-20 20:17:40 UTC));
+    assert_eq!(days[1].label, "Berlin, Wall Fall");
+    assert_eq!(days[1].date, datetime!(1989-11-09 0:00 UTC));
+
+    let error = days_from_csv("label,date\nok,1969-07-20\nbad,yesterday\n".as_bytes())
+        .expect_err("Invalid date");
+    assert!(matches!(error, ConfigError::InvalidDay { row: 2, .. }));
+    assert!(error
+        .to_string()
+        .starts_with("invalid day on row 2: invalid date 'yesterday'"));
+
+    assert!(matches!(
+        days_from_csv("label,date\nonly label\n".as_bytes()),
+        Err(ConfigError::Csv(_) | ConfigError::InvalidDay { .. })
+    ));
+}
+
+#[test]
+fn toml_test() {
+    use time::macros::datetime;
+
+    let mut config = Config::default();
+    config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    config.days.push(Day {
+        description: Some("Fell on a Thursday".to_string()),
+        tags: vec!["history".to_string(), "europe".to_string()],
+        ..Day::new("Berlin Wall Fall", datetime!(1989-11-09 18:53 +01:00))
+    });
+
+    let toml = config.write_to_toml_string().expect("Failed to serialize");
+    assert_eq!(
+        toml,
+        r#"[[days]]
+label = "Moon landing"
+date = "1969-07-20T20:17:40Z"
+
+[[days]]
+label = "Berlin Wall Fall"
+date = "1989-11-09T18:53:00+01:00"
+description = "Fell on a Thursday"
+tags = [
+    "history",
+    "europe",
+]
+"#
+    );
+
+    let parsed = Config::from_toml_str(&toml).expect("Failed to parse");
+    assert_eq!(
+        parsed.write_to_string().unwrap(),
+        config.write_to_string().unwrap()
+    );
+
+    assert!(matches!(
+        Config::from_toml_str("days = 42"),
+        Err(ConfigError::Toml(_))
+    ));
+}
+
+#[test]
+fn badge_style_test() {
+    let config =
+        Config::from_json_str(r#"{"days": [], "badge_style": "ascii"}"#).expect("Failed to parse");
+    assert_eq!(config.badge_style, Some(BadgeStyle::Ascii));
+    assert_eq!(
+        config.write_to_string().unwrap(),
+        "{\n  \"days\": [],\n  \"badge_style\": \"ascii\"\n}"
+    );
+    assert_eq!(Config::from_json_str("{}").unwrap().badge_style, None);
+    assert!(Config::from_json_str(r#"{"badge_style": "Ascii"}"#).is_err());
+
+    let config = Config {
+        badge_style: Some(BadgeStyle::None),
+        ..Default::default()
+    };
+    let toml = config.write_to_toml_string().unwrap();
+    assert_eq!(Config::from_toml_str(&toml).unwrap(), config);
+}
+
+#[test]
+fn days_to_csv_test() {
+    use time::macros::datetime;
+
+    let days = vec![
+        Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC)),
+        Day {
+            description: Some("Fell on a \"Thursday\"".to_string()),
+            tags: vec!["history".to_string(), "europe".to_string()],
+            ..Day::new("Berlin, Wall Fall", datetime!(1989-11-09 18:53 +01:00))
+        },
+    ];
+
+    let mut csv = Vec::new();
+    days_to_csv(&days, &mut csv).expect("Failed to write CSV");
+    let csv = String::from_utf8(csv).unwrap();
+    assert_eq!(
+        csv,
+        r#"label,date,description,tags
+Moon landing,1969-07-20T20:17:40Z,,
+"Berlin, Wall Fall",1989-11-09T18:53:00+01:00,"Fell on a ""Thursday""","history,europe"
+"#
+    );
+
+    let parsed = days_from_csv(csv.as_bytes()).expect("Failed to parse CSV");
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0].description, None);
+    assert!(parsed[0].tags.is_empty());
+    assert_eq!(parsed[1].label, days[1].label);
+    assert_eq!(parsed[1].date, days[1].date);
+    assert_eq!(parsed[1].description, days[1].description);
+    assert_eq!(parsed[1].tags, days[1].tags);
+}
+
+#[test]
+fn elapsed_test() {
+    use time::macros::datetime;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let day = Day::new("Run", datetime!(2023-12-18 12:00 UTC));
+    assert_eq!(day.elapsed_at(now), Interval::from_days(14));
+    assert_eq!(
+        day.elapsed_at(datetime!(2023-12-18 11:00 UTC)),
+        Interval::from_days(0)
+    );
+
+    let future = Day::new(
+        "Launch",
+        OffsetDateTime::now_utc() + time::Duration::days(30),
+    );
+    assert_eq!(future.elapsed(), Interval::from_days(0));
+    let past = Day::new(
+        "Launch",
+        OffsetDateTime::now_utc() - time::Duration::days(30),
+    );
+    assert_eq!(past.elapsed(), Interval::from_days(30));
+}
+
+#[test]
+fn update_label_test() {
+    use time::macros::datetime;
+
+    let mut config = Config::default();
+    config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    config.set_day("Berlin Wall Fall", datetime!(1989-11-09 18:53 +01:00));
+
+    assert!(config
+        .update_label(" moon LANDING", " Apollo 11 ")
+        .expect("No conflict"));
+    assert_eq!(config.days[0].label, "Apollo 11");
+    assert_eq!(config.days[0].date, datetime!(1969-07-20 20:17:40 UTC));
+    assert!(!config
+        .update_label("Moon landing", "Moon")
+        .expect("No conflict"));
+
+    // Only the case changes
+    assert!(config
+        .update_label("apollo 11", "APOLLO 11")
+        .expect("No conflict"));
+    assert_eq!(config.days[0].label, "APOLLO 11");
+
+    assert!(matches!(
+        config.update_label("Apollo 11", " berlin wall fall"),
+        Err(ConfigError::DuplicateLabel(label)) if label == "berlin wall fall"
+    ));
+    assert_eq!(config.days[0].label, "APOLLO 11");
+    assert_eq!(config.days.len(), 2);
+}
+
+#[test]
+fn set_archived_test() {
+    use time::macros::datetime;
+
+    let mut config = Config::default();
+    config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+
+    config
+        .set_archived(" moon LANDING", true)
+        .expect("Day found");
+    assert!(config.days[0].archived);
+    config
+        .set_archived("Moon landing", false)
+        .expect("Day found");
+    assert!(!config.days[0].archived);
+    assert!(matches!(
+        config.set_archived("Mars landing", true),
+        Err(ConfigError::NotFound(label)) if label == "Mars landing"
+    ));
+}
+
+#[test]
+fn day_ord_test() {
+    use time::macros::datetime;
+
+    let moon_landing = Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    // same instant in another offset
+    let same_instant = Day::new("Apollo 11", datetime!(1969-07-20 21:17:40 +01:00));
+    let wall_fall = Day::new("Berlin Wall Fall", datetime!(1989-11-09 18:53 +01:00));
+
+    assert!(moon_landing < wall_fall);
+    assert!(same_instant < moon_landing);
+    assert_eq!(moon_landing.cmp(&moon_landing.clone()), Ordering::Equal);
+    let archived = Day {
+        archived: true,
+        ..moon_landing.clone()
+    };
+    assert!(moon_landing < archived);
+    // Consistent with Eq, down to the icon
+    let with_icon = Day {
+        icon: Some("🚀".to_string()),
+        ..moon_landing.clone()
+    };
+    assert_ne!(moon_landing, with_icon);
+    assert_ne!(moon_landing.cmp(&with_icon), Ordering::Equal);
+    assert!(moon_landing < with_icon);
+
+    let mut days = vec![
+        wall_fall.clone(),
+        moon_landing.clone(),
+        same_instant.clone(),
+    ];
+    days.sort();
+    assert_eq!(days, [same_instant, moon_landing, wall_fall]);
+}
+
+#[test]
+fn earliest_latest_test() {
+    use time::macros::datetime;
+
+    let mut config = Config::default();
+    assert!(config.earliest().is_none());
+    assert!(config.latest().is_none());
+
+    config.set_day("b", datetime!(2020-01-01 12:00 UTC));
+    config.set_day("c", datetime!(2010-01-01 12:00 UTC));
+    config.set_day("a", datetime!(2023-01-01 12:00 UTC));
+
+    assert_eq!(config.earliest().map(|day| day.label.as_str()), Some("c"));
+    assert_eq!(config.latest().map(|day| day.label.as_str()), Some("a"));
+}
+
+#[test]
+fn upcoming_test() {
+    use time::macros::datetime;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let mut config = Config::default();
+    assert!(config.upcoming(now, 30).is_empty());
+
+    // 5 years in 6 days
+    config.set_day("Wedding", datetime!(2019-01-08 12:00 UTC));
+    // 1000 days in 12 days
+    config.set_day("Quit smoking", datetime!(2021-04-18 12:00 UTC));
+    // 1 year today
+    config.set_day("Moved", datetime!(2023-01-01 12:00 UTC));
+    // 100 days in 40 days
+    config.set_day("Running", datetime!(2023-11-02 12:00 UTC));
+    // closed and archived days are skipped
+    config.days.push(Day {
+        end: Some(datetime!(2023-06-01 12:00 UTC)),
+        ..Day::new("Old job", datetime!(2023-01-01 12:00 UTC))
+    });
+    config.days.push(Day {
+        archived: true,
+        ..Day::new("Old hobby", datetime!(2023-01-01 12:00 UTC))
+    });
+    // as are days that haven't happened yet
+    config.set_day("Launch", datetime!(2024-01-20 12:00 UTC));
+
+    let upcoming = config.upcoming(now, 30);
+    let summary: Vec<_> = upcoming
+        .iter()
+        .map(|upcoming| {
+            (
+                upcoming.day.label.as_str(),
+                upcoming.milestone.to_string(),
+                upcoming.in_days(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("Moved", "1 year".to_string(), 0),
+            ("Wedding", "5 years".to_string(), 6),
+            ("Quit smoking", "1000 days".to_string(), 12),
+        ]
+    );
+    assert!(upcoming[0].is_today());
+
+    assert_eq!(config.upcoming(now, 40).len(), 4);
+    assert_eq!(config.upcoming(now, 0).len(), 1);
+}
@@ -0,0 +1,274 @@
+//! Health checks of the config file, used by `achievements doctor`
+//!
+//! Each check returns a `Check` with its status and a message, so they can
+//! be rendered (see `render::doctor()`) or tested on their own.
+
+use std::{fs, io::ErrorKind, path::Path};
+
+use time::OffsetDateTime;
+
+use crate::config::{self, Config};
+
+/// Latest version of the config schema
+///
+/// Config files without a `"version"` field are version 1.
+pub const SCHEMA_VERSION: u64 = 1;
+
+/// Outcome of a check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// Something worth fixing that doesn't stop the config from working
+    Warning,
+    /// The config can't be used as it is
+    Error,
+}
+
+/// Result of a single check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    /// What was checked, e.g. "Config file"
+    pub name: &'static str,
+    pub status: Status,
+    pub message: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: Status, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            message: message.into(),
+        }
+    }
+
+    /// Whether the check found no issues
+    pub fn is_ok(&self) -> bool {
+        self.status == Status::Ok
+    }
+}
+
+/// Runs all the checks on the config file at `path`
+///
+/// The checks that need the content are skipped when the file doesn't
+/// exist, and the days are only validated if the JSON is valid.
+pub fn run(path: &Path, now: OffsetDateTime) -> Vec<Check> {
+    let mut checks = vec![check_path(path)];
+    if !path.exists() {
+        return checks;
+    }
+
+    checks.push(check_permissions(path));
+    let json = match fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) => {
+            checks.push(Check::new(
+                "JSON",
+                Status::Error,
+                format!("can't read: {e}"),
+            ));
+            return checks;
+        }
+    };
+
+    checks.push(check_json(&json));
+    checks.push(check_schema_version(&json));
+    if let Ok(config) = Config::from_json_str(&json) {
+        checks.push(check_days(&config, now));
+    }
+
+    checks
+}
+
+/// Reports the config path and whether the file exists
+///
+/// A missing file is fine, it's created when the first day is added.
+pub fn check_path(path: &Path) -> Check {
+    let message = match fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() => format!("{} exists", path.display()),
+        Ok(_) => {
+            return Check::new(
+                "Config file",
+                Status::Error,
+                format!("{} is not a file", path.display()),
+            )
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            format!("{} doesn't exist yet, no days tracked", path.display())
+        }
+        Err(e) => {
+            return Check::new(
+                "Config file",
+                Status::Error,
+                format!("{}: {e}", path.display()),
+            )
+        }
+    };
+
+    Check::new("Config file", Status::Ok, message)
+}
+
+/// Warns if the config file is readable by other users
+///
+/// Always ok on platforms other than Unix.
+pub fn check_permissions(path: &Path) -> Check {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) => return Check::new("Permissions", Status::Error, e.to_string()),
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = metadata.permissions().mode() & 0o777;
+        if mode & 0o004 != 0 {
+            return Check::new(
+                "Permissions",
+                Status::Warning,
+                format!("{mode:o}, the config is readable by other users"),
+            );
+        }
+        Check::new("Permissions", Status::Ok, format!("{mode:o}"))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        Check::new("Permissions", Status::Ok, "not checked on this platform")
+    }
+}
+
+/// Checks the content is a valid config JSON
+pub fn check_json(json: &str) -> Check {
+    match Config::from_json_str(json) {
+        Ok(config) => Check::new(
+            "JSON",
+            Status::Ok,
+            format!("valid, {} days", config.days.len()),
+        ),
+        Err(e) => Check::new("JSON", Status::Error, e.to_string()),
+    }
+}
+
+/// Checks the `"version"` of the config is one this version supports
+pub fn check_schema_version(json: &str) -> Check {
+    let version = match serde_json::from_str::<serde_json::Value>(json) {
+        Ok(value) => value.get("version").cloned(),
+        Err(_) => return Check::new("Schema version", Status::Error, "not valid JSON"),
+    };
+
+    match version {
+        None => Check::new("Schema version", Status::Ok, "1 (implicit)"),
+        Some(version) => match version.as_u64() {
+            Some(version) if (1..=SCHEMA_VERSION).contains(&version) => {
+                Check::new("Schema version", Status::Ok, version.to_string())
+            }
+            Some(version) => Check::new(
+                "Schema version",
+                Status::Error,
+                format!("{version} is not supported, latest is {SCHEMA_VERSION}"),
+            ),
+            None => Check::new(
+                "Schema version",
+                Status::Error,
+                format!("{version} is not a version number"),
+            ),
+        },
+    }
+}
+
+/// Validates the days, see `Config::validate()`
+pub fn check_days(config: &Config, now: OffsetDateTime) -> Check {
+    let issues = config.validate(now, config::MIN_DATE);
+    if issues.is_empty() {
+        return Check::new("Days", Status::Ok, "no issues");
+    }
+
+    let issues: Vec<String> = issues.iter().map(|issue| issue.to_string()).collect();
+    Check::new("Days", Status::Warning, issues.join(", "))
+}
+
+#[test]
+fn check_path_test() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("config.json");
+
+    let check = check_path(&path);
+    assert!(check.is_ok());
+    assert!(check
+        .message
+        .ends_with("doesn't exist yet, no days tracked"));
+
+    fs::write(&path, "{}").unwrap();
+    assert!(check_path(&path).is_ok());
+
+    assert_eq!(check_path(dir.path()).status, Status::Error);
+}
+
+#[cfg(unix)]
+#[test]
+fn check_permissions_test() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("config.json");
+    fs::write(&path, "{}").unwrap();
+
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+    assert_eq!(
+        check_permissions(&path),
+        Check::new("Permissions", Status::Ok, "600")
+    );
+
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+    assert_eq!(check_permissions(&path).status, Status::Warning);
+}
+
+#[test]
+fn check_json_test() {
+    assert_eq!(
+        check_json(r#"{"days": []}"#),
+        Check::new("JSON", Status::Ok, "valid, 0 days")
+    );
+    assert_eq!(check_json("not JSON").status, Status::Error);
+    assert_eq!(check_json(r#"{"days": [{}]}"#).status, Status::Error);
+}
+
+#[test]
+fn check_schema_version_test() {
+    let status = |json: &str| check_schema_version(json).status;
+
+    assert_eq!(status(r#"{"days": []}"#), Status::Ok);
+    assert_eq!(status(r#"{"version": 1, "days": []}"#), Status::Ok);
+    assert_eq!(status(r#"{"version": 2, "days": []}"#), Status::Error);
+    assert_eq!(status(r#"{"version": "1", "days": []}"#), Status::Error);
+    assert_eq!(status("not JSON"), Status::Error);
+}
+
+#[test]
+fn check_days_test() {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 0:00 UTC);
+    let mut config = Config::default();
+    config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    assert!(check_days(&config, now).is_ok());
+
+    config
+        .days
+        .push(Day::new("Launch", datetime!(2030-01-01 0:00 UTC)));
+    config
+        .days
+        .push(Day::new("", datetime!(2000-01-01 0:00 UTC)));
+    assert_eq!(
+        check_days(&config, now),
+        Check::new(
+            "Days",
+            Status::Warning,
+            "'Launch' has a date in the future, day 3 has an empty label"
+        )
+    );
+}
@@ -0,0 +1,524 @@
+//! iCalendar (RFC 5545) export of the upcoming anniversaries and import
+//! of events, used by `achievements export --format ics` and `achievements
+//! import --format ics`
+//!
+//! Each anniversary is an all-day event, so calendar apps can remind of
+//! it. Events have UIDs derived from the label and year, so importing the
+//! calendar again updates the events instead of duplicating them.
+//!
+//! The import is not a full RFC 5545 parser: it unfolds the lines and
+//! scans the `SUMMARY` and `DTSTART` properties of each `VEVENT`.
+
+use time::{
+    format_description::FormatItem, macros::format_description, Date, Month, OffsetDateTime,
+    PrimitiveDateTime,
+};
+
+use crate::{
+    config::Day,
+    locale::{English, Locale},
+};
+
+const DATE_FORMAT: &[FormatItem<'static>] = format_description!("[year][month][day]");
+const DATE_TIME_FORMAT: &[FormatItem<'static>] =
+    format_description!("[year][month][day]T[hour][minute][second]Z");
+const LOCAL_DATE_TIME_FORMAT: &[FormatItem<'static>] =
+    format_description!("[year][month][day]T[hour][minute][second]");
+
+/// Longest content line in octets, longer lines are folded
+const MAX_LINE_OCTETS: usize = 75;
+
+/// All-day event of an anniversary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// Unique and stable across exports, see `uid()`
+    pub uid: String,
+    pub date: Date,
+    /// E.g. "Moon landing — 56 years"
+    pub summary: String,
+}
+
+/// Anniversaries of the days from today until `years` years from now
+///
+/// The anniversary of a day on February 29th is on February 28th in
+/// years that are not leap years. Days in the future and their first
+/// (zeroth) anniversary are skipped. Events are sorted by date, then by
+/// label.
+pub fn anniversaries(days: &[Day], now: OffsetDateTime, years: u16) -> Vec<Event> {
+    let today = now.date();
+    let end = anniversary_in(today, today.year() + i32::from(years));
+
+    let mut events = Vec::new();
+    for day in days {
+        let date = day.date.date();
+        for year in today.year()..=end.year() {
+            let anniversary = anniversary_in(date, year);
+            let count = year - date.year();
+            if count <= 0 || anniversary < today || anniversary >= end {
+                continue;
+            }
+
+            events.push(Event {
+                uid: uid(&day.label, year),
+                date: anniversary,
+                summary: format!("{} — {}", day.label, English.year(count as usize)),
+            });
+        }
+    }
+    events.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.summary.cmp(&b.summary)));
+
+    events
+}
+
+/// Renders the events as a VCALENDAR, with CRLF line endings
+///
+/// `stamp` is the creation time of the events (`DTSTAMP`), usually now.
+pub fn calendar(events: &[Event], stamp: OffsetDateTime) -> String {
+    let stamp = stamp
+        .to_offset(time::UtcOffset::UTC)
+        .format(DATE_TIME_FORMAT)
+        .expect("Failed to format date");
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//xoen//achievements//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    for event in events {
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}", escape(&event.uid)),
+            format!("DTSTAMP:{stamp}"),
+            format!("DTSTART;VALUE=DATE:{}", format_date(event.date)),
+            format!("DTEND;VALUE=DATE:{}", format_date(next_day(event.date))),
+            format!("SUMMARY:{}", escape(&event.summary)),
+            "TRANSP:TRANSPARENT".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line) + "\r\n").collect()
+}
+
+/// Event that couldn't be imported, see `days_from_ics()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedEvent {
+    /// Position of the event in the calendar, from 1
+    pub number: usize,
+    pub reason: String,
+}
+
+/// Reads the days from the events of an iCalendar
+///
+/// The `SUMMARY` of each `VEVENT` is the label and its `DTSTART` the date,
+/// either a date (midnight UTC) or a date-time. Date-times in UTC (ending
+/// with `Z`) are kept as they are, the others (floating or with a `TZID`)
+/// are taken as UTC as there's no time zone database, which only matters
+/// for the time of the day.
+///
+/// The events without a summary or a valid start are skipped, returned
+/// with the reason.
+pub fn days_from_ics(ics: &str) -> (Vec<Day>, Vec<SkippedEvent>) {
+    let mut days = Vec::new();
+    let mut skipped = Vec::new();
+
+    let mut event: Option<(Option<String>, Option<Property>)> = None;
+    let mut number = 0;
+    // components nested in the event, e.g. VALARM
+    let mut nested = 0;
+    for line in unfold(ics) {
+        let Some(property) = Property::parse(&line) else {
+            continue;
+        };
+        match (property.name.as_str(), &mut event) {
+            ("BEGIN", None) if property.value.eq_ignore_ascii_case("VEVENT") => {
+                number += 1;
+                event = Some((None, None));
+            }
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            ("END", Some((summary, start))) => {
+                match event_day(summary.take(), start.take()) {
+                    Ok(day) => days.push(day),
+                    Err(reason) => skipped.push(SkippedEvent { number, reason }),
+                }
+                event = None;
+            }
+            ("SUMMARY", Some((summary, _))) if nested == 0 => {
+                *summary = Some(unescape(&property.value));
+            }
+            ("DTSTART", Some((_, start))) if nested == 0 => *start = Some(property),
+            _ => {}
+        }
+    }
+
+    (days, skipped)
+}
+
+/// Content line of an iCalendar, e.g. `DTSTART;VALUE=DATE:20240720`
+#[derive(Debug)]
+struct Property {
+    /// Name in uppercase
+    name: String,
+    /// Parameters as written, e.g. `VALUE=DATE`
+    params: Vec<String>,
+    value: String,
+}
+
+impl Property {
+    /// Parses the line, `None` if it has no `:`
+    ///
+    /// The value starts after the first `:` not in a quoted parameter.
+    fn parse(line: &str) -> Option<Self> {
+        let mut quoted = false;
+        let colon = line.char_indices().find_map(|(i, c)| match c {
+            '"' => {
+                quoted = !quoted;
+                None
+            }
+            ':' if !quoted => Some(i),
+            _ => None,
+        })?;
+
+        let mut parts = line[..colon].split(';');
+        let name = parts.next()?.trim().to_uppercase();
+        Some(Self {
+            name,
+            params: parts.map(str::to_string).collect(),
+            value: line[colon + 1..].to_string(),
+        })
+    }
+
+    /// Whether the property has the parameter, e.g. `VALUE=DATE`
+    fn has_param(&self, param: &str) -> bool {
+        self.params.iter().any(|p| p.eq_ignore_ascii_case(param))
+    }
+}
+
+/// Day of an event, or why it can't be imported
+fn event_day(summary: Option<String>, start: Option<Property>) -> Result<Day, String> {
+    let label = summary
+        .filter(|summary| !summary.trim().is_empty())
+        .ok_or("no SUMMARY")?;
+    let start = start.ok_or(format!("'{label}' has no DTSTART"))?;
+    let date = parse_start(&start).ok_or(format!(
+        "'{label}' has an invalid DTSTART '{}'",
+        start.value
+    ))?;
+
+    Ok(Day::new(label.trim(), date))
+}
+
+/// Parses a `DTSTART` date or date-time, see `days_from_ics()`
+fn parse_start(start: &Property) -> Option<OffsetDateTime> {
+    let value = start.value.trim();
+    if start.has_param("VALUE=DATE") || value.len() == 8 {
+        let date = Date::parse(value, DATE_FORMAT).ok()?;
+        return Some(date.midnight().assume_utc());
+    }
+
+    let value = value.strip_suffix('Z').unwrap_or(value);
+    let date_time = PrimitiveDateTime::parse(value, LOCAL_DATE_TIME_FORMAT).ok()?;
+    Some(date_time.assume_utc())
+}
+
+/// Splits the iCalendar in content lines, joining the folded ones
+///
+/// A line starting with a space or a tab continues the previous one.
+fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines
+}
+
+/// Reverses `escape()`
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => unescaped.push('\n'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+/// UID of the anniversary of the label in the year
+///
+/// Derived from a hash of the label (FNV-1a, stable across Rust versions
+/// unlike `std`'s hasher) so it's the same in every export.
+fn uid(label: &str, year: i32) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in label.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    format!("{year}-{hash:016x}@achievements")
+}
+
+/// The date in the given year, February 28th for February 29th in years
+/// that are not leap years
+pub(crate) fn anniversary_in(date: Date, year: i32) -> Date {
+    date.replace_year(year).unwrap_or_else(|_| {
+        Date::from_calendar_date(year, Month::February, 28).expect("Invalid date")
+    })
+}
+
+fn next_day(date: Date) -> Date {
+    date.next_day().expect("Date out of range")
+}
+
+fn format_date(date: Date) -> String {
+    date.format(DATE_FORMAT).expect("Failed to format date")
+}
+
+/// Escapes a TEXT value: backslashes, semicolons, commas and newlines
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Folds a content line longer than 75 octets, continuing it on the next
+/// lines after a CRLF and a space
+///
+/// Lines are only split between characters, never inside a multi-byte
+/// UTF-8 sequence.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // the leading space counts towards the limit
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+
+    folded
+}
+
+#[test]
+fn anniversaries_test() {
+    use time::macros::datetime;
+
+    let days = [
+        Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC)),
+        Day::new("Leap day", datetime!(2020-02-29 0:00 UTC)),
+        Day::new("Launch", datetime!(2030-01-01 0:00 UTC)),
+        Day::new("Today", datetime!(2024-01-01 9:00 UTC)),
+    ];
+    let now = datetime!(2024-01-01 12:00 UTC);
+
+    let events = anniversaries(&days, now, 1);
+    let summaries: Vec<(Date, &str)> = events
+        .iter()
+        .map(|event| (event.date, event.summary.as_str()))
+        .collect();
+    assert_eq!(
+        summaries,
+        [
+            (datetime!(2024-02-29 0:00 UTC).date(), "Leap day — 4 years"),
+            (
+                datetime!(2024-07-20 0:00 UTC).date(),
+                "Moon landing — 55 years"
+            ),
+        ]
+    );
+
+    // not a leap year, after the first anniversary of "Today"
+    let events = anniversaries(&days, datetime!(2025-01-01 0:00 UTC), 1);
+    assert_eq!(events[0].summary, "Today — 1 year");
+    assert_eq!(events[1].date, datetime!(2025-02-28 0:00 UTC).date());
+    assert_eq!(events[1].summary, "Leap day — 5 years");
+
+    let events = anniversaries(&days, now, 3);
+    assert_eq!(events.len(), 8);
+    assert_eq!(events[7].summary, "Moon landing — 57 years");
+}
+
+/// Calendar with folded lines, both date forms and events that can't be
+/// imported
+#[cfg(test)]
+const TEST_CALENDAR: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example//Calendar//EN\r
+BEGIN:VEVENT\r
+UID:1@example.com\r
+DTSTART;VALUE=DATE:19690720\r
+SUMMARY:Moon landing\\, Apollo 11 and a very long summary that needs to be f\r
+ olded\r
+BEGIN:VALARM\r
+SUMMARY:Reminder\r
+DTSTART:20000101T000000Z\r
+END:VALARM\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+SUMMARY:Berlin Wall Fall\r
+DTSTART:19891109T175300Z\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+SUMMARY:Meeting\r
+DTSTART;TZID=\"Europe/Rome\":20240102T093000\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+DTSTART:20240101T000000Z\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+SUMMARY:Someday\r
+DTSTART:someday\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+SUMMARY:Whenever\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+#[test]
+fn days_from_ics_test() {
+    use time::macros::datetime;
+
+    let (days, skipped) = days_from_ics(TEST_CALENDAR);
+    assert_eq!(
+        days,
+        [
+            Day::new(
+                "Moon landing, Apollo 11 and a very long summary that needs to be folded",
+                datetime!(1969-07-20 0:00 UTC)
+            ),
+            Day::new("Berlin Wall Fall", datetime!(1989-11-09 17:53 UTC)),
+            Day::new("Meeting", datetime!(2024-01-02 9:30 UTC)),
+        ]
+    );
+    assert_eq!(
+        skipped,
+        [
+            SkippedEvent {
+                number: 4,
+                reason: "no SUMMARY".to_string()
+            },
+            SkippedEvent {
+                number: 5,
+                reason: "'Someday' has an invalid DTSTART 'someday'".to_string()
+            },
+            SkippedEvent {
+                number: 6,
+                reason: "'Whenever' has no DTSTART".to_string()
+            },
+        ]
+    );
+
+    // the export can be imported back
+    let events = anniversaries(&days[..1], datetime!(2024-01-01 0:00 UTC), 1);
+    let (days, skipped) = days_from_ics(&calendar(&events, datetime!(2024-01-01 0:00 UTC)));
+    assert!(skipped.is_empty());
+    assert_eq!(
+        days[0].label,
+        "Moon landing, Apollo 11 and a very long summary that needs to be folded — 55 years"
+    );
+}
+
+#[test]
+fn unescape_test() {
+    for text in ["Moon landing", r"Bread, milk; eggs\ham", "One\nTwo"] {
+        assert_eq!(unescape(&escape(text)), text);
+    }
+    assert_eq!(unescape(r"trailing\"), r"trailing\");
+}
+
+#[test]
+fn uid_test() {
+    assert_eq!(uid("Moon landing", 2024), uid("Moon landing", 2024));
+    assert_ne!(uid("Moon landing", 2024), uid("Moon landing", 2025));
+    assert_ne!(uid("Moon landing", 2024), uid("Moon-landing", 2024));
+    assert!(uid("Moon landing", 2024).starts_with("2024-"));
+}
+
+#[test]
+fn escape_test() {
+    assert_eq!(escape("Moon landing"), "Moon landing");
+    assert_eq!(
+        escape(r"Bread, milk; eggs\ham"),
+        r"Bread\, milk\; eggs\\ham"
+    );
+    assert_eq!(escape("One\r\nTwo"), r"One\nTwo");
+}
+
+#[test]
+fn fold_test() {
+    assert_eq!(fold("SUMMARY:short"), "SUMMARY:short");
+
+    let line = format!("SUMMARY:{}", "a".repeat(100));
+    let folded = fold(&line);
+    let lines: Vec<&str> = folded.split("\r\n").collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].len(), 75);
+    assert_eq!(lines[1], format!(" {}", "a".repeat(33)));
+    assert_eq!(folded.replace("\r\n ", ""), line);
+
+    // multi-byte characters are never split
+    let line = format!("SUMMARY:{}", "🚀".repeat(30));
+    let folded = fold(&line);
+    for line in folded.split("\r\n") {
+        assert!(line.len() <= 75);
+    }
+    assert_eq!(folded.replace("\r\n ", ""), line);
+}
+
+#[test]
+fn calendar_test() {
+    use time::macros::datetime;
+
+    let events = [Event {
+        uid: "2024-1@achievements".to_string(),
+        date: datetime!(2024-07-20 0:00 UTC).date(),
+        summary: "Moon landing, 1969 — 55 years".to_string(),
+    }];
+
+    assert_eq!(
+        calendar(&events, datetime!(2024-01-01 12:00 +01:00)),
+        "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//xoen//achievements//EN\r
+CALSCALE:GREGORIAN\r
+BEGIN:VEVENT\r
+UID:2024-1@achievements\r
+DTSTAMP:20240101T110000Z\r
+DTSTART;VALUE=DATE:20240720\r
+DTEND;VALUE=DATE:20240721\r
+SUMMARY:Moon landing\\, 1969 — 55 years\r
+TRANSP:TRANSPARENT\r
+END:VEVENT\r
+END:VCALENDAR\r
+"
+    );
+}
@@ -10,7 +10,7 @@
 //! ```Bash
 //! $ achievements
 //!
-//! Moon landing: 19984 days 💎💎💎💎💎
+//! Moon landing:     19984 days 💎💎💎💎💎
 //! Berlin Wall Fall: 12567 days 💎💎💎
 //! ```
 //!
@@ -25,11 +25,32 @@
 //! - the accurate number of days since the Berlin Wall fall should
 //!   be 12568 days but the tool reports 12567 days (1 day off)
 
-use std::fmt::Display;
+use core::{
+    cell::Cell,
+    fmt::{self, Display, Formatter},
+    ops::{Add, Sub},
+    str::FromStr,
+};
 
-use time::OffsetDateTime;
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+use time::{
+    format_description::{well_known::Rfc3339, FormatItem},
+    macros::format_description,
+    Date, OffsetDateTime,
+};
+use unicode_width::UnicodeWidthStr;
+
+use config::{Config, Day, SortKey};
+use locale::{English, IntervalLocale, Locale};
 
 pub mod config;
+pub mod doctor;
+pub mod ics;
+pub mod locale;
+pub mod render;
+pub mod tui;
+pub mod watch;
+pub mod webhook;
 
 pub const YEAR: usize = 365;
 pub const MONTH: usize = 30;
@@ -37,7 +58,10 @@ pub const WEEK: usize = 7;
 
 pub const DAY_IN_SECONDS: usize = 24 * 60 * 60;
 
-#[derive(PartialEq, Debug)]
+/// Round days milestones before the every 1000 days ones
+const DAY_MILESTONES: [usize; 3] = [100, 500, 1000];
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub enum IntervalEnum {
     Day(usize),
     Week(usize),
@@ -46,7 +70,56 @@ pub enum IntervalEnum {
     Decade(usize),
 }
 
-#[derive(PartialEq, Debug)]
+impl IntervalEnum {
+    /// Returns `true` for `IntervalEnum::Day`, whatever the number of days
+    ///
+    /// ```
+    /// use achievements::IntervalEnum;
+    ///
+    /// assert!(IntervalEnum::Day(3).is_day());
+    /// assert!(!IntervalEnum::Week(3).is_day());
+    /// ```
+    pub fn is_day(&self) -> bool {
+        matches!(self, IntervalEnum::Day(_))
+    }
+
+    /// Returns `true` for `IntervalEnum::Week`, see `is_day()`
+    pub fn is_week(&self) -> bool {
+        matches!(self, IntervalEnum::Week(_))
+    }
+
+    /// Returns `true` for `IntervalEnum::Month`, see `is_day()`
+    pub fn is_month(&self) -> bool {
+        matches!(self, IntervalEnum::Month(_))
+    }
+
+    /// Returns `true` for `IntervalEnum::Year`, see `is_day()`
+    pub fn is_year(&self) -> bool {
+        matches!(self, IntervalEnum::Year(_))
+    }
+
+    /// Returns `true` for `IntervalEnum::Decade`, see `is_day()`
+    pub fn is_decade(&self) -> bool {
+        matches!(self, IntervalEnum::Decade(_))
+    }
+}
+
+/// A number of days and the unit it's displayed in
+///
+/// `Interval`s are `Eq` and `Hash`, e.g. to group days by interval:
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use achievements::Interval;
+///
+/// let mut labels: HashMap<Interval, Vec<&str>> = HashMap::new();
+/// for (label, days) in [("Run", 14), ("Swim", 3), ("Bike", 14)] {
+///     labels.entry(Interval::from_days(days)).or_default().push(label);
+/// }
+/// assert_eq!(labels[&Interval::from_days(14)], ["Run", "Bike"]);
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub struct Interval {
     days: usize,
     e: IntervalEnum,
@@ -70,6 +143,153 @@ impl Interval {
         }
     }
 
+    /// Builds an `Interval` from a signed number of days, zero days when
+    /// it's negative, e.g. for a day in the future
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// assert_eq!(Interval::from_days(60), Interval::from_days_saturating(60));
+    /// assert_eq!(Interval::from_days(0), Interval::from_days_saturating(-3));
+    /// ```
+    pub fn from_days_saturating(days: i64) -> Self {
+        Self::from_days(usize::try_from(days.max(0)).unwrap_or(usize::MAX))
+    }
+
+    /// Builds the `Interval` between two dates, in either order
+    ///
+    /// ```
+    /// use achievements::Interval;
+    /// use time::macros::datetime;
+    ///
+    /// let moon_landing = datetime!(1969-07-20 20:17:40 UTC);
+    /// let berlin_wall_fall = datetime!(1989-11-09 18:53 +01:00);
+    /// let gap = Interval::between(moon_landing, berlin_wall_fall);
+    /// assert_eq!(7416, gap.as_days());
+    /// assert_eq!(gap, Interval::between(berlin_wall_fall, moon_landing));
+    /// ```
+    pub fn between(a: OffsetDateTime, b: OffsetDateTime) -> Self {
+        Self::from_days(days_since_at(a.min(b), a.max(b)))
+    }
+
+    /// Returns the number of days in the `Interval`
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// assert_eq!(60, Interval::from_days(60).as_days());
+    /// ```
+    pub fn as_days(&self) -> usize {
+        self.days
+    }
+
+    /// Returns the number of whole centuries in the `Interval`
+    pub fn century_count(&self) -> usize {
+        self.days / (100 * YEAR)
+    }
+
+    /// Returns the number of whole decades in the `Interval`
+    pub fn decade_count(&self) -> usize {
+        self.days / (10 * YEAR)
+    }
+
+    /// Returns the number of whole years in the `Interval`
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// let interval = Interval::from_days(800);
+    /// assert_eq!(2, interval.year_count());
+    /// assert_eq!(26, interval.month_count());
+    /// assert_eq!(114, interval.week_count());
+    /// ```
+    pub fn year_count(&self) -> usize {
+        self.days / YEAR
+    }
+
+    /// Returns the number of whole months in the `Interval`
+    pub fn month_count(&self) -> usize {
+        self.days / MONTH
+    }
+
+    /// Returns the number of whole weeks in the `Interval`
+    pub fn week_count(&self) -> usize {
+        self.days / WEEK
+    }
+
+    /// Returns the kind of `Interval`, i.e. the unit it's expressed in
+    ///
+    /// Useful to pattern-match on the unit, for example:
+    ///
+    /// ```
+    /// use achievements::{Interval, IntervalEnum};
+    ///
+    /// let interval = Interval::from_days(2 * 365);
+    /// if let IntervalEnum::Year(years) = interval.kind() {
+    ///     assert_eq!(2, *years);
+    /// }
+    /// assert_eq!(&IntervalEnum::Week(3), Interval::from_days(21).kind());
+    /// ```
+    pub fn kind(&self) -> &IntervalEnum {
+        &self.e
+    }
+
+    /// Returns `true` when the `Interval` is a whole number of weeks,
+    /// months, years or decades
+    ///
+    /// That is when `to_words()` names the unit instead of falling back to
+    /// days, e.g. to highlight milestones:
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// assert!(Interval::from_days(14).is_exact_unit());
+    /// assert!(Interval::from_days(365).is_exact_unit());
+    /// assert!(!Interval::from_days(15).is_exact_unit());
+    /// ```
+    pub fn is_exact_unit(&self) -> bool {
+        !self.e.is_day()
+    }
+
+    /// Returns `true` when the `Interval` is expressed in days, i.e. it's
+    /// not a whole number of any larger unit, see `kind()`
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// assert!(Interval::from_days(15).is_in_days());
+    /// assert!(Interval::from_days(14).is_in_weeks());
+    /// assert!(Interval::from_days(20 * 365).is_in_decades());
+    /// assert!(!Interval::from_days(20 * 365).is_in_years());
+    /// ```
+    pub fn is_in_days(&self) -> bool {
+        self.e.is_day()
+    }
+
+    /// Returns `true` when the `Interval` is expressed in weeks, see
+    /// `is_in_days()`
+    pub fn is_in_weeks(&self) -> bool {
+        self.e.is_week()
+    }
+
+    /// Returns `true` when the `Interval` is expressed in months, see
+    /// `is_in_days()`
+    pub fn is_in_months(&self) -> bool {
+        self.e.is_month()
+    }
+
+    /// Returns `true` when the `Interval` is expressed in years, see
+    /// `is_in_days()`
+    pub fn is_in_years(&self) -> bool {
+        self.e.is_year()
+    }
+
+    /// Returns `true` when the `Interval` is expressed in decades, see
+    /// `is_in_days()`
+    pub fn is_in_decades(&self) -> bool {
+        self.e.is_decade()
+    }
+
     fn enum_from_days(days: usize) -> IntervalEnum {
         if days == 0 {
             return IntervalEnum::Day(0);
@@ -113,143 +333,2011 @@ impl Interval {
     /// assert_eq!("10 days", a_week_and_a_bit.to_words());
     /// ```
     pub fn to_words(&self) -> String {
+        self.to_words_in(&English)
+    }
+
+    /// Converts an `Interval` to words, without the special messages
+    ///
+    /// Same as `to_words()` but "1 year" and "1 decade" are not followed by
+    /// a celebration message, useful when the output is parsed by other
+    /// tools.
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// let year = Interval::from_days(365);
+    /// assert_eq!("1 year, happy anniversary!", year.to_words());
+    /// assert_eq!("1 year", year.to_words_plain());
+    /// ```
+    pub fn to_words_plain(&self) -> String {
+        self.to_words_plain_in(&English)
+    }
+
+    /// Plain interval words for programmatic comparison, e.g. "1 year"
+    /// instead of "1 year, happy anniversary!"
+    ///
+    /// Same as `to_words_plain()`.
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// let decade = Interval::from_days(3650);
+    /// assert_eq!("1 decade, that's amazing", decade.to_words());
+    /// assert_eq!("1 decade", decade.display_plain());
+    /// ```
+    pub fn display_plain(&self) -> String {
+        self.to_words_plain()
+    }
+
+    /// Converts an `Interval` to abbreviated words
+    ///
+    /// Number followed by an abbreviated unit, suitable for narrow columns
+    /// and status bars: `d` for days, `wk` for weeks, `mo` for months, `y`
+    /// for years and `dec` for decades.
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// assert_eq!("10d", Interval::from_days(10).to_words_short());
+    /// assert_eq!("5wk", Interval::from_days(35).to_words_short());
+    /// assert_eq!("2mo", Interval::from_days(60).to_words_short());
+    /// assert_eq!("3y", Interval::from_days(3 * 365).to_words_short());
+    /// assert_eq!("3dec", Interval::from_days(30 * 365).to_words_short());
+    /// assert_eq!("0d", Interval::from_days(0).to_words_short());
+    /// ```
+    pub fn to_words_short(&self) -> String {
         match self.e {
-            IntervalEnum::Decade(1) => "1 decade, that's amazing".to_string(),
-            IntervalEnum::Decade(d) => format!("{d} decades"),
-            IntervalEnum::Year(1) => "1 year, happy anniversary!".to_string(),
-            IntervalEnum::Year(y) => format!("{y} years"),
-            IntervalEnum::Month(1) => "1 month".to_string(),
-            IntervalEnum::Month(m) => format!("{m} months"),
-            IntervalEnum::Week(1) => "1 week".to_string(),
-            IntervalEnum::Week(w) => format!("{w} weeks"),
-            IntervalEnum::Day(0) => "Recently".to_string(),
-            IntervalEnum::Day(1) => "1 day".to_string(),
-            IntervalEnum::Day(d) => format!("{d} days"),
+            IntervalEnum::Decade(d) => format!("{d}dec"),
+            IntervalEnum::Year(y) => format!("{y}y"),
+            IntervalEnum::Month(m) => format!("{m}mo"),
+            IntervalEnum::Week(w) => format!("{w}wk"),
+            IntervalEnum::Day(d) => format!("{d}d"),
         }
     }
 
-    fn badges(&self) -> String {
-        match self.days {
-            d if d >= 10 * YEAR => {
-                let decades = d / (10 * YEAR);
-                String::from("💎").repeat(decades)
-            }
-            d if d >= YEAR => {
-                let years = d / YEAR;
-                String::from("🌟").repeat(years)
-            }
-            d if d >= MONTH => {
-                let months = d / MONTH;
-                String::from("⭐").repeat(months)
-            }
-            d if d >= WEEK => {
-                let weeks = d / WEEK;
-                String::from("★").repeat(weeks)
-            }
-            d if d >= 1 => String::from("☆").repeat(d),
-            _ => String::new(),
+    /// Converts an `Interval` to words, spelling out counts from one to
+    /// ten
+    ///
+    /// Same as `to_words()` otherwise, for English prose. Larger counts
+    /// stay numerals, see `word_number()`.
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// assert_eq!("two weeks", Interval::from_days(14).to_words_spelled());
+    /// assert_eq!("one year, happy anniversary!", Interval::from_days(365).to_words_spelled());
+    /// assert_eq!("15 days", Interval::from_days(15).to_words_spelled());
+    /// ```
+    pub fn to_words_spelled(&self) -> String {
+        let words = self.to_words();
+        let (IntervalEnum::Decade(n)
+        | IntervalEnum::Year(n)
+        | IntervalEnum::Month(n)
+        | IntervalEnum::Week(n)
+        | IntervalEnum::Day(n)) = self.e;
+
+        match word_number(n) {
+            Some(word) => words.replacen(&n.to_string(), word, 1),
+            None => words,
         }
     }
-}
 
-impl Display for Interval {
-    /// Ability to display and convert `Interval` to strings
+    /// Converts an `Interval` to words in the given locale
     ///
-    /// "Badges" are displayed after the interval in words, for example:
-    /// - 2 days ☆☆
-    /// - 3 weeks ★★★
-    /// - 1 month ⭐
-    /// - 2 years 🌟🌟
-    /// - 1 decade 💎
+    /// The locale's special message, if any, is appended after a comma.
+    pub fn to_words_in(&self, locale: &dyn Locale) -> String {
+        let words = self.to_words_plain_in(locale);
+        match locale.message(&self.e) {
+            Some(message) => format!("{words}, {message}"),
+            None => words,
+        }
+    }
+
+    /// Converts an `Interval` to words in the given locale, without the
+    /// special messages
+    pub fn to_words_plain_in(&self, locale: &dyn Locale) -> String {
+        match self.e {
+            IntervalEnum::Decade(d) => locale.decade(d),
+            IntervalEnum::Year(y) => locale.year(y),
+            IntervalEnum::Month(m) => locale.month(m),
+            IntervalEnum::Week(w) => locale.week(w),
+            IntervalEnum::Day(0) => locale.recently(),
+            IntervalEnum::Day(d) => locale.day(d),
+        }
+    }
+
+    /// Converts an `Interval` to words using all the units it spans
     ///
-    /// Example:
+    /// Years, months, weeks and days, biggest first, skipping the units
+    /// with a zero count:
     ///
     /// ```
     /// use achievements::Interval;
     ///
-    /// // Intervals can be converted to `String``
-    /// let days = Interval::from_days(3);
-    /// assert_eq!("3 days ☆☆☆", days.to_string());
-    ///
-    /// // Intervals can be displayed
-    /// let weeks = Interval::from_days(14);
-    /// println!("{}", weeks);
-    /// // prints "2 weeks ★★"
+    /// let interval = Interval::from_days(2 * 365 + 3 * 30 + 9);
+    /// assert_eq!("2 years, 3 months, 1 week, 2 days", interval.to_compound_string());
+    /// assert_eq!("Recently", Interval::from_days(0).to_compound_string());
     /// ```
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {}", self.to_words(), self.badges())
+    pub fn to_compound_string(&self) -> String {
+        self.to_compound_string_in(&English)
     }
-}
 
-/// Returns the number of days since the given date
-///
-/// Implementation is very simple and assumes a day is 86400 seconds.
-/// This means the returned value could not be accurate but it is close
-/// enough.
-pub fn days_since(day: OffsetDateTime) -> usize {
-    let now = OffsetDateTime::now_utc();
-    let seconds_elapsed: time::Duration = now - day;
+    /// Same as `to_compound_string()` but in the given locale
+    pub fn to_compound_string_in(&self, locale: &dyn Locale) -> String {
+        if self.days == 0 {
+            return locale.recently();
+        }
 
-    seconds_elapsed.as_seconds_f64() as usize / DAY_IN_SECONDS
-}
+        let years = self.days / YEAR;
+        let months = self.days % YEAR / MONTH;
+        let weeks = self.days % YEAR % MONTH / WEEK;
+        let days = self.days % YEAR % MONTH % WEEK;
 
-#[test]
-fn test_from_days() {
-    const DECADE: usize = 10 * YEAR;
-    assert_eq!(IntervalEnum::Decade(3), Interval::from_days(3 * DECADE).e);
-    assert_eq!(IntervalEnum::Decade(1), Interval::from_days(1 * DECADE).e);
+        let units = [
+            (years, locale.year(years)),
+            (months, locale.month(months)),
+            (weeks, locale.week(weeks)),
+            (days, locale.day(days)),
+        ];
+        units
+            .into_iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(_, words)| words)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
 
-    assert_eq!(IntervalEnum::Year(33), Interval::from_days(33 * YEAR).e);
-    assert_eq!(IntervalEnum::Year(11), Interval::from_days(11 * YEAR).e);
-    assert_eq!(IntervalEnum::Year(5), Interval::from_days(5 * YEAR).e);
-    assert_eq!(IntervalEnum::Year(1), Interval::from_days(1 * YEAR).e);
+    /// Converts an `Interval` to words using all the units it spans,
+    /// followed by the total number of days, to check the units add up
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// assert_eq!(
+    ///     "2 years, 3 months, 1 week (827 days total)",
+    ///     Interval::from_days(827).to_verbose_string()
+    /// );
+    /// ```
+    pub fn to_verbose_string(&self) -> String {
+        self.to_verbose_string_in(&English)
+    }
 
-    assert_eq!(IntervalEnum::Month(5), Interval::from_days(5 * MONTH).e);
-    assert_eq!(IntervalEnum::Month(1), Interval::from_days(MONTH).e);
+    /// Same as `to_verbose_string()` but in the given locale
+    ///
+    /// ```
+    /// use achievements::{locale::Italian, Interval};
+    ///
+    /// assert_eq!(
+    ///     "2 anni, 3 mesi, 1 settimana (827 giorni in tutto)",
+    ///     Interval::from_days(827).to_verbose_string_in(&Italian)
+    /// );
+    /// ```
+    pub fn to_verbose_string_in(&self, locale: &dyn Locale) -> String {
+        format!(
+            "{} ({})",
+            self.to_compound_string_in(locale),
+            locale.total_days(self.days)
+        )
+    }
 
-    assert_eq!(IntervalEnum::Week(3), Interval::from_days(3 * WEEK).e);
-    assert_eq!(IntervalEnum::Week(1), Interval::from_days(WEEK).e);
+    /// Returns a formatter to customise how the `Interval` is displayed
+    ///
+    /// See `IntervalFormatterBuilder` for the available options.
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// let interval = Interval::from_days(3 * 365);
+    /// assert_eq!("3 years @@@", interval.formatter().ascii_badges().to_string());
+    /// assert_eq!("3 years 🌟", interval.formatter().max_badges(1).to_string());
+    /// ```
+    pub fn formatter(&self) -> IntervalFormatterBuilder<'_> {
+        IntervalFormatterBuilder::new(self)
+    }
 
-    assert_eq!(IntervalEnum::Day(15), Interval::from_days(15).e);
-    assert_eq!(IntervalEnum::Day(10), Interval::from_days(10).e);
-    assert_eq!(IntervalEnum::Day(5), Interval::from_days(5).e);
-    assert_eq!(IntervalEnum::Day(1), Interval::from_days(1).e);
-    assert_eq!(IntervalEnum::Day(0), Interval::from_days(0).e);
-}
+    /// Returns the next round milestone the `Interval` reaches
+    ///
+    /// Milestones are whole years (decades included) and the 100, 500 and
+    /// 1000 days marks (then every 1000 days). An `Interval` exactly on a
+    /// milestone returns it, zero days returns the first one.
+    ///
+    /// ```
+    /// use achievements::{Interval, Milestone};
+    ///
+    /// assert_eq!(Milestone::Days(100), Interval::from_days(90).next_milestone());
+    /// assert_eq!(Milestone::Years(1), Interval::from_days(300).next_milestone());
+    /// assert_eq!(Milestone::Days(1000), Interval::from_days(990).next_milestone());
+    /// assert_eq!(Milestone::Years(5), Interval::from_days(5 * 365).next_milestone());
+    /// ```
+    pub fn next_milestone(&self) -> Milestone {
+        let days = self.days.max(1);
+        let years = Milestone::Years(days.div_ceil(YEAR));
+        let day_mark = DAY_MILESTONES
+            .into_iter()
+            .find(|mark| *mark >= days)
+            .unwrap_or_else(|| days.div_ceil(1000) * 1000);
 
-#[test]
-fn test_to_words() {
-    assert_eq!("3 decades", Interval::from_days(3 * 10 * YEAR).to_words());
-    assert_eq!(
-        "1 decade, that's amazing",
-        Interval::from_days(10 * YEAR).to_words()
-    );
+        if day_mark < years.as_days() {
+            Milestone::Days(day_mark)
+        } else {
+            years
+        }
+    }
 
-    assert_eq!("33 years", Interval::from_days(33 * YEAR).to_words());
-    assert_eq!("11 years", Interval::from_days(11 * YEAR).to_words());
-    assert_eq!("5 years", Interval::from_days(5 * YEAR).to_words());
-    assert_eq!(
-        "1 year, happy anniversary!",
-        Interval::from_days(YEAR).to_words()
-    );
+    /// Returns the progress from the previous milestone to the next one,
+    /// from 0.0 to 1.0 when the `Interval` is exactly on a milestone
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// assert_eq!(0.0, Interval::from_days(0).progress());
+    /// // from 0 to 100 days
+    /// assert_eq!(0.5, Interval::from_days(50).progress());
+    /// assert_eq!(1.0, Interval::from_days(365).progress());
+    /// ```
+    pub fn progress(&self) -> f64 {
+        let milestone = self.next_milestone();
+        let previous = milestone.previous_days();
+        let elapsed = self.days.saturating_sub(previous);
+        elapsed as f64 / (milestone.as_days() - previous) as f64
+    }
 
-    assert_eq!("5 months", Interval::from_days(5 * MONTH).to_words());
-    assert_eq!("1 month", Interval::from_days(1 * MONTH).to_words());
+    /// Returns the round milestone the `Interval` is exactly at, if any
+    ///
+    /// That is a whole number of years or a round number of days, see
+    /// `next_milestone()`:
+    ///
+    /// ```
+    /// use achievements::{Interval, Milestone};
+    ///
+    /// assert_eq!(Some(Milestone::Years(1)), Interval::from_days(365).milestone());
+    /// assert_eq!(Some(Milestone::Days(1000)), Interval::from_days(1000).milestone());
+    /// assert_eq!(None, Interval::from_days(364).milestone());
+    /// assert_eq!(None, Interval::from_days(0).milestone());
+    /// ```
+    pub fn milestone(&self) -> Option<Milestone> {
+        let milestone = self.next_milestone();
+        (self.days > 0 && milestone.as_days() == self.days).then_some(milestone)
+    }
 
-    assert_eq!("3 weeks", Interval::from_days(3 * WEEK).to_words());
-    assert_eq!("1 week", Interval::from_days(1 * WEEK).to_words());
+    /// Returns the "badges" for the `Interval`
+    ///
+    /// One badge per whole unit of the biggest unit reached, e.g. a 💎 per
+    /// decade, a 🌟 per year, a ⭐ per month, a ★ per week or a ☆ per day.
+    /// Empty for a zero days `Interval`.
+    ///
+    /// `Display` shows the badges after `to_words()`:
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// let interval = Interval::from_days(15);
+    /// assert_eq!("★★", interval.badges());
+    /// assert_eq!(
+    ///     format!("{} {}", interval.to_words(), interval.badges()),
+    ///     interval.to_string()
+    /// );
+    /// ```
+    pub fn badges(&self) -> String {
+        self.badges_in_style(BadgeStyle::Emoji)
+    }
 
-    assert_eq!("15 days", Interval::from_days(15).to_words());
-    assert_eq!("10 days", Interval::from_days(10).to_words());
-    assert_eq!("5 days", Interval::from_days(5).to_words());
-    assert_eq!("1 day", Interval::from_days(1).to_words());
-    assert_eq!("Recently", Interval::from_days(0).to_words());
-}
+    /// Returns the "badges" for the `Interval` in the given style
+    ///
+    /// See `BadgeStyle` for the badges of each style.
+    ///
+    /// ```
+    /// use achievements::{BadgeStyle, Interval};
+    ///
+    /// let interval = Interval::from_days(15);
+    /// assert_eq!("★★", interval.badges_in_style(BadgeStyle::Emoji));
+    /// assert_eq!("++", interval.badges_in_style(BadgeStyle::Ascii));
+    /// assert_eq!("", interval.badges_in_style(BadgeStyle::None));
+    /// ```
+    pub fn badges_in_style(&self, style: BadgeStyle) -> String {
+        let (badge, count) = self.badge(style);
+        badge.repeat(count)
+    }
 
-#[test]
-fn test_to_string() {
-    assert_eq!(
-        "3 decades 💎💎💎",
+    /// Returns how many of the badges in the given style fit in `width`
+    /// terminal columns, at most all of them
+    ///
+    /// ```
+    /// use achievements::{BadgeStyle, Interval};
+    ///
+    /// let interval = Interval::from_days(50 * 365); // 💎💎💎💎💎
+    /// assert_eq!(3, interval.badges_fitting(BadgeStyle::Emoji, 7));
+    /// assert_eq!(5, interval.badges_fitting(BadgeStyle::Ascii, 7));
+    /// assert_eq!(0, interval.badges_fitting(BadgeStyle::None, 7));
+    /// ```
+    pub fn badges_fitting(&self, style: BadgeStyle, width: usize) -> usize {
+        let (badge, count) = self.badge(style);
+        match badge.width() {
+            0 => 0,
+            badge_width => count.min(width / badge_width),
+        }
+    }
+
+    /// Returns the badge in the given style and how many of them there are
+    fn badge(&self, style: BadgeStyle) -> (&'static str, usize) {
+        let badges = match style {
+            BadgeStyle::Emoji => ["💎", "🌟", "⭐", "★", "☆"],
+            BadgeStyle::Ascii => ["#", "@", "*", "+", "."],
+            BadgeStyle::None => return ("", 0),
+        };
+
+        let (tier, count) = self.tier();
+        (badges[tier], count)
+    }
+
+    /// Returns the badge tier, i.e. the biggest unit reached from 0 for
+    /// decades to 4 for days, and how many whole units of it there are
+    fn tier(&self) -> (usize, usize) {
+        match self {
+            i if i.decade_count() > 0 => (0, i.decade_count()),
+            i if i.year_count() > 0 => (1, i.year_count()),
+            i if i.month_count() > 0 => (2, i.month_count()),
+            i if i.week_count() > 0 => (3, i.week_count()),
+            i => (4, i.days),
+        }
+    }
+}
+
+/// Style of the badges shown after an `Interval`
+///
+/// | Unit   | `Emoji` | `Ascii` |
+/// |--------|---------|---------|
+/// | decade | 💎      | `#`     |
+/// | year   | 🌟      | `@`     |
+/// | month  | ⭐      | `*`     |
+/// | week   | ★       | `+`     |
+/// | day    | ☆       | `.`     |
+///
+/// `None` shows no badges at all.
+///
+/// Serialized in lowercase, e.g. `"ascii"`, as in the config
+/// `badge_style`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BadgeStyle {
+    #[default]
+    Emoji,
+    Ascii,
+    None,
+}
+
+/// Options for displaying an `Interval`
+///
+/// The defaults reproduce the plain `Interval` `Display`: emoji badges, no
+/// limit on the number of badges, no compound units and the special
+/// messages.
+///
+/// `set_default()` changes the options used by `Interval`'s `Display` (and
+/// so `to_string()`) on the current thread, without changing call sites,
+/// e.g. once at startup:
+///
+/// ```
+/// use achievements::{BadgeStyle, Interval, IntervalOptions};
+///
+/// let interval = Interval::from_days(14);
+/// assert_eq!("2 weeks ★★", interval.to_string());
+///
+/// IntervalOptions::set_default(IntervalOptions {
+///     badge_style: BadgeStyle::Ascii,
+///     ..Default::default()
+/// });
+/// assert_eq!("2 weeks ++", interval.to_string());
+///
+/// IntervalOptions::reset_default();
+/// assert_eq!("2 weeks ★★", interval.to_string());
+/// ```
+///
+/// To customise a single `Interval` use `Interval::formatter()` instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntervalOptions {
+    /// Style of the badges
+    pub badge_style: BadgeStyle,
+    /// Shows at most this many badges, `None` for no limit
+    pub max_badges: Option<usize>,
+    /// Shows all the units the interval spans, see
+    /// `Interval::to_compound_string()`
+    pub compound: bool,
+    /// Shows the special messages, e.g. "1 year, happy anniversary!"
+    pub messages: bool,
+    /// Shows abbreviated words, see `Interval::to_words_short()`
+    pub short: bool,
+}
+
+impl Default for IntervalOptions {
+    fn default() -> Self {
+        Self {
+            badge_style: BadgeStyle::Emoji,
+            max_badges: None,
+            compound: false,
+            messages: true,
+            short: false,
+        }
+    }
+}
+
+thread_local! {
+    static DEFAULT_OPTIONS: Cell<IntervalOptions> = Cell::new(IntervalOptions::default());
+}
+
+impl IntervalOptions {
+    /// Options used by `Interval`'s `Display` on the current thread
+    pub fn current() -> Self {
+        DEFAULT_OPTIONS.with(Cell::get)
+    }
+
+    /// Sets the options used by `Interval`'s `Display` on the current thread
+    pub fn set_default(options: IntervalOptions) {
+        DEFAULT_OPTIONS.with(|default| default.set(options));
+    }
+
+    /// Restores the default options on the current thread
+    pub fn reset_default() {
+        Self::set_default(IntervalOptions::default());
+    }
+
+    /// Interval in words in the given locale, according to the options
+    pub(crate) fn words(&self, interval: &Interval, locale: &dyn Locale) -> String {
+        if self.short {
+            interval.to_words_short()
+        } else if self.compound {
+            interval.to_compound_string_in(locale)
+        } else if self.messages {
+            interval.to_words_in(locale)
+        } else {
+            interval.to_words_plain_in(locale)
+        }
+    }
+
+    /// Badge of the interval and how many of them to show, according to
+    /// the options
+    fn badges(&self, interval: &Interval) -> (&'static str, usize) {
+        let (badge, count) = interval.badge(self.badge_style);
+        let count = self.max_badges.map_or(count, |max| count.min(max));
+        (badge, count)
+    }
+
+    /// Writes the badges of the interval without allocating, with a space
+    /// before them when `space` is `true`
+    fn write_badges(&self, f: &mut Formatter<'_>, interval: &Interval, space: bool) -> fmt::Result {
+        let (badge, count) = self.badges(interval);
+        if space {
+            f.write_str(" ")?;
+        }
+        for _ in 0..count {
+            f.write_str(badge)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Displays an `Interval` with customised options
+///
+/// Built with `Interval::formatter()`, see `IntervalFormatterBuilder`.
+/// Unlike `Interval`'s `Display`, there is no trailing space when there are
+/// no badges.
+#[derive(Clone, Copy)]
+pub struct IntervalFormatter<'a> {
+    interval: &'a Interval,
+    locale: &'a dyn Locale,
+    options: IntervalOptions,
+}
+
+impl IntervalFormatter<'_> {
+    /// The badges shown after the words, e.g. to put them in a column
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// let interval = Interval::from_days(50 * 365);
+    /// let formatter = interval.formatter().max_badges(2).build();
+    /// assert_eq!("💎💎", formatter.badges());
+    /// ```
+    pub fn badges(&self) -> String {
+        let (badge, count) = self.options.badges(self.interval);
+        badge.repeat(count)
+    }
+}
+
+impl Display for IntervalFormatter<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.options.words(self.interval, self.locale))?;
+
+        let (_, count) = self.options.badges(self.interval);
+        self.options.write_badges(f, self.interval, count > 0)
+    }
+}
+
+/// Builds an `IntervalFormatter`
+///
+/// By default the `Interval` is displayed in English, with emoji badges,
+/// no limit on the number of badges, no compound units and with the special
+/// messages:
+///
+/// ```
+/// use achievements::{locale::English, Interval};
+///
+/// let interval = Interval::from_days(365 + 40);
+/// let formatted = interval
+///     .formatter()
+///     .locale(&English)
+///     .compound(true)
+///     .ascii_badges()
+///     .to_string();
+/// assert_eq!("1 year, 1 month, 1 week, 3 days @", formatted);
+/// ```
+#[derive(Clone, Copy)]
+pub struct IntervalFormatterBuilder<'a> {
+    formatter: IntervalFormatter<'a>,
+}
+
+impl<'a> IntervalFormatterBuilder<'a> {
+    fn new(interval: &'a Interval) -> Self {
+        Self {
+            formatter: IntervalFormatter {
+                interval,
+                locale: &English,
+                options: IntervalOptions::default(),
+            },
+        }
+    }
+
+    /// Locale of the interval words
+    pub fn locale(mut self, locale: &'a dyn Locale) -> Self {
+        self.formatter.locale = locale;
+        self
+    }
+
+    /// Style of the badges
+    pub fn badge_style(mut self, badge_style: BadgeStyle) -> Self {
+        self.formatter.options.badge_style = badge_style;
+        self
+    }
+
+    /// Shortcut for `badge_style(BadgeStyle::Ascii)`
+    pub fn ascii_badges(self) -> Self {
+        self.badge_style(BadgeStyle::Ascii)
+    }
+
+    /// Shortcut for `badge_style(BadgeStyle::None)`
+    pub fn no_badges(self) -> Self {
+        self.badge_style(BadgeStyle::None)
+    }
+
+    /// Shows at most `max_badges` badges
+    pub fn max_badges(mut self, max_badges: usize) -> Self {
+        self.formatter.options.max_badges = Some(max_badges);
+        self
+    }
+
+    /// Shows at most the badges fitting on a line `terminal_width` columns
+    /// wide, after a label column `label_width` columns wide and the
+    /// interval words (each followed by a space)
+    ///
+    /// Set the other options first, the words are measured as they are
+    /// displayed. A lower `max_badges()` is kept.
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// let interval = Interval::from_days(50 * 365);
+    /// let formatter = interval.formatter().auto_cap_badges(30, 13).build();
+    /// // "Moon landing: 5 decades " leaves 6 columns, 3 💎
+    /// assert_eq!("5 decades 💎💎💎", formatter.to_string());
+    /// ```
+    pub fn auto_cap_badges(mut self, terminal_width: u16, label_width: usize) -> Self {
+        let formatter = &self.formatter;
+        let words = formatter
+            .options
+            .words(formatter.interval, formatter.locale);
+        let available =
+            usize::from(terminal_width).saturating_sub(label_width + 1 + words.width() + 1);
+        let fitting = formatter
+            .interval
+            .badges_fitting(formatter.options.badge_style, available);
+
+        let max_badges = formatter
+            .options
+            .max_badges
+            .map_or(fitting, |max| max.min(fitting));
+        self.formatter.options.max_badges = Some(max_badges);
+        self
+    }
+
+    /// Shows all the units the interval spans, see
+    /// `Interval::to_compound_string()`
+    pub fn compound(mut self, compound: bool) -> Self {
+        self.formatter.options.compound = compound;
+        self
+    }
+
+    /// Shows the special messages, e.g. "1 year, happy anniversary!"
+    pub fn messages(mut self, messages: bool) -> Self {
+        self.formatter.options.messages = messages;
+        self
+    }
+
+    /// Shows abbreviated words, see `Interval::to_words_short()`
+    pub fn short(mut self, short: bool) -> Self {
+        self.formatter.options.short = short;
+        self
+    }
+
+    /// Builds the `IntervalFormatter`
+    pub fn build(self) -> IntervalFormatter<'a> {
+        self.formatter
+    }
+}
+
+impl Display for IntervalFormatterBuilder<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.formatter.fmt(f)
+    }
+}
+
+impl Display for Interval {
+    /// Ability to display and convert `Interval` to strings
+    ///
+    /// "Badges" are displayed after the interval in words, for example:
+    /// - 2 days ☆☆
+    /// - 3 weeks ★★★
+    /// - 1 month ⭐
+    /// - 2 years 🌟🌟
+    /// - 1 decade 💎
+    ///
+    /// The words and badges follow the `IntervalOptions` of the current
+    /// thread, see `IntervalOptions::set_default()`. There are no badges,
+    /// nor the space before them, with `BadgeStyle::None`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// // Intervals can be converted to `String``
+    /// let days = Interval::from_days(3);
+    /// assert_eq!("3 days ☆☆☆", days.to_string());
+    ///
+    /// // Intervals can be displayed
+    /// let weeks = Interval::from_days(14);
+    /// println!("{}", weeks);
+    /// // prints "2 weeks ★★"
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_padded(f, 0)
+    }
+}
+
+impl Interval {
+    /// Same as `Display` but with the words right-aligned to `width`
+    /// terminal columns, padded with spaces on the left
+    ///
+    /// To align the intervals in a column, `width` is the widest of their
+    /// words, computed before rendering any of them. Words wider than
+    /// `width` are not truncated.
+    ///
+    /// ```
+    /// use std::fmt::{self, Display, Formatter};
+    ///
+    /// use achievements::Interval;
+    ///
+    /// struct Column<'a>(&'a Interval, usize);
+    ///
+    /// impl Display for Column<'_> {
+    ///     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    ///         self.0.fmt_padded(f, self.1)
+    ///     }
+    /// }
+    ///
+    /// let weeks = Interval::from_days(14);
+    /// assert_eq!("  2 weeks ★★", Column(&weeks, 9).to_string());
+    /// ```
+    pub fn fmt_padded(&self, f: &mut Formatter<'_>, width: usize) -> fmt::Result {
+        let options = IntervalOptions::current();
+        let words = options.words(self, IntervalLocale::current());
+        let padding = width.saturating_sub(words.width());
+        write!(f, "{}{words}", " ".repeat(padding))?;
+        options.write_badges(f, self, options.badge_style != BadgeStyle::None)
+    }
+}
+
+impl Add<usize> for Interval {
+    type Output = Interval;
+
+    /// Adds a number of days to the `Interval`
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// let today = Interval::from_days(6);
+    /// let tomorrow = today + 1;
+    /// assert_eq!("1 week", tomorrow.to_words());
+    /// ```
+    fn add(self, days: usize) -> Self::Output {
+        Interval::from_days(self.days + days)
+    }
+}
+
+impl Sub<usize> for Interval {
+    type Output = Option<Interval>;
+
+    /// Subtracts a number of days from the `Interval`
+    ///
+    /// Returns `None` if the result would be negative.
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// let week = Interval::from_days(7);
+    /// assert_eq!(Some(Interval::from_days(6)), week.clone() - 1);
+    /// assert_eq!(None, week - 8);
+    /// ```
+    fn sub(self, days: usize) -> Self::Output {
+        self.days.checked_sub(days).map(Interval::from_days)
+    }
+}
+
+impl Add<Interval> for Interval {
+    type Output = Interval;
+
+    /// Combines two intervals by adding their number of days
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// let combined = Interval::from_days(20) + Interval::from_days(10);
+    /// assert_eq!("1 month", combined.to_words());
+    /// ```
+    fn add(self, other: Interval) -> Self::Output {
+        Interval::from_days(self.days + other.days)
+    }
+}
+
+impl From<u32> for Interval {
+    /// Same as `Interval::from_days()`
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// let days: u32 = 14;
+    /// assert_eq!(Interval::from_days(14), Interval::from(days));
+    /// ```
+    fn from(days: u32) -> Self {
+        Interval::from_days(days as usize)
+    }
+}
+
+impl From<u64> for Interval {
+    /// Same as `Interval::from_days()`
+    ///
+    /// On 32-bit targets the number of days is truncated to `usize` (`as`
+    /// cast), which only matters beyond ~11 million years.
+    fn from(days: u64) -> Self {
+        Interval::from_days(days as usize)
+    }
+}
+
+impl From<Interval> for usize {
+    /// Same as `Interval::as_days()`
+    fn from(interval: Interval) -> Self {
+        interval.as_days()
+    }
+}
+
+/// Error parsing an `Interval`, see `Interval::from_str()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseIntervalError {
+    input: String,
+}
+
+impl Display for ParseIntervalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid interval '{}', expected e.g. \"3 weeks\" or \"3w\"",
+            self.input
+        )
+    }
+}
+
+impl std::error::Error for ParseIntervalError {}
+
+impl FromStr for Interval {
+    type Err = ParseIntervalError;
+
+    /// Parses an `Interval` from words or abbreviated words
+    ///
+    /// Accepts the output of `to_words()` (special messages included),
+    /// `to_words_short()` and `to_compound_string()`, ignoring case, e.g.
+    /// "3 weeks", "3w", "3wk", "1 year, happy anniversary!" or "2 years,
+    /// 1 month". Units are `d`/`day`, `w`/`wk`/`week`, `mo`/`month`,
+    /// `y`/`year` and `dec`/`decade`, in singular or plural.
+    ///
+    /// ```
+    /// use achievements::Interval;
+    ///
+    /// assert_eq!(Ok(Interval::from_days(21)), "3 weeks".parse());
+    /// assert_eq!(Ok(Interval::from_days(21)), "3w".parse());
+    /// assert_eq!(Ok(Interval::from_days(365)), "1 year, happy anniversary!".parse());
+    /// assert!("3 fortnights".parse::<Interval>().is_err());
+    ///
+    /// let interval = Interval::from_days(2 * 365 + 3 * 30 + 9);
+    /// assert_eq!(Ok(interval.clone()), interval.to_compound_string().parse());
+    /// ```
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let error = || ParseIntervalError {
+            input: input.to_string(),
+        };
+
+        let parts: Vec<&str> = input.split(',').map(str::trim).collect();
+        let mut days: usize = 0;
+        for (i, part) in parts.iter().enumerate() {
+            match parse_interval_part(part) {
+                Some(part_days) => days = days.checked_add(part_days).ok_or_else(error)?,
+                // the special message after the words, e.g. "happy anniversary!"
+                None if i > 0 && i == parts.len() - 1 => {
+                    let message = English.message(&Interval::enum_from_days(days));
+                    if message.is_none_or(|message| !message.eq_ignore_ascii_case(part)) {
+                        return Err(error());
+                    }
+                }
+                None => return Err(error()),
+            }
+        }
+
+        Ok(Interval::from_days(days))
+    }
+}
+
+/// Number of days of a single number and unit, e.g. "3 weeks" or "3w"
+fn parse_interval_part(part: &str) -> Option<usize> {
+    let part = part.to_lowercase();
+    if part == English.recently().to_lowercase() {
+        return Some(0);
+    }
+
+    let unit_start = part.find(|c: char| !c.is_ascii_digit())?;
+    let count: usize = part[..unit_start].parse().ok()?;
+    let unit_days = match part[unit_start..].trim_start() {
+        "d" | "day" | "days" => 1,
+        "w" | "wk" | "week" | "weeks" => WEEK,
+        "mo" | "month" | "months" => MONTH,
+        "y" | "year" | "years" => YEAR,
+        "dec" | "decade" | "decades" => 10 * YEAR,
+        _ => return None,
+    };
+
+    count.checked_mul(unit_days)
+}
+
+impl Serialize for Interval {
+    /// Serializes an `Interval` as its number of days, words and badges
+    ///
+    /// For example `Interval::from_days(14)` is serialized as:
+    ///
+    /// ```JSON
+    /// {"days": 14, "words": "2 weeks", "badges": "★★"}
+    /// ```
+    ///
+    /// The words are in the locale of `IntervalLocale::current()`, and
+    /// abbreviated (see `to_words_short()`), compound or without special
+    /// messages according to `IntervalOptions::current()`, as are the style
+    /// and number of the badges (empty with `BadgeStyle::None`).
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let options = IntervalOptions::current();
+        let words = options.words(self, IntervalLocale::current());
+
+        let mut state = serializer.serialize_struct("Interval", 3)?;
+        state.serialize_field("days", &self.days)?;
+        state.serialize_field("words", &words)?;
+        let (badge, count) = options.badges(self);
+        state.serialize_field("badges", &badge.repeat(count))?;
+        state.end()
+    }
+}
+
+/// A day from the config with the interval elapsed since then
+///
+/// Serializes as a flat object with the day label and date followed by the
+/// interval days, words and badges, for example:
+///
+/// ```JSON
+/// {
+///   "label": "Moon landing",
+///   "date": "1969-07-20T20:17:40Z",
+///   "days": 19985,
+///   "words": "19985 days",
+///   "badges": "💎💎💎💎💎"
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct Achievement {
+    #[serde(flatten)]
+    pub day: Day,
+    #[serde(flatten)]
+    pub interval: Interval,
+}
+
+impl Achievement {
+    /// Builds an `Achievement` computing the interval from the day to `now`
+    pub fn new(day: Day, now: OffsetDateTime) -> Self {
+        Self {
+            interval: day.elapsed_at(now),
+            day,
+        }
+    }
+
+    /// Returns the round milestone the day reaches today, if any, see
+    /// `Interval::milestone()`
+    ///
+    /// Archived days and closed periods don't reach milestones.
+    pub fn milestone_today(&self) -> Option<Milestone> {
+        if self.day.archived || self.day.is_closed() {
+            return None;
+        }
+        self.interval.milestone()
+    }
+}
+
+/// Aggregated numbers about the days in a config, see `Config::stats()`
+#[derive(Debug, Clone, Serialize)]
+pub struct Stats {
+    /// Number of days in the config
+    pub count: usize,
+    /// Achievement of the day with the earliest date
+    pub oldest: Option<Achievement>,
+    /// Achievement of the day with the latest date
+    pub newest: Option<Achievement>,
+    /// Sum of the days elapsed since each day
+    pub total_days: usize,
+    /// Average days elapsed (rounded down), 0 without days
+    pub average_days: usize,
+    /// How many days are in each badge tier
+    pub tiers: Tiers,
+}
+
+/// Number of intervals per biggest unit reached, i.e. per badge tier
+///
+/// Intervals of zero days count as `day`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Tiers {
+    pub decade: usize,
+    pub year: usize,
+    pub month: usize,
+    pub week: usize,
+    pub day: usize,
+}
+
+impl Tiers {
+    /// Counts the interval in its tier
+    pub fn add(&mut self, interval: &Interval) {
+        let count = match interval.tier() {
+            (0, _) => &mut self.decade,
+            (1, _) => &mut self.year,
+            (2, _) => &mut self.month,
+            (3, _) => &mut self.week,
+            _ => &mut self.day,
+        };
+        *count += 1;
+    }
+}
+
+/// A round number of years or days, see `Interval::next_milestone()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Milestone {
+    Years(usize),
+    Days(usize),
+}
+
+impl Milestone {
+    /// Number of days of the milestone
+    pub fn as_days(&self) -> usize {
+        match self {
+            Milestone::Years(years) => years * YEAR,
+            Milestone::Days(days) => *days,
+        }
+    }
+
+    /// Number of days of the milestone before this one, 0 for the first
+    ///
+    /// ```
+    /// use achievements::Milestone;
+    ///
+    /// assert_eq!(0, Milestone::Days(100).previous_days());
+    /// assert_eq!(365, Milestone::Days(500).previous_days());
+    /// assert_eq!(1000, Milestone::Years(3).previous_days());
+    /// ```
+    pub fn previous_days(&self) -> usize {
+        let days = self.as_days().max(1);
+        let years = (days - 1) / YEAR * YEAR;
+        let day_mark = DAY_MILESTONES
+            .into_iter()
+            .filter(|mark| *mark < days)
+            .max()
+            .unwrap_or_default();
+        let thousands = (days - 1) / 1000 * 1000;
+
+        years.max(day_mark).max(thousands)
+    }
+
+    /// The milestone in words, e.g. "5 years" or "1000 days"
+    pub fn to_words(&self) -> String {
+        self.to_words_in(&English)
+    }
+
+    /// The milestone in words in the given locale
+    ///
+    /// ```
+    /// use achievements::{locale::Italian, Milestone};
+    ///
+    /// assert_eq!("5 anni", Milestone::Years(5).to_words_in(&Italian));
+    /// ```
+    pub fn to_words_in(&self, locale: &dyn Locale) -> String {
+        match self {
+            Milestone::Years(years) => locale.year(*years),
+            Milestone::Days(days) => locale.day(*days),
+        }
+    }
+}
+
+impl Display for Milestone {
+    /// The milestone in words, in the locale of the current thread, see
+    /// `IntervalLocale`
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_words_in(IntervalLocale::current()))
+    }
+}
+
+/// A day with its next milestone
+///
+/// Serialized with the day label and date followed by the milestone,
+/// for example:
+///
+/// ```JSON
+/// {
+///   "label": "Wedding",
+///   "date": "2019-01-07T12:00:00Z",
+///   "milestone": "5 years",
+///   "milestone_days": 1825,
+///   "in_days": 6,
+///   "today": false,
+///   "progress": 0.9835616438356164
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Upcoming {
+    pub day: Day,
+    pub interval: Interval,
+    pub milestone: Milestone,
+}
+
+impl Upcoming {
+    /// Builds an `Upcoming` with the next milestone of the day at `now`
+    pub fn new(day: Day, now: OffsetDateTime) -> Self {
+        let interval = day.elapsed_at(now);
+        let milestone = interval.next_milestone();
+        Self {
+            day,
+            interval,
+            milestone,
+        }
+    }
+
+    /// Days left until the milestone, 0 when it's today
+    pub fn in_days(&self) -> usize {
+        self.milestone.as_days() - self.interval.as_days()
+    }
+
+    /// Whether the milestone is reached today
+    pub fn is_today(&self) -> bool {
+        self.in_days() == 0
+    }
+
+    /// Progress from the previous milestone to this one, from 0.0 to 1.0
+    /// (reached today)
+    pub fn progress(&self) -> f64 {
+        self.interval.progress()
+    }
+}
+
+impl Serialize for Upcoming {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Upcoming", 7)?;
+        state.serialize_field("label", &self.day.label)?;
+        let date = self
+            .day
+            .date
+            .format(&Rfc3339)
+            .map_err(serde::ser::Error::custom)?;
+        state.serialize_field("date", &date)?;
+        state.serialize_field("milestone", &self.milestone.to_words())?;
+        state.serialize_field("milestone_days", &self.milestone.as_days())?;
+        state.serialize_field("in_days", &self.in_days())?;
+        state.serialize_field("today", &self.is_today())?;
+        state.serialize_field("progress", &self.progress())?;
+        state.end()
+    }
+}
+
+/// Two days and the interval between them, earliest first
+///
+/// Serialized with the achievements of the two days and the gap between
+/// them, for example:
+///
+/// ```JSON
+/// {
+///   "earlier": {"label": "Moon landing", "date": "1969-07-20T20:17:40Z", ...},
+///   "later": {"label": "Berlin Wall Fall", "date": "1989-11-09T18:53:00+01:00", ...},
+///   "gap": {"days": 7416, "words": "7416 days", ...}
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct Comparison {
+    pub earlier: Achievement,
+    pub later: Achievement,
+    pub gap: Interval,
+}
+
+impl Comparison {
+    /// Compares the two days, with their intervals computed against `now`
+    ///
+    /// Days with the same date keep the given order.
+    pub fn new(a: Day, b: Day, now: OffsetDateTime) -> Self {
+        let gap = Interval::between(a.date, b.date);
+        let (earlier, later) = if b.date < a.date { (b, a) } else { (a, b) };
+        Self {
+            earlier: Achievement::new(earlier, now),
+            later: Achievement::new(later, now),
+            gap,
+        }
+    }
+}
+
+/// Which days of the config to show and in which order
+///
+/// Example:
+///
+/// ```
+/// use achievements::{config::Config, Selection};
+/// use time::macros::datetime;
+///
+/// let mut config = Config::default();
+/// config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+/// config.set_day("Berlin Wall Fall", datetime!(1989-11-09 18:53 +01:00));
+///
+/// let now = datetime!(2024-01-01 12:00 UTC);
+/// let newest = Selection::newest(1).achievements(&config, now);
+/// assert_eq!(1, newest.len());
+/// assert_eq!("Berlin Wall Fall", newest[0].day.label);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Selection {
+    /// Days to show, applied before sorting and limiting
+    pub filter: Filter,
+    /// Sort key, config order when `None`
+    pub sort: Option<SortKey>,
+    /// Reverse the sort order
+    pub reverse: bool,
+    /// Maximum number of achievements (after sorting), `0` means no limit
+    pub limit: usize,
+}
+
+impl Selection {
+    /// Selects the `limit` newest days, i.e. sorted by fewest days elapsed
+    pub fn newest(limit: usize) -> Self {
+        Self {
+            filter: Filter::default(),
+            sort: Some(SortKey::Days),
+            reverse: false,
+            limit,
+        }
+    }
+
+    /// Selects the `limit` oldest days, i.e. sorted by most days elapsed
+    pub fn oldest(limit: usize) -> Self {
+        Self {
+            filter: Filter::default(),
+            sort: Some(SortKey::Days),
+            reverse: true,
+            limit,
+        }
+    }
+
+    /// Computes the achievements for the selected days of the config
+    ///
+    /// All intervals (and the sorting by days) are computed against `now`.
+    pub fn achievements(&self, config: &Config, now: OffsetDateTime) -> Vec<Achievement> {
+        let days = match self.sort {
+            Some(key) => config.sorted_days(key, self.reverse, now),
+            None => config.days.iter().collect(),
+        };
+
+        let mut achievements = days
+            .into_iter()
+            .map(|day| Achievement::new(day.clone(), now))
+            .filter(|achievement| self.filter.matches(achievement))
+            .collect();
+        limit(&mut achievements, self.limit);
+
+        achievements
+    }
+}
+
+/// Conditions on the achievements to show, all of them must hold
+///
+/// The default filter matches every achievement.
+///
+/// ```
+/// use achievements::{config::Day, Achievement, Filter};
+/// use time::macros::datetime;
+///
+/// let now = datetime!(2024-01-01 12:00 UTC);
+/// let moon_landing = Achievement::new(
+///     Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC)),
+///     now,
+/// );
+///
+/// let filter = Filter {
+///     min_days: Some(1000),
+///     before: Some(datetime!(1970-01-01 0:00 UTC)),
+///     ..Default::default()
+/// };
+/// assert!(filter.matches(&moon_landing));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    /// Days with any of these tags (case-insensitive), any day when empty
+    pub tags: Vec<String>,
+    /// At least this many days elapsed
+    pub min_days: Option<usize>,
+    /// At most this many days elapsed
+    pub max_days: Option<usize>,
+    /// Days with a date strictly before this one
+    pub before: Option<OffsetDateTime>,
+    /// Days with a date on or after this one
+    pub after: Option<OffsetDateTime>,
+    /// Days with none of these labels (ignoring case and leading/trailing
+    /// whitespace)
+    pub exclude: Vec<String>,
+}
+
+impl Filter {
+    /// Whether the achievement satisfies all the conditions
+    pub fn matches(&self, achievement: &Achievement) -> bool {
+        let Achievement { day, interval } = achievement;
+        let days = interval.as_days();
+
+        (self.tags.is_empty() || self.tags.iter().any(|tag| day.has_tag(tag)))
+            && self.min_days.is_none_or(|min| days >= min)
+            && self.max_days.is_none_or(|max| days <= max)
+            && self.before.is_none_or(|before| day.date < before)
+            && self.after.is_none_or(|after| day.date >= after)
+            && !self.excludes(&day.label)
+    }
+
+    fn excludes(&self, label: &str) -> bool {
+        let label = label.trim().to_lowercase();
+        self.exclude
+            .iter()
+            .any(|excluded| excluded.trim().to_lowercase() == label)
+    }
+
+    /// Keeps only the days whose achievements (computed against `now`)
+    /// match, e.g. to filter the days of the config before listing or
+    /// exporting them
+    pub fn retain(&self, days: &mut Vec<Day>, now: OffsetDateTime) {
+        days.retain(|day| self.matches(&Achievement::new(day.clone(), now)));
+    }
+}
+
+/// Keeps only the first `limit` items, `0` means no limit
+///
+/// ```
+/// let mut items = vec![1, 2, 3];
+/// achievements::limit(&mut items, 0);
+/// assert_eq!(vec![1, 2, 3], items);
+/// achievements::limit(&mut items, 2);
+/// assert_eq!(vec![1, 2], items);
+/// ```
+pub fn limit<T>(items: &mut Vec<T>, limit: usize) {
+    if limit > 0 {
+        items.truncate(limit);
+    }
+}
+
+/// Weight of every achievement with `weighted`, on top of its progress,
+/// so the ones just past a milestone can still be picked
+const MIN_WEIGHT: f64 = 0.1;
+
+/// Picks one of the achievements at random with the given generator,
+/// `None` when there are none
+///
+/// All of them are equally likely, unless `weighted` is `true`: then the
+/// closer an achievement is to its next milestone (see
+/// `Interval::progress()`) the likelier it is. The same seed always picks
+/// the same achievement.
+///
+/// ```
+/// use achievements::{config::Day, pick_random, Achievement};
+/// use time::macros::datetime;
+///
+/// let now = datetime!(2024-01-01 12:00 UTC);
+/// let achievements = [
+///     Achievement::new(Day::new("Moved", datetime!(2023-12-01 12:00 UTC)), now),
+///     Achievement::new(Day::new("Run", datetime!(2023-12-29 12:00 UTC)), now),
+/// ];
+///
+/// let pick = |seed| {
+///     let mut rng = fastrand::Rng::with_seed(seed);
+///     let picked = pick_random(&achievements, &mut rng, false);
+///     picked.map(|achievement| achievement.day.label.clone())
+/// };
+/// assert_eq!(pick(42), pick(42));
+/// ```
+pub fn pick_random<'a>(
+    achievements: &'a [Achievement],
+    rng: &mut fastrand::Rng,
+    weighted: bool,
+) -> Option<&'a Achievement> {
+    if achievements.is_empty() {
+        return None;
+    }
+    if !weighted {
+        return achievements.get(rng.usize(..achievements.len()));
+    }
+
+    let weight = |achievement: &Achievement| MIN_WEIGHT + achievement.interval.progress();
+    let mut left = rng.f64() * achievements.iter().map(weight).sum::<f64>();
+    for achievement in achievements {
+        left -= weight(achievement);
+        if left < 0.0 {
+            return Some(achievement);
+        }
+    }
+    // Rounding errors
+    achievements.last()
+}
+
+/// Environment variable overriding the current time, see `current_time()`
+pub const NOW_ENV_VAR: &str = "ACHIEVEMENTS_NOW";
+
+/// Error reading `ACHIEVEMENTS_NOW`, see `current_time()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurrentTimeError {
+    value: String,
+}
+
+impl Display for CurrentTimeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid {NOW_ENV_VAR} '{}', expected RFC3339 e.g. \"2024-01-01T00:00:00Z\"",
+            self.value
+        )
+    }
+}
+
+impl std::error::Error for CurrentTimeError {}
+
+/// Returns the current time
+///
+/// If the `ACHIEVEMENTS_NOW` environment variable is set, its RFC3339
+/// date-time is used instead, for reproducible output in tests, demos or
+/// screenshots. Returns an error if it's set but not a valid date-time.
+pub fn current_time() -> Result<OffsetDateTime, CurrentTimeError> {
+    current_time_from(std::env::var(NOW_ENV_VAR).ok())
+}
+
+fn current_time_from(value: Option<String>) -> Result<OffsetDateTime, CurrentTimeError> {
+    match value {
+        None => Ok(OffsetDateTime::now_utc()),
+        Some(value) => {
+            OffsetDateTime::parse(value.trim(), &Rfc3339).map_err(|_| CurrentTimeError { value })
+        }
+    }
+}
+
+/// Returns the number of days since the given date
+///
+/// Implementation is very simple and assumes a day is 86400 seconds.
+/// This means the returned value could not be accurate but it is close
+/// enough.
+///
+/// "Now" is `current_time()`, the actual current time if that fails.
+pub fn days_since(day: OffsetDateTime) -> usize {
+    let now = current_time().unwrap_or_else(|_| OffsetDateTime::now_utc());
+    days_since_at(day, now)
+}
+
+/// Returns the number of days between the given date and `now`
+///
+/// Same as `days_since` but against a caller-supplied `now`, useful to
+/// compute several intervals against the same instant. Zero when the date
+/// is after `now`.
+///
+/// Example:
+///
+/// ```
+/// use achievements::days_since_at;
+/// use time::macros::datetime;
+///
+/// let day = datetime!(2024-01-01 12:00 UTC);
+/// let now = datetime!(2024-01-31 12:00 UTC);
+/// assert_eq!(30, days_since_at(day, now));
+/// assert_eq!(0, days_since_at(now, day));
+/// ```
+pub fn days_since_at(day: OffsetDateTime, now: OffsetDateTime) -> usize {
+    let seconds_elapsed: time::Duration = now - day;
+    let days = seconds_elapsed.whole_seconds() / DAY_IN_SECONDS as i64;
+
+    Interval::from_days_saturating(days).as_days()
+}
+
+const DAY_FORMAT: &[FormatItem<'static>] = format_description!("[year]-[month]-[day]");
+
+/// Parses a date given by the user
+///
+/// Accepts RFC3339 dates, e.g. "1969-07-20T20:17:40Z", or only the day as
+/// `YYYY-MM-DD`, taken as midnight UTC. Surrounding whitespace is ignored.
+///
+/// Example:
+///
+/// ```
+/// use achievements::parse_date;
+/// use time::macros::datetime;
+///
+/// assert_eq!(Ok(datetime!(1969-07-20 0:00 UTC)), parse_date("1969-07-20"));
+/// assert!(parse_date("20/07/1969").is_err());
+/// ```
+pub fn parse_date(input: &str) -> Result<OffsetDateTime, time::error::Parse> {
+    let input = input.trim();
+    OffsetDateTime::parse(input, &Rfc3339)
+        .or_else(|_| Ok(Date::parse(input, DAY_FORMAT)?.midnight().assume_utc()))
+}
+
+/// English word of the numbers from one to ten, `None` for the others
+///
+/// ```
+/// use achievements::word_number;
+///
+/// assert_eq!(Some("seven"), word_number(7));
+/// assert_eq!(None, word_number(11));
+/// ```
+pub fn word_number(n: usize) -> Option<&'static str> {
+    const WORDS: [&str; 10] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+    n.checked_sub(1).and_then(|i| WORDS.get(i)).copied()
+}
+
+/// Parses a date relative to `now` given by the user
+///
+/// Accepts "today", "yesterday" or an interval (see `Interval::from_str()`)
+/// followed by "ago", e.g. "1 year ago" or "3w ago", ignoring case and
+/// surrounding whitespace. Returns `None` for other inputs.
+///
+/// ```
+/// use achievements::parse_relative_date;
+/// use time::macros::datetime;
+///
+/// let now = datetime!(2024-01-01 12:00 UTC);
+/// assert_eq!(Some(datetime!(2023-01-01 12:00 UTC)), parse_relative_date("1 year ago", now));
+/// assert_eq!(Some(datetime!(2023-12-31 12:00 UTC)), parse_relative_date("yesterday", now));
+/// assert_eq!(None, parse_relative_date("1969-07-20", now));
+/// ```
+pub fn parse_relative_date(input: &str, now: OffsetDateTime) -> Option<OffsetDateTime> {
+    let input = input.trim().to_lowercase();
+    let days = match input.as_str() {
+        "today" => 0,
+        "yesterday" => 1,
+        _ => {
+            let interval: Interval = input.strip_suffix("ago")?.parse().ok()?;
+            interval.as_days()
+        }
+    };
+
+    let seconds = i64::try_from(days)
+        .ok()?
+        .checked_mul(DAY_IN_SECONDS as i64)?;
+    now.checked_sub(time::Duration::seconds(seconds))
+}
+
+#[test]
+fn test_current_time() {
+    use time::macros::datetime;
+
+    assert_eq!(
+        Ok(datetime!(2024-01-01 12:00 UTC)),
+        current_time_from(Some("2024-01-01T12:00:00Z".to_string()))
+    );
+    assert!(current_time_from(None).is_ok());
+
+    let error = current_time_from(Some("2024-01-01".to_string())).unwrap_err();
+    assert_eq!(
+        "invalid ACHIEVEMENTS_NOW '2024-01-01', expected RFC3339 e.g. \"2024-01-01T00:00:00Z\"",
+        error.to_string()
+    );
+}
+
+#[test]
+fn test_fmt_padded() {
+    struct Padded(Interval, usize);
+
+    impl Display for Padded {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            self.0.fmt_padded(f, self.1)
+        }
+    }
+
+    let padded = |days, width| Padded(Interval::from_days(days), width).to_string();
+    assert_eq!("   3 days ☆☆☆", padded(3, 9));
+    assert_eq!("2 weeks ★★", padded(14, 0));
+    assert_eq!("2 weeks ★★", padded(14, 3));
+    assert_eq!(Interval::from_days(14).to_string(), padded(14, 0));
+}
+
+#[test]
+fn test_counts() {
+    let interval = Interval::from_days(3 * 10 * YEAR + 2 * YEAR + 40);
+    assert_eq!(0, interval.century_count());
+    assert_eq!(3, interval.decade_count());
+    assert_eq!(32, interval.year_count());
+    assert_eq!(390, interval.month_count());
+    assert_eq!(interval.as_days() / 7, interval.week_count());
+
+    assert_eq!(1, Interval::from_days(100 * YEAR).century_count());
+    assert_eq!(0, Interval::from_days(6).week_count());
+}
+
+#[test]
+fn test_from_days() {
+    const DECADE: usize = 10 * YEAR;
+    assert_eq!(IntervalEnum::Decade(3), Interval::from_days(3 * DECADE).e);
+    assert_eq!(IntervalEnum::Decade(1), Interval::from_days(1 * DECADE).e);
+
+    assert_eq!(IntervalEnum::Year(33), Interval::from_days(33 * YEAR).e);
+    assert_eq!(IntervalEnum::Year(11), Interval::from_days(11 * YEAR).e);
+    assert_eq!(IntervalEnum::Year(5), Interval::from_days(5 * YEAR).e);
+    assert_eq!(IntervalEnum::Year(1), Interval::from_days(1 * YEAR).e);
+
+    assert_eq!(IntervalEnum::Month(5), Interval::from_days(5 * MONTH).e);
+    assert_eq!(IntervalEnum::Month(1), Interval::from_days(MONTH).e);
+
+    assert_eq!(IntervalEnum::Week(3), Interval::from_days(3 * WEEK).e);
+    assert_eq!(IntervalEnum::Week(1), Interval::from_days(WEEK).e);
+
+    assert_eq!(IntervalEnum::Day(15), Interval::from_days(15).e);
+    assert_eq!(IntervalEnum::Day(10), Interval::from_days(10).e);
+    assert_eq!(IntervalEnum::Day(5), Interval::from_days(5).e);
+    assert_eq!(IntervalEnum::Day(1), Interval::from_days(1).e);
+    assert_eq!(IntervalEnum::Day(0), Interval::from_days(0).e);
+}
+
+#[test]
+fn test_milestone() {
+    use time::macros::datetime;
+
+    let milestone = |days| Interval::from_days(days).milestone();
+    assert_eq!(None, milestone(0));
+    assert_eq!(None, milestone(99));
+    assert_eq!(Some(Milestone::Days(100)), milestone(100));
+    assert_eq!(None, milestone(WEEK));
+    assert_eq!(Some(Milestone::Years(1)), milestone(YEAR));
+    assert_eq!(Some(Milestone::Days(500)), milestone(500));
+    assert_eq!(Some(Milestone::Years(10)), milestone(10 * YEAR));
+    assert_eq!(Some(Milestone::Days(4000)), milestone(4000));
+    assert_eq!(None, milestone(4001));
+
+    let now = datetime!(2024-01-01 18:00 UTC);
+    let wedding = Day::new("Wedding", datetime!(2019-01-02 12:00 UTC));
+    let achievement = Achievement::new(wedding.clone(), now);
+    assert_eq!(Some(Milestone::Years(5)), achievement.milestone_today());
+
+    let archived = Day {
+        archived: true,
+        ..wedding.clone()
+    };
+    assert_eq!(None, Achievement::new(archived, now).milestone_today());
+    let closed = Day {
+        end: Some(datetime!(2020-01-01 12:00 UTC)),
+        ..wedding
+    };
+    assert_eq!(None, Achievement::new(closed, now).milestone_today());
+}
+
+#[test]
+fn test_unit_predicates() {
+    let units = |interval: IntervalEnum| {
+        [
+            interval.is_day(),
+            interval.is_week(),
+            interval.is_month(),
+            interval.is_year(),
+            interval.is_decade(),
+        ]
+    };
+    assert_eq!(
+        units(IntervalEnum::Day(0)),
+        [true, false, false, false, false]
+    );
+    assert_eq!(
+        units(IntervalEnum::Week(3)),
+        [false, true, false, false, false]
+    );
+    assert_eq!(
+        units(IntervalEnum::Month(1)),
+        [false, false, true, false, false]
+    );
+    assert_eq!(
+        units(IntervalEnum::Year(5)),
+        [false, false, false, true, false]
+    );
+    assert_eq!(
+        units(IntervalEnum::Decade(2)),
+        [false, false, false, false, true]
+    );
+
+    let units = |days| {
+        let interval = Interval::from_days(days);
+        [
+            interval.is_in_days(),
+            interval.is_in_weeks(),
+            interval.is_in_months(),
+            interval.is_in_years(),
+            interval.is_in_decades(),
+        ]
+    };
+    assert_eq!(units(0), [true, false, false, false, false]);
+    assert_eq!(units(YEAR + 1), [true, false, false, false, false]);
+    assert_eq!(units(3 * WEEK), [false, true, false, false, false]);
+    assert_eq!(units(2 * MONTH), [false, false, true, false, false]);
+    assert_eq!(units(YEAR), [false, false, false, true, false]);
+    assert_eq!(units(30 * YEAR), [false, false, false, false, true]);
+}
+
+#[test]
+fn test_is_exact_unit() {
+    assert!(!Interval::from_days(0).is_exact_unit());
+    assert!(!Interval::from_days(1).is_exact_unit());
+    assert!(!Interval::from_days(6).is_exact_unit());
+    assert!(Interval::from_days(WEEK).is_exact_unit());
+    assert!(Interval::from_days(MONTH).is_exact_unit());
+    assert!(Interval::from_days(YEAR).is_exact_unit());
+    assert!(Interval::from_days(10 * YEAR).is_exact_unit());
+    assert!(!Interval::from_days(YEAR + 1).is_exact_unit());
+}
+
+#[test]
+fn test_to_words() {
+    assert_eq!("3 decades", Interval::from_days(3 * 10 * YEAR).to_words());
+    assert_eq!(
+        "1 decade, that's amazing",
+        Interval::from_days(10 * YEAR).to_words()
+    );
+
+    assert_eq!("33 years", Interval::from_days(33 * YEAR).to_words());
+    assert_eq!("11 years", Interval::from_days(11 * YEAR).to_words());
+    assert_eq!("5 years", Interval::from_days(5 * YEAR).to_words());
+    assert_eq!(
+        "1 year, happy anniversary!",
+        Interval::from_days(YEAR).to_words()
+    );
+
+    assert_eq!("5 months", Interval::from_days(5 * MONTH).to_words());
+    assert_eq!("1 month", Interval::from_days(1 * MONTH).to_words());
+
+    assert_eq!("3 weeks", Interval::from_days(3 * WEEK).to_words());
+    assert_eq!("1 week", Interval::from_days(1 * WEEK).to_words());
+
+    assert_eq!("15 days", Interval::from_days(15).to_words());
+    assert_eq!("10 days", Interval::from_days(10).to_words());
+    assert_eq!("5 days", Interval::from_days(5).to_words());
+    assert_eq!("1 day", Interval::from_days(1).to_words());
+    assert_eq!("Recently", Interval::from_days(0).to_words());
+}
+
+#[test]
+fn test_to_words_spelled() {
+    assert_eq!("3 decades", Interval::from_days(3 * 10 * YEAR).to_words());
+    assert_eq!(
+        "three decades",
+        Interval::from_days(3 * 10 * YEAR).to_words_spelled()
+    );
+    assert_eq!(
+        "one decade, that's amazing",
+        Interval::from_days(10 * YEAR).to_words_spelled()
+    );
+    assert_eq!(
+        "one year, happy anniversary!",
+        Interval::from_days(YEAR).to_words_spelled()
+    );
+    assert_eq!(
+        "ten weeks",
+        Interval::from_days(10 * WEEK).to_words_spelled()
+    );
+    assert_eq!(
+        "11 years",
+        Interval::from_days(11 * YEAR).to_words_spelled()
+    );
+    assert_eq!("one month", Interval::from_days(MONTH).to_words_spelled());
+    assert_eq!(
+        "two weeks",
+        Interval::from_days(2 * WEEK).to_words_spelled()
+    );
+    assert_eq!("one day", Interval::from_days(1).to_words_spelled());
+    assert_eq!("ten days", Interval::from_days(10).to_words_spelled());
+    assert_eq!("15 days", Interval::from_days(15).to_words_spelled());
+    assert_eq!("Recently", Interval::from_days(0).to_words_spelled());
+}
+
+#[test]
+fn test_word_number() {
+    assert_eq!(word_number(0), None);
+    assert_eq!(word_number(1), Some("one"));
+    assert_eq!(word_number(10), Some("ten"));
+    assert_eq!(word_number(11), None);
+}
+
+#[test]
+fn test_to_words_in_italian() {
+    let words = |days: usize| Interval::from_days(days).to_words_in(&locale::Italian);
+
+    assert_eq!("3 decenni", words(3 * 10 * YEAR));
+    assert_eq!("1 decennio, che meraviglia!", words(10 * YEAR));
+
+    assert_eq!("33 anni", words(33 * YEAR));
+    assert_eq!("11 anni", words(11 * YEAR));
+    assert_eq!("5 anni", words(5 * YEAR));
+    assert_eq!("1 anno, buon anniversario!", words(YEAR));
+
+    assert_eq!("5 mesi", words(5 * MONTH));
+    assert_eq!("1 mese", words(MONTH));
+
+    assert_eq!("3 settimane", words(3 * WEEK));
+    assert_eq!("1 settimana", words(WEEK));
+
+    assert_eq!("15 giorni", words(15));
+    assert_eq!("10 giorni", words(10));
+    assert_eq!("5 giorni", words(5));
+    assert_eq!("1 giorno", words(1));
+    assert_eq!("Di recente", words(0));
+}
+
+#[test]
+fn test_to_words_plain() {
+    assert_eq!(
+        "3 decades",
+        Interval::from_days(3 * 10 * YEAR).to_words_plain()
+    );
+    assert_eq!("1 decade", Interval::from_days(10 * YEAR).to_words_plain());
+    assert_eq!("1 year", Interval::from_days(YEAR).to_words_plain());
+    assert_eq!("5 years", Interval::from_days(5 * YEAR).to_words_plain());
+    assert_eq!("1 month", Interval::from_days(MONTH).to_words_plain());
+    assert_eq!("1 week", Interval::from_days(WEEK).to_words_plain());
+    assert_eq!("1 day", Interval::from_days(1).to_words_plain());
+    assert_eq!("Recently", Interval::from_days(0).to_words_plain());
+}
+
+#[test]
+fn test_badges_in_style() {
+    for style in [BadgeStyle::Emoji, BadgeStyle::Ascii, BadgeStyle::None] {
+        assert_eq!("", Interval::from_days(0).badges_in_style(style));
+    }
+
+    let ascii = |days| Interval::from_days(days).badges_in_style(BadgeStyle::Ascii);
+    assert_eq!("###", ascii(3 * 10 * YEAR));
+    assert_eq!("@@", ascii(2 * YEAR + 3));
+    assert_eq!("*****", ascii(5 * MONTH));
+    assert_eq!("++", ascii(15));
+    assert_eq!("...", ascii(3));
+
+    let none = |days| Interval::from_days(days).badges_in_style(BadgeStyle::None);
+    assert_eq!("", none(3 * 10 * YEAR));
+    assert_eq!("", none(3));
+}
+
+#[test]
+fn test_from_days_saturating() {
+    use time::macros::datetime;
+
+    assert_eq!(IntervalEnum::Week(2), Interval::from_days_saturating(14).e);
+    assert_eq!(Interval::from_days(0), Interval::from_days_saturating(0));
+    assert_eq!(Interval::from_days(0), Interval::from_days_saturating(-1));
+    assert_eq!(
+        Interval::from_days(0),
+        Interval::from_days_saturating(i64::MIN)
+    );
+
+    // Less than a day either way, and in the future
+    let now = datetime!(2024-01-01 12:00 UTC);
+    assert_eq!(0, days_since_at(datetime!(2024-01-01 0:00:01 UTC), now));
+    assert_eq!(0, days_since_at(datetime!(2024-01-01 23:59 UTC), now));
+    assert_eq!(0, days_since_at(datetime!(2030-01-01 0:00 UTC), now));
+    assert_eq!(1, days_since_at(datetime!(2023-12-31 12:00 UTC), now));
+}
+
+#[test]
+fn test_from_integers() {
+    assert_eq!(Interval::from_days(WEEK), Interval::from(7_u32));
+    assert_eq!(Interval::from_days(YEAR), Interval::from(365_u64));
+    assert_eq!(IntervalEnum::Month(2), Interval::from(60_u32).e);
+    assert_eq!(42, usize::from(Interval::from_days(42)));
+}
+
+#[test]
+fn test_arithmetic() {
+    assert_eq!(Interval::from_days(WEEK), Interval::from_days(6) + 1);
+    assert_eq!(IntervalEnum::Year(1), (Interval::from_days(YEAR - 1) + 1).e);
+    assert_eq!(Interval::from_days(3), Interval::from_days(3) + 0);
+
+    assert_eq!(Some(Interval::from_days(0)), Interval::from_days(3) - 3);
+    assert_eq!(
+        Some(Interval::from_days(MONTH)),
+        Interval::from_days(MONTH + 2) - 2
+    );
+    assert_eq!(None, Interval::from_days(3) - 4);
+
+    assert_eq!(
+        Interval::from_days(10 * YEAR),
+        Interval::from_days(9 * YEAR) + Interval::from_days(YEAR)
+    );
+}
+
+#[test]
+fn test_selection() {
+    use time::macros::datetime;
+
+    let mut config = Config::default();
+    config.set_day("b", datetime!(2020-01-01 12:00 UTC));
+    config.set_day("c", datetime!(2010-01-01 12:00 UTC));
+    config.set_day("a", datetime!(2023-01-01 12:00 UTC));
+    let now = datetime!(2024-01-01 12:00 UTC);
+
+    let labels = |selection: Selection| -> Vec<String> {
+        selection
+            .achievements(&config, now)
+            .into_iter()
+            .map(|achievement| achievement.day.label)
+            .collect()
+    };
+
+    assert_eq!(labels(Selection::default()), ["b", "c", "a"]);
+    assert_eq!(labels(Selection::newest(0)), ["a", "b", "c"]);
+    assert_eq!(labels(Selection::newest(2)), ["a", "b"]);
+    assert_eq!(labels(Selection::oldest(1)), ["c"]);
+    assert_eq!(labels(Selection::oldest(5)), ["c", "b", "a"]);
+
+    let selection = Selection {
+        sort: Some(SortKey::Label),
+        reverse: true,
+        limit: 2,
+        ..Default::default()
+    };
+    assert_eq!(labels(selection), ["c", "b"]);
+}
+
+#[test]
+fn test_to_compound_string() {
+    assert_eq!("Recently", Interval::from_days(0).to_compound_string());
+    assert_eq!("1 day", Interval::from_days(1).to_compound_string());
+    assert_eq!("1 week", Interval::from_days(WEEK).to_compound_string());
+    assert_eq!("1 month", Interval::from_days(MONTH).to_compound_string());
+    assert_eq!(
+        "1 month, 1 day",
+        Interval::from_days(MONTH + 1).to_compound_string()
+    );
+    assert_eq!(
+        "10 years",
+        Interval::from_days(10 * YEAR).to_compound_string()
+    );
+    assert_eq!(
+        "2 years, 3 months, 1 week",
+        Interval::from_days(827).to_compound_string()
+    );
+}
+
+#[test]
+fn test_to_verbose_string() {
+    assert_eq!(
+        "Recently (0 days total)",
+        Interval::from_days(0).to_verbose_string()
+    );
+    assert_eq!(
+        "1 day (1 day total)",
+        Interval::from_days(1).to_verbose_string()
+    );
+    assert_eq!(
+        "1 month, 1 day (31 days total)",
+        Interval::from_days(MONTH + 1).to_verbose_string()
+    );
+}
+
+#[test]
+fn test_auto_cap_badges() {
+    let interval = Interval::from_days(5 * YEAR);
+    let capped = |width, label_width| {
+        interval
+            .formatter()
+            .auto_cap_badges(width, label_width)
+            .build()
+            .to_string()
+    };
+
+    // "Label: 5 years " is 15 columns
+    assert_eq!(capped(80, 6), "5 years 🌟🌟🌟🌟🌟");
+    assert_eq!(capped(25, 6), "5 years 🌟🌟🌟🌟🌟");
+    assert_eq!(capped(24, 6), "5 years 🌟🌟🌟🌟");
+    assert_eq!(capped(16, 6), "5 years");
+    assert_eq!(capped(10, 40), "5 years");
+
+    // The words are measured with the other options, set before
+    let longer = Interval::from_days(5 * YEAR + 1);
+    let formatter = longer
+        .formatter()
+        .compound(true)
+        .ascii_badges()
+        .auto_cap_badges(26, 6)
+        .build();
+    assert_eq!(formatter.to_string(), "5 years, 1 day @@@@");
+    let formatter = interval
+        .formatter()
+        .max_badges(1)
+        .auto_cap_badges(80, 6)
+        .build();
+    assert_eq!(formatter.to_string(), "5 years 🌟");
+}
+
+#[test]
+fn test_formatter() {
+    let interval = Interval::from_days(5 * YEAR);
+    assert_eq!(
+        interval.formatter().to_string(),
+        interval.formatter().build().to_string()
+    );
+    assert_eq!("5 years 🌟🌟🌟🌟🌟", interval.formatter().to_string());
+    assert_eq!(
+        "5 years @@@@@",
+        interval.formatter().ascii_badges().to_string()
+    );
+    assert_eq!("5 years", interval.formatter().no_badges().to_string());
+    assert_eq!(
+        "5 years 🌟🌟",
+        interval.formatter().max_badges(2).to_string()
+    );
+    assert_eq!("5 years", interval.formatter().max_badges(0).to_string());
+    assert_eq!("Recently", Interval::from_days(0).formatter().to_string());
+
+    let year = Interval::from_days(YEAR);
+    assert_eq!(
+        "1 year, happy anniversary! 🌟",
+        year.formatter().to_string()
+    );
+    assert_eq!("1 year 🌟", year.formatter().messages(false).to_string());
+    assert_eq!("1y 🌟", year.formatter().short(true).to_string());
+
+    let interval = Interval::from_days(YEAR + MONTH);
+    assert_eq!(
+        "1 year, 1 month @",
+        interval
+            .formatter()
+            .compound(true)
+            .ascii_badges()
+            .to_string()
+    );
+}
+
+#[test]
+fn test_to_string() {
+    assert_eq!(
+        "3 decades 💎💎💎",
         Interval::from_days(3 * 10 * YEAR).to_string()
     );
     assert_eq!(
@@ -286,3 +2374,456 @@ fn test_to_string() {
     assert_eq!("1 day ☆", Interval::from_days(1).to_string());
     assert_eq!("Recently ", Interval::from_days(0).to_string());
 }
+
+#[test]
+fn test_default_options() {
+    let interval = Interval::from_days(2 * YEAR + MONTH);
+    assert_eq!(IntervalOptions::current(), IntervalOptions::default());
+    assert_eq!("760 days 🌟🌟", interval.to_string());
+
+    IntervalOptions::set_default(IntervalOptions {
+        badge_style: BadgeStyle::Ascii,
+        compound: true,
+        ..Default::default()
+    });
+    assert_eq!("2 years, 1 month @@", interval.to_string());
+    // the formatter doesn't use the thread defaults
+    assert_eq!("760 days 🌟🌟", interval.formatter().to_string());
+
+    // other threads keep the defaults
+    let other = std::thread::spawn(move || interval.to_string())
+        .join()
+        .expect("Thread panicked");
+    assert_eq!("760 days 🌟🌟", other);
+
+    IntervalOptions::set_default(IntervalOptions {
+        badge_style: BadgeStyle::None,
+        messages: false,
+        ..Default::default()
+    });
+    assert_eq!("1 year", Interval::from_days(YEAR).to_string());
+    assert_eq!("Recently", Interval::from_days(0).to_string());
+
+    IntervalOptions::set_default(IntervalOptions {
+        max_badges: Some(2),
+        ..Default::default()
+    });
+    assert_eq!("3 years 🌟🌟", Interval::from_days(3 * YEAR).to_string());
+
+    IntervalOptions::set_default(IntervalOptions {
+        short: true,
+        ..Default::default()
+    });
+    assert_eq!("3y 🌟🌟🌟", Interval::from_days(3 * YEAR).to_string());
+
+    IntervalOptions::reset_default();
+    assert_eq!("Recently ", Interval::from_days(0).to_string());
+}
+
+#[test]
+fn test_display_plain() {
+    let year = Interval::from_days(YEAR);
+    assert_eq!("1 year", year.display_plain());
+    assert_eq!("1 year, happy anniversary!", year.to_words());
+    assert_eq!("Recently", Interval::from_days(0).display_plain());
+
+    let json = |interval: &Interval| serde_json::to_value(interval).expect("Failed to serialize");
+    assert_eq!(json(&year)["words"], "1 year, happy anniversary!");
+    IntervalOptions::set_default(IntervalOptions {
+        messages: false,
+        ..Default::default()
+    });
+    assert_eq!(json(&year)["words"], "1 year");
+    IntervalOptions::set_default(IntervalOptions {
+        compound: true,
+        ..Default::default()
+    });
+    assert_eq!(
+        json(&Interval::from_days(YEAR + 3))["words"],
+        "1 year, 3 days"
+    );
+    IntervalOptions::reset_default();
+}
+
+#[test]
+fn test_display_locale() {
+    use locale::Italian;
+
+    let year = Interval::from_days(YEAR);
+    assert_eq!("1 year, happy anniversary! 🌟", year.to_string());
+
+    IntervalLocale::set_current(Box::new(Italian));
+    assert_eq!("1 anno, buon anniversario! 🌟", year.to_string());
+    assert_eq!("3 giorni ☆☆☆", Interval::from_days(3).to_string());
+    assert_eq!("Di recente ", format!("{}", Interval::from_days(0)));
+    // the other conversions stay in English
+    assert_eq!("1 year, happy anniversary!", year.to_words());
+
+    // other threads have their own locale
+    let other = std::thread::spawn(|| Interval::from_days(14).to_string());
+    assert_eq!("2 weeks ★★", other.join().unwrap());
+
+    IntervalLocale::set_current(Box::new(English));
+    assert_eq!("3 days ☆☆☆", Interval::from_days(3).to_string());
+}
+
+#[test]
+fn test_from_str() {
+    let parse = |input: &str| input.parse::<Interval>().map(|interval| interval.as_days());
+
+    for (input, days) in [
+        ("42d", 42),
+        ("1 day", 1),
+        ("2 days", 2),
+        ("3w", 21),
+        ("3wk", 21),
+        ("1 week", 7),
+        ("2mo", 60),
+        ("2 months", 60),
+        ("3y", 3 * YEAR),
+        ("1 year", YEAR),
+        ("3dec", 30 * YEAR),
+        ("1 decade", 10 * YEAR),
+        ("2 DECADES", 20 * YEAR),
+        ("  3 weeks ", 21),
+        ("0d", 0),
+        ("Recently", 0),
+        ("1 year, happy anniversary!", YEAR),
+        ("1 decade, that's amazing", 10 * YEAR),
+        ("2 years, 1 month, 2 days", 2 * YEAR + MONTH + 2),
+    ] {
+        assert_eq!(parse(input), Ok(days), "{input}");
+    }
+
+    for input in [
+        "",
+        "3",
+        "weeks",
+        "3 fortnights",
+        "-3d",
+        "3.5 weeks",
+        "2 years, happy anniversary!",
+        "1 year, whatever",
+        "happy anniversary!",
+        "99999999999999999999 days",
+    ] {
+        assert!(parse(input).is_err(), "{input}");
+    }
+
+    // round-trip
+    for days in [0, 1, 15, 21, 60, 365, 760, 3650, 7300] {
+        let interval = Interval::from_days(days);
+        assert_eq!(interval.to_words().parse(), Ok(interval.clone()));
+        assert_eq!(interval.to_words_short().parse(), Ok(interval.clone()));
+        assert_eq!(interval.to_compound_string().parse(), Ok(interval));
+    }
+
+    assert_eq!(
+        "3 fortnights".parse::<Interval>().unwrap_err().to_string(),
+        "invalid interval '3 fortnights', expected e.g. \"3 weeks\" or \"3w\""
+    );
+}
+
+#[test]
+fn test_pick_random() {
+    use std::collections::HashMap;
+
+    use time::macros::datetime;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let achievements = [
+        // Just past 1 year
+        Achievement::new(Day::new("Moved", datetime!(2022-12-31 12:00 UTC)), now),
+        // 99 days, close to 100
+        Achievement::new(Day::new("Run", datetime!(2023-09-24 12:00 UTC)), now),
+        Achievement::new(Day::new("Quit", datetime!(2023-11-22 12:00 UTC)), now),
+    ];
+    let counts = |weighted: bool| {
+        let mut rng = fastrand::Rng::with_seed(7);
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for _ in 0..3000 {
+            let picked = pick_random(&achievements, &mut rng, weighted).expect("Not empty");
+            *counts.entry(picked.day.label.as_str()).or_default() += 1;
+        }
+        counts
+    };
+
+    assert!(pick_random(&[], &mut fastrand::Rng::with_seed(7), false).is_none());
+    assert!(pick_random(&[], &mut fastrand::Rng::with_seed(7), true).is_none());
+    // Deterministic for a seed
+    assert_eq!(counts(false), counts(false));
+    assert_eq!(counts(true), counts(true));
+
+    let uniform = counts(false);
+    assert!(uniform.values().all(|count| (900..1100).contains(count)));
+    let weighted = counts(true);
+    assert!(weighted["Run"] > 5 * weighted["Moved"]);
+    assert!(weighted["Run"] > weighted["Quit"]);
+}
+
+#[test]
+fn test_filter() {
+    use time::macros::datetime;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let mut config = Config::default();
+    config.days.push(Day {
+        tags: vec!["history".to_string(), "space".to_string()],
+        ..Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC))
+    });
+    config.days.push(Day {
+        tags: vec!["History".to_string()],
+        ..Day::new("Berlin Wall Fall", datetime!(1989-11-09 18:53 +01:00))
+    });
+    config.set_day("Moved", datetime!(2023-12-18 12:00 UTC));
+    config.set_day("Today", datetime!(2024-01-01 11:00 UTC));
+
+    let labels = |filter: Filter| -> Vec<String> {
+        let selection = Selection {
+            filter,
+            ..Default::default()
+        };
+        selection
+            .achievements(&config, now)
+            .into_iter()
+            .map(|achievement| achievement.day.label)
+            .collect()
+    };
+
+    assert_eq!(labels(Filter::default()).len(), 4);
+    assert_eq!(
+        labels(Filter {
+            tags: vec!["HISTORY".to_string()],
+            ..Default::default()
+        }),
+        ["Moon landing", "Berlin Wall Fall"]
+    );
+    assert_eq!(
+        labels(Filter {
+            tags: vec!["space".to_string(), "nope".to_string()],
+            ..Default::default()
+        }),
+        ["Moon landing"]
+    );
+    assert_eq!(
+        labels(Filter {
+            min_days: Some(14),
+            ..Default::default()
+        }),
+        ["Moon landing", "Berlin Wall Fall", "Moved"]
+    );
+    assert_eq!(
+        labels(Filter {
+            max_days: Some(14),
+            ..Default::default()
+        }),
+        ["Moved", "Today"]
+    );
+    assert_eq!(
+        labels(Filter {
+            before: Some(datetime!(1989-11-09 17:53 UTC)),
+            ..Default::default()
+        }),
+        ["Moon landing"]
+    );
+    assert_eq!(
+        labels(Filter {
+            after: Some(datetime!(1989-11-09 17:53 UTC)),
+            ..Default::default()
+        }),
+        ["Berlin Wall Fall", "Moved", "Today"]
+    );
+    assert_eq!(
+        labels(Filter {
+            exclude: vec![" moon LANDING ".to_string(), "Today".to_string()],
+            ..Default::default()
+        }),
+        ["Berlin Wall Fall", "Moved"]
+    );
+
+    // combined, before the limit
+    let filter = Filter {
+        tags: vec!["history".to_string()],
+        min_days: Some(1000),
+        after: Some(datetime!(1980-01-01 0:00 UTC)),
+        ..Default::default()
+    };
+    assert_eq!(labels(filter.clone()), ["Berlin Wall Fall"]);
+    let selection = Selection {
+        filter: filter.clone(),
+        limit: 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        selection.achievements(&config, now)[0].day.label,
+        "Berlin Wall Fall"
+    );
+
+    let mut days = config.days.clone();
+    filter.retain(&mut days, now);
+    assert_eq!(days.len(), 1);
+    assert_eq!(days[0].label, "Berlin Wall Fall");
+}
+
+#[test]
+fn test_hash() {
+    use std::collections::HashSet;
+
+    let intervals: HashSet<Interval> = [7, 14, 7, 365, 14]
+        .into_iter()
+        .map(Interval::from_days)
+        .collect();
+    assert_eq!(intervals.len(), 3);
+    assert!(intervals.contains(&Interval::from_days(365)));
+    assert!(!intervals.contains(&Interval::from_days(3)));
+
+    let kinds: HashSet<&IntervalEnum> = intervals.iter().map(Interval::kind).collect();
+    assert!(kinds.contains(&IntervalEnum::Week(2)));
+    assert!(!kinds.contains(&IntervalEnum::Day(14)));
+}
+
+#[test]
+fn test_comparison() {
+    use time::macros::datetime;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let moon_landing = Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    let berlin_wall_fall = Day::new("Berlin Wall Fall", datetime!(1989-11-09 18:53 +01:00));
+
+    for (a, b) in [
+        (moon_landing.clone(), berlin_wall_fall.clone()),
+        (berlin_wall_fall.clone(), moon_landing.clone()),
+    ] {
+        let comparison = Comparison::new(a, b, now);
+        assert_eq!(comparison.earlier.day.label, "Moon landing");
+        assert_eq!(comparison.later.day.label, "Berlin Wall Fall");
+        assert_eq!(comparison.gap.as_days(), 7416);
+        assert_eq!(comparison.earlier.interval.as_days(), 19887);
+    }
+
+    let same_day = Day::new("Eagle has landed", moon_landing.date);
+    let comparison = Comparison::new(same_day, moon_landing, now);
+    assert_eq!(comparison.earlier.day.label, "Eagle has landed");
+    assert_eq!(comparison.gap.as_days(), 0);
+
+    let json = serde_json::to_value(&comparison).expect("Failed to serialize");
+    assert_eq!(json["later"]["label"], "Moon landing");
+    assert_eq!(json["gap"]["days"], 0);
+}
+
+#[test]
+fn test_next_milestone() {
+    let milestone = |days| Interval::from_days(days).next_milestone();
+
+    assert_eq!(Milestone::Days(100), milestone(0));
+    assert_eq!(Milestone::Days(100), milestone(1));
+    assert_eq!(Milestone::Days(100), milestone(100));
+    assert_eq!(Milestone::Years(1), milestone(101));
+    assert_eq!(Milestone::Years(1), milestone(YEAR));
+    assert_eq!(Milestone::Days(500), milestone(YEAR + 1));
+    assert_eq!(Milestone::Years(2), milestone(501));
+    assert_eq!(Milestone::Days(1000), milestone(2 * YEAR + 1));
+    assert_eq!(Milestone::Years(3), milestone(1001));
+    assert_eq!(Milestone::Days(2000), milestone(5 * YEAR + 1));
+    assert_eq!(Milestone::Years(10), milestone(10 * YEAR));
+
+    assert_eq!(0, Milestone::Days(100).previous_days());
+    assert_eq!(100, Milestone::Years(1).previous_days());
+    assert_eq!(365, Milestone::Days(500).previous_days());
+    assert_eq!(2 * YEAR, Milestone::Days(1000).previous_days());
+    assert_eq!(5 * YEAR, Milestone::Days(2000).previous_days());
+    assert_eq!(9 * YEAR, Milestone::Years(10).previous_days());
+
+    assert_eq!("5 years", Milestone::Years(5).to_string());
+    assert_eq!("1 year", Milestone::Years(1).to_string());
+    assert_eq!("1000 days", Milestone::Days(1000).to_string());
+}
+
+#[test]
+fn test_upcoming() {
+    use time::macros::datetime;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let upcoming = |date| Upcoming::new(Day::new("Wedding", date), now);
+
+    let wedding = upcoming(datetime!(2019-01-03 12:00 UTC));
+    assert_eq!(Milestone::Years(5), wedding.milestone);
+    assert_eq!(1, wedding.in_days());
+    assert!(!wedding.is_today());
+
+    assert_eq!(364.0 / 365.0, wedding.progress());
+
+    let today = upcoming(datetime!(2019-01-02 12:00 UTC));
+    assert_eq!(Milestone::Years(5), today.milestone);
+    assert!(today.is_today());
+
+    let json = serde_json::to_value(&today).expect("Failed to serialize");
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "label": "Wedding",
+            "date": "2019-01-02T12:00:00Z",
+            "milestone": "5 years",
+            "milestone_days": 5 * YEAR,
+            "in_days": 0,
+            "today": true,
+            "progress": 1.0,
+        })
+    );
+}
+
+#[test]
+fn test_parse_date() {
+    use time::macros::datetime;
+
+    assert_eq!(Ok(datetime!(1969-07-20 0:00 UTC)), parse_date("1969-07-20"));
+    assert_eq!(
+        Ok(datetime!(1989-11-09 18:53 +01:00)),
+        parse_date(" 1989-11-09T18:53:00+01:00\n")
+    );
+    assert!(parse_date("").is_err());
+    assert!(parse_date("1969-13-20").is_err());
+    assert!(parse_date("yesterday").is_err());
+}
+
+#[test]
+fn test_parse_relative_date() {
+    use time::macros::datetime;
+
+    let now = datetime!(2024-03-01 12:00 UTC);
+    let parse = |input| parse_relative_date(input, now);
+
+    assert_eq!(parse("today"), Some(now));
+    assert_eq!(parse(" Yesterday "), Some(datetime!(2024-02-29 12:00 UTC)));
+    assert_eq!(parse("3 days ago"), Some(datetime!(2024-02-27 12:00 UTC)));
+    assert_eq!(parse("2w ago"), Some(datetime!(2024-02-16 12:00 UTC)));
+    assert_eq!(parse("1 year ago"), Some(datetime!(2023-03-02 12:00 UTC)));
+    assert_eq!(parse("0d ago"), Some(now));
+
+    assert_eq!(parse("ago"), None);
+    assert_eq!(parse("3 days"), None);
+    assert_eq!(parse("in 3 days"), None);
+    assert_eq!(parse("2024-01-01"), None);
+    assert_eq!(parse("99999999 decades ago"), None);
+    assert_eq!(parse("999999999999999 decades ago"), None);
+}
+
+#[test]
+fn test_achievement_to_json() {
+    use time::macros::datetime;
+
+    let day = Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    let achievement = Achievement::new(day, datetime!(1969-08-03 20:17:40 UTC));
+
+    let json = serde_json::to_value(&achievement).expect("Failed to serialize");
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "label": "Moon landing",
+            "date": "1969-07-20T20:17:40Z",
+            "days": 14,
+            "words": "2 weeks",
+            "badges": "★★",
+        })
+    );
+}
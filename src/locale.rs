@@ -0,0 +1,807 @@
+//! Languages the intervals can be displayed in
+//!
+//! A `Locale` provides the words for each unit, the words for a zero days
+//! interval and the special celebration messages. `English` is the
+//! default locale used by `Interval::to_words()`, `Italian` is also built
+//! in.
+//!
+//! The built-in locales are `Words` tables. The form of a unit is picked
+//! by the plural category of the number, as in the CLDR plural rules, so
+//! languages with more than a singular and a plural (e.g. Russian "1 год",
+//! "2 года", "5 лет") only need a table.
+//!
+//! `Display for Interval` uses the locale of the current thread instead,
+//! see `IntervalLocale`. The binary sets it from `--lang` or the
+//! environment, see `detect_locale()`.
+
+use std::cell::Cell;
+
+use clap::ValueEnum;
+use time::{Date, Weekday};
+
+use crate::IntervalEnum;
+
+/// Words used to display an `Interval`
+pub trait Locale {
+    /// Number of days in words, e.g. "1 day", "2 days"
+    fn day(&self, n: usize) -> String;
+    /// Number of weeks in words, e.g. "1 week", "2 weeks"
+    fn week(&self, n: usize) -> String;
+    /// Number of months in words, e.g. "1 month", "2 months"
+    fn month(&self, n: usize) -> String;
+    /// Number of years in words, e.g. "1 year", "2 years"
+    fn year(&self, n: usize) -> String;
+    /// Number of decades in words, e.g. "1 decade", "2 decades"
+    fn decade(&self, n: usize) -> String;
+    /// Words for a zero days interval, e.g. "Recently"
+    fn recently(&self) -> String;
+    /// Celebration message shown after the interval words, if any
+    fn message(&self, interval: &IntervalEnum) -> Option<String>;
+    /// Total number of days, e.g. "827 days total", English unless
+    /// implemented
+    fn total_days(&self, n: usize) -> String {
+        format!("{} total", self.day(n))
+    }
+}
+
+/// CLDR plural category of a whole number, picking the form of a word
+///
+/// Only the categories of whole numbers in the built-in rules, "zero" and
+/// "two" would be added with a language using them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    /// E.g. 1 in English, 1, 21, 31... in Russian
+    One,
+    /// E.g. 2-4, 22-24... in Russian and Polish
+    Few,
+    /// E.g. 0, 5-20, 25-30... in Russian and Polish
+    Many,
+    /// Everything else, e.g. all but 1 in English
+    Other,
+}
+
+impl PluralCategory {
+    /// Rule of English: one for 1, other otherwise
+    pub fn english(n: usize) -> Self {
+        if n == 1 {
+            PluralCategory::One
+        } else {
+            PluralCategory::Other
+        }
+    }
+
+    /// Rule of Italian, the same as English for whole numbers
+    pub fn italian(n: usize) -> Self {
+        PluralCategory::english(n)
+    }
+
+    /// Rule of Russian: one for 1, 21, 31... but 11, few for 2-4, 22-24...
+    /// but 12-14, many otherwise
+    pub fn russian(n: usize) -> Self {
+        match (n % 10, n % 100) {
+            (1, last_two) if last_two != 11 => PluralCategory::One,
+            (2..=4, last_two) if !(12..=14).contains(&last_two) => PluralCategory::Few,
+            _ => PluralCategory::Many,
+        }
+    }
+
+    /// Rule of Polish: one only for 1, few for 2-4, 22-24... but 12-14,
+    /// many otherwise
+    pub fn polish(n: usize) -> Self {
+        match (n, n % 10, n % 100) {
+            (1, _, _) => PluralCategory::One,
+            (_, 2..=4, last_two) if !(12..=14).contains(&last_two) => PluralCategory::Few,
+            _ => PluralCategory::Many,
+        }
+    }
+}
+
+/// Plural category of a whole number in a language, e.g.
+/// `PluralCategory::russian`
+pub type PluralRule = fn(usize) -> PluralCategory;
+
+/// Forms of a word for each plural category
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Forms {
+    pub one: &'static str,
+    pub few: &'static str,
+    pub many: &'static str,
+    pub other: &'static str,
+}
+
+impl Forms {
+    /// Forms of a language with a singular and a plural only
+    pub const fn new(one: &'static str, other: &'static str) -> Self {
+        Forms {
+            one,
+            few: other,
+            many: other,
+            other,
+        }
+    }
+
+    /// Returns the form for the category
+    pub fn get(&self, category: PluralCategory) -> &'static str {
+        match category {
+            PluralCategory::One => self.one,
+            PluralCategory::Few => self.few,
+            PluralCategory::Many => self.many,
+            PluralCategory::Other => self.other,
+        }
+    }
+}
+
+/// Words of a language, for the intervals and the messages of the binary
+///
+/// Each built-in locale is one of these tables, so adding a language is
+/// adding a table (and a `Language`).
+#[derive(Debug, Clone, Copy)]
+pub struct Words {
+    /// Plural rule of the language, picking the forms of the units
+    pub plural: PluralRule,
+    /// Forms of the units
+    pub day: Forms,
+    pub week: Forms,
+    pub month: Forms,
+    pub year: Forms,
+    pub decade: Forms,
+    /// Words for a zero days interval
+    pub recently: &'static str,
+    /// Total number of days, with `{days}`, after the compound units
+    pub total_days: &'static str,
+    /// Celebration messages after 1 year and 1 decade
+    pub one_year: &'static str,
+    pub one_decade: &'static str,
+    /// Hint of the overviews when there are no days
+    pub no_achievements: &'static str,
+    /// When an upcoming milestone is reached, today or in `{days}`
+    pub today: &'static str,
+    pub in_days: &'static str,
+    /// Hint of `upcoming` when no milestone is coming up, with `{days}`
+    pub nothing_upcoming: &'static str,
+    /// Time elapsed so far, with `{elapsed}`, in `top-upcoming`
+    pub elapsed_now: &'static str,
+    /// A closed period, it has no next milestone
+    pub completed: &'static str,
+    /// Start of an achievement, with `{date}`, in `stats`
+    pub since: &'static str,
+    /// Question before removing a day, with `{label}` and `{date}`
+    pub remove_day: &'static str,
+    /// Question before removing several days, with `{count}`
+    pub remove_days: &'static str,
+    /// Choices after a yes or no question, no being the default
+    pub yes_no: &'static str,
+    /// Lowercase answers meaning yes to a yes or no question
+    pub yes_answers: &'static [&'static str],
+    /// Word to type to confirm a destructive action
+    pub confirm_word: &'static str,
+    /// Asks to type the `{word}` to confirm
+    pub type_to_continue: &'static str,
+    /// Warnings before resetting the config, to its backup or to no days
+    pub restore_backup: &'static str,
+    pub delete_all: &'static str,
+    /// The user didn't confirm
+    pub aborted: &'static str,
+    /// Names of the months, January first, in full and abbreviated
+    pub months: [&'static str; 12],
+    pub months_short: [&'static str; 12],
+    /// Names of the weekdays, Monday first
+    pub weekdays: [&'static str; 7],
+}
+
+impl Words {
+    pub const ENGLISH: Words = Words {
+        plural: PluralCategory::english,
+        day: Forms::new("day", "days"),
+        week: Forms::new("week", "weeks"),
+        month: Forms::new("month", "months"),
+        year: Forms::new("year", "years"),
+        decade: Forms::new("decade", "decades"),
+        recently: "Recently",
+        total_days: "{days} total",
+        one_year: "happy anniversary!",
+        one_decade: "that's amazing",
+        no_achievements: "No achievements tracked yet — run 'achievements init' or \
+                          'achievements add' to get started.",
+        today: "today",
+        in_days: "in {days}",
+        nothing_upcoming: "Nothing coming up in the next {days} 🌵",
+        elapsed_now: "{elapsed} now",
+        completed: "completed",
+        since: "since {date}",
+        remove_day: "Remove '{label}' ({date})?",
+        remove_days: "Remove these {count} days?",
+        yes_no: "[y/N]",
+        yes_answers: &["y", "yes"],
+        confirm_word: "yes",
+        type_to_continue: "Type '{word}' to continue:",
+        restore_backup: "This will replace the config with its backup.",
+        delete_all: "This will delete all tracked achievements.",
+        aborted: "Aborted",
+        months: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        months_short: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        weekdays: [
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+            "Sunday",
+        ],
+    };
+
+    pub const ITALIAN: Words = Words {
+        plural: PluralCategory::italian,
+        day: Forms::new("giorno", "giorni"),
+        week: Forms::new("settimana", "settimane"),
+        month: Forms::new("mese", "mesi"),
+        year: Forms::new("anno", "anni"),
+        decade: Forms::new("decennio", "decenni"),
+        recently: "Di recente",
+        total_days: "{days} in tutto",
+        one_year: "buon anniversario!",
+        one_decade: "che meraviglia!",
+        no_achievements: "Ancora nessun traguardo — esegui 'achievements init' o \
+                          'achievements add' per iniziare.",
+        today: "oggi",
+        in_days: "tra {days}",
+        nothing_upcoming: "Niente in arrivo nei prossimi {days} 🌵",
+        elapsed_now: "{elapsed} finora",
+        completed: "completato",
+        since: "dal {date}",
+        remove_day: "Rimuovere '{label}' ({date})?",
+        remove_days: "Rimuovere questi {count} giorni?",
+        yes_no: "[s/N]",
+        yes_answers: &["s", "si", "sì"],
+        confirm_word: "si",
+        type_to_continue: "Scrivi '{word}' per continuare:",
+        restore_backup: "La configurazione verrà sostituita dal suo backup.",
+        delete_all: "Tutti i traguardi registrati verranno eliminati.",
+        aborted: "Annullato",
+        months: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        months_short: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        weekdays: [
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+            "domenica",
+        ],
+    };
+
+    /// Replaces the `{name}` placeholders of one of the words
+    ///
+    /// ```
+    /// use achievements::locale::Words;
+    ///
+    /// let question = Words::fill(Words::ITALIAN.remove_days, &[("count", "3")]);
+    /// assert_eq!("Rimuovere questi 3 giorni?", question);
+    /// ```
+    pub fn fill(words: &str, values: &[(&str, &str)]) -> String {
+        values
+            .iter()
+            .fold(words.to_string(), |words, (name, value)| {
+                words.replace(&format!("{{{name}}}"), value)
+            })
+    }
+
+    /// Name of the weekday
+    ///
+    /// ```
+    /// use achievements::locale::Words;
+    /// use time::Weekday;
+    ///
+    /// assert_eq!("domenica", Words::ITALIAN.weekday(Weekday::Sunday));
+    /// ```
+    pub fn weekday(&self, weekday: Weekday) -> &'static str {
+        self.weekdays[usize::from(weekday.number_days_from_monday())]
+    }
+
+    /// "{n} {form}", the form of the word for the plural category of `n`
+    fn count(&self, n: usize, word: Forms) -> String {
+        format!("{n} {}", word.get((self.plural)(n)))
+    }
+}
+
+impl Locale for Words {
+    fn day(&self, n: usize) -> String {
+        self.count(n, self.day)
+    }
+
+    fn week(&self, n: usize) -> String {
+        self.count(n, self.week)
+    }
+
+    fn month(&self, n: usize) -> String {
+        self.count(n, self.month)
+    }
+
+    fn year(&self, n: usize) -> String {
+        self.count(n, self.year)
+    }
+
+    fn decade(&self, n: usize) -> String {
+        self.count(n, self.decade)
+    }
+
+    fn recently(&self) -> String {
+        self.recently.to_string()
+    }
+
+    fn message(&self, interval: &IntervalEnum) -> Option<String> {
+        match interval {
+            IntervalEnum::Decade(1) => Some(self.one_decade.to_string()),
+            IntervalEnum::Year(1) => Some(self.one_year.to_string()),
+            _ => None,
+        }
+    }
+
+    fn total_days(&self, n: usize) -> String {
+        Words::fill(self.total_days, &[("days", &self.day(n))])
+    }
+}
+
+/// English locale, see `Words::ENGLISH`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct English;
+
+/// Italian locale, see `Words::ITALIAN`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Italian;
+
+impl Locale for English {
+    fn day(&self, n: usize) -> String {
+        Words::ENGLISH.day(n)
+    }
+
+    fn week(&self, n: usize) -> String {
+        Words::ENGLISH.week(n)
+    }
+
+    fn month(&self, n: usize) -> String {
+        Words::ENGLISH.month(n)
+    }
+
+    fn year(&self, n: usize) -> String {
+        Words::ENGLISH.year(n)
+    }
+
+    fn decade(&self, n: usize) -> String {
+        Words::ENGLISH.decade(n)
+    }
+
+    fn recently(&self) -> String {
+        Words::ENGLISH.recently()
+    }
+
+    fn message(&self, interval: &IntervalEnum) -> Option<String> {
+        Words::ENGLISH.message(interval)
+    }
+
+    fn total_days(&self, n: usize) -> String {
+        Words::ENGLISH.total_days(n)
+    }
+}
+
+impl Locale for Italian {
+    fn day(&self, n: usize) -> String {
+        Words::ITALIAN.day(n)
+    }
+
+    fn week(&self, n: usize) -> String {
+        Words::ITALIAN.week(n)
+    }
+
+    fn month(&self, n: usize) -> String {
+        Words::ITALIAN.month(n)
+    }
+
+    fn year(&self, n: usize) -> String {
+        Words::ITALIAN.year(n)
+    }
+
+    fn decade(&self, n: usize) -> String {
+        Words::ITALIAN.decade(n)
+    }
+
+    fn recently(&self) -> String {
+        Words::ITALIAN.recently()
+    }
+
+    fn message(&self, interval: &IntervalEnum) -> Option<String> {
+        Words::ITALIAN.message(interval)
+    }
+
+    fn total_days(&self, n: usize) -> String {
+        Words::ITALIAN.total_days(n)
+    }
+}
+
+/// How dates are written, see `format_date()`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum DateStyle {
+    /// Numeric, e.g. 1969-07-20
+    #[default]
+    Short,
+    /// With the abbreviated month, e.g. 20 Jul 1969
+    Medium,
+    /// With the month in full, e.g. 20 July 1969
+    Long,
+}
+
+/// Formats the date in the style, with the month names of the locale
+///
+/// ```
+/// use achievements::locale::{format_date, DateStyle, Words};
+/// use time::macros::date;
+///
+/// let date = date!(1969 - 07 - 20);
+/// assert_eq!("1969-07-20", format_date(date, &Words::ITALIAN, DateStyle::Short));
+/// assert_eq!("20 luglio 1969", format_date(date, &Words::ITALIAN, DateStyle::Long));
+/// ```
+pub fn format_date(date: Date, locale: &Words, style: DateStyle) -> String {
+    let month = usize::from(u8::from(date.month())) - 1;
+    match style {
+        DateStyle::Short => format!(
+            "{:04}-{:02}-{:02}",
+            date.year(),
+            u8::from(date.month()),
+            date.day()
+        ),
+        DateStyle::Medium => format!(
+            "{} {} {}",
+            date.day(),
+            locale.months_short[month],
+            date.year()
+        ),
+        DateStyle::Long => format!("{} {} {}", date.day(), locale.months[month], date.year()),
+    }
+}
+
+/// Built-in locales, e.g. for `--lang`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Language {
+    #[default]
+    English,
+    Italian,
+}
+
+impl Language {
+    /// Returns the locale of the language
+    pub fn locale(self) -> Box<dyn Locale> {
+        Box::new(*self.words())
+    }
+
+    /// Returns the words of the language
+    pub fn words(self) -> &'static Words {
+        match self {
+            Language::English => &Words::ENGLISH,
+            Language::Italian => &Words::ITALIAN,
+        }
+    }
+
+    /// Returns the language of a POSIX locale name like "it_IT.UTF-8",
+    /// `None` if it's not a built-in one or the name is malformed
+    ///
+    /// ```
+    /// use achievements::locale::Language;
+    ///
+    /// assert_eq!(Some(Language::Italian), Language::from_posix("it_IT.UTF-8"));
+    /// assert_eq!(Some(Language::English), Language::from_posix("en_GB"));
+    /// assert_eq!(None, Language::from_posix("C"));
+    /// ```
+    pub fn from_posix(name: &str) -> Option<Self> {
+        // language[_territory][.codeset][@modifier]
+        let name = name.split(['.', '@']).next().unwrap_or_default();
+        let language = name.split('_').next().unwrap_or_default();
+        match language.to_lowercase().as_str() {
+            "en" => Some(Language::English),
+            "it" => Some(Language::Italian),
+            _ => None,
+        }
+    }
+}
+
+/// Environment variables of the locale, most important first
+pub const LOCALE_ENV_VARS: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+/// Returns the language of the environment, looking up the variables
+/// with `var`, e.g. `|name| std::env::var(name).ok()`
+///
+/// The first variable set (and not empty) of `LC_ALL`, `LC_MESSAGES` and
+/// `LANG` decides, like for other programs. English when none is set or
+/// its locale isn't built in, e.g. "C" or "de_DE.UTF-8".
+pub fn detect_locale(var: impl Fn(&str) -> Option<String>) -> Language {
+    LOCALE_ENV_VARS
+        .into_iter()
+        .filter_map(var)
+        .find(|value| !value.trim().is_empty())
+        .and_then(|value| Language::from_posix(value.trim()))
+        .unwrap_or_default()
+}
+
+thread_local! {
+    static CURRENT: Cell<&'static dyn Locale> = Cell::new(&English);
+}
+
+/// Locale of the current thread, used by `Display for Interval`
+///
+/// Set it once at startup to display the intervals in another language
+/// without passing the locale around:
+///
+/// ```
+/// use achievements::{
+///     locale::{English, IntervalLocale, Locale},
+///     Interval, IntervalEnum,
+/// };
+///
+/// struct Shouting;
+///
+/// impl Locale for Shouting {
+///     fn day(&self, n: usize) -> String {
+///         English.day(n).to_uppercase()
+///     }
+///     fn week(&self, n: usize) -> String {
+///         English.week(n).to_uppercase()
+///     }
+///     fn month(&self, n: usize) -> String {
+///         English.month(n).to_uppercase()
+///     }
+///     fn year(&self, n: usize) -> String {
+///         English.year(n).to_uppercase()
+///     }
+///     fn decade(&self, n: usize) -> String {
+///         English.decade(n).to_uppercase()
+///     }
+///     fn recently(&self) -> String {
+///         "RECENTLY".to_string()
+///     }
+///     fn message(&self, _interval: &IntervalEnum) -> Option<String> {
+///         None
+///     }
+/// }
+///
+/// IntervalLocale::set_current(Box::new(Shouting));
+/// assert_eq!("2 WEEKS ★★", Interval::from_days(14).to_string());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct IntervalLocale;
+
+impl IntervalLocale {
+    /// Returns the locale of the current thread, `English` unless set
+    pub fn current() -> &'static dyn Locale {
+        CURRENT.with(Cell::get)
+    }
+
+    /// Sets the locale of the current thread
+    ///
+    /// The locale is leaked to live until the end of the program, set it
+    /// once rather than every time an interval is displayed.
+    pub fn set_current(locale: Box<dyn Locale>) {
+        CURRENT.with(|current| current.set(Box::leak(locale)));
+    }
+}
+
+#[test]
+fn detect_locale_test() {
+    let env = |vars: &'static [(&'static str, &'static str)]| {
+        move |name: &str| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        }
+    };
+
+    assert_eq!(detect_locale(env(&[])), Language::English);
+    assert_eq!(
+        detect_locale(env(&[("LANG", "it_IT.UTF-8")])),
+        Language::Italian
+    );
+    // LC_MESSAGES over LANG, LC_ALL over both
+    assert_eq!(
+        detect_locale(env(&[
+            ("LANG", "it_IT.UTF-8"),
+            ("LC_MESSAGES", "en_US.UTF-8")
+        ])),
+        Language::English
+    );
+    assert_eq!(
+        detect_locale(env(&[("LANG", "en_US"), ("LC_MESSAGES", "it_CH@euro")])),
+        Language::Italian
+    );
+    assert_eq!(
+        detect_locale(env(&[
+            ("LANG", "en_US"),
+            ("LC_MESSAGES", "en_US"),
+            ("LC_ALL", "it")
+        ])),
+        Language::Italian
+    );
+    // Empty is unset
+    assert_eq!(
+        detect_locale(env(&[("LC_ALL", ""), ("LANG", "it_IT")])),
+        Language::Italian
+    );
+
+    // Unknown or malformed, English without falling back to the others
+    assert_eq!(
+        detect_locale(env(&[("LC_ALL", "C"), ("LANG", "it_IT")])),
+        Language::English
+    );
+    assert_eq!(
+        detect_locale(env(&[("LC_ALL", "de_DE.UTF-8"), ("LANG", "it_IT")])),
+        Language::English
+    );
+    assert_eq!(detect_locale(env(&[("LANG", "_IT.@")])), Language::English);
+    assert_eq!(
+        detect_locale(env(&[("LANG", "italian")])),
+        Language::English
+    );
+}
+
+#[test]
+fn format_date_test() {
+    use time::macros::date;
+
+    let cases = [
+        (DateStyle::Short, "1969-07-20", "1969-07-20"),
+        (DateStyle::Medium, "20 Jul 1969", "20 lug 1969"),
+        (DateStyle::Long, "20 July 1969", "20 luglio 1969"),
+    ];
+    for (style, in_english, in_italian) in cases {
+        let date = date!(1969 - 07 - 20);
+        assert_eq!(format_date(date, &Words::ENGLISH, style), in_english);
+        assert_eq!(format_date(date, &Words::ITALIAN, style), in_italian);
+    }
+
+    let date = date!(0999 - 01 - 02);
+    assert_eq!(
+        format_date(date, &Words::ENGLISH, DateStyle::Short),
+        "0999-01-02"
+    );
+    assert_eq!(
+        format_date(date, &Words::ITALIAN, DateStyle::Medium),
+        "2 gen 999"
+    );
+    let date = date!(2024 - 12 - 31);
+    assert_eq!(
+        format_date(date, &Words::ITALIAN, DateStyle::Long),
+        "31 dicembre 2024"
+    );
+}
+
+#[test]
+fn plural_rules_test() {
+    use PluralCategory::*;
+
+    let rules: [(PluralRule, &[(usize, PluralCategory)]); 4] = [
+        (
+            PluralCategory::english,
+            &[(0, Other), (1, One), (2, Other), (11, Other), (21, Other)],
+        ),
+        (
+            PluralCategory::italian,
+            &[(0, Other), (1, One), (2, Other), (11, Other), (21, Other)],
+        ),
+        (
+            PluralCategory::russian,
+            &[
+                (0, Many),
+                (1, One),
+                (2, Few),
+                (4, Few),
+                (5, Many),
+                (11, Many),
+                (12, Many),
+                (14, Many),
+                (20, Many),
+                (21, One),
+                (22, Few),
+                (101, One),
+                (111, Many),
+                (112, Many),
+                (122, Few),
+            ],
+        ),
+        (
+            PluralCategory::polish,
+            &[
+                (0, Many),
+                (1, One),
+                (2, Few),
+                (4, Few),
+                (5, Many),
+                (11, Many),
+                (12, Many),
+                (14, Many),
+                (21, Many),
+                (22, Few),
+                (101, Many),
+                (112, Many),
+                (122, Few),
+            ],
+        ),
+    ];
+    for (rule, cases) in rules {
+        for &(n, category) in cases {
+            assert_eq!(rule(n), category, "{n}");
+        }
+    }
+}
+
+#[test]
+fn plural_forms_test() {
+    use crate::Interval;
+
+    let russian = Words {
+        plural: PluralCategory::russian,
+        year: Forms {
+            one: "год",
+            few: "года",
+            many: "лет",
+            other: "года",
+        },
+        ..Words::ENGLISH
+    };
+    let polish = Words {
+        plural: PluralCategory::polish,
+        year: Forms {
+            one: "rok",
+            few: "lata",
+            many: "lat",
+            other: "roku",
+        },
+        ..Words::ENGLISH
+    };
+
+    let cases = [
+        (1, "1 год", "1 rok"),
+        (2, "2 года", "2 lata"),
+        (5, "5 лет", "5 lat"),
+        (11, "11 лет", "11 lat"),
+        (21, "21 год", "21 lat"),
+        (24, "24 года", "24 lata"),
+    ];
+    for (years, in_russian, in_polish) in cases {
+        assert_eq!(russian.year(years), in_russian);
+        assert_eq!(polish.year(years), in_polish);
+    }
+    // Picked by `to_words_in()` too
+    let interval = Interval::from_days(5 * 365);
+    assert_eq!(interval.to_words_in(&russian), "5 лет");
+
+    assert_eq!(Forms::new("day", "days").get(PluralCategory::Few), "days");
+}
@@ -1,70 +1,2472 @@
-use clap::{command, Parser, Subcommand};
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    path::PathBuf,
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
+    thread,
+    time::{Duration as StdDuration, Instant},
+};
+
+use clap::{
+    error::ErrorKind, parser::ValueSource, ArgMatches, Args, CommandFactory, FromArgMatches,
+    Parser, Subcommand, ValueEnum,
+};
+use clap_complete::Shell;
+use serde::Serialize;
+use time::{format_description::well_known::Rfc3339, macros::datetime, Duration, OffsetDateTime};
 
 use achievements::{
-    config::{self, Day},
-    days_since, Interval,
+    config::{
+        self, Config, ConfigError, Day, MergeSummary, OnConflict, SearchField, SearchOptions,
+        SortKey, ValidationIssue,
+    },
+    current_time, days_since_at,
+    doctor::{self, Check},
+    ics,
+    locale::{self, detect_locale, DateStyle, IntervalLocale, Language, Words},
+    parse_date, parse_relative_date, pick_random,
+    render::{self, template::Template, ColorChoice, ProgressBar, TextOptions},
+    tui::TuiState,
+    watch::{self, Redraw},
+    webhook, Achievement, BadgeStyle, Comparison, Filter, IntervalOptions, Selection,
 };
 
+/// Words of the language of the messages, picked with `--lang` or the
+/// environment
+static WORDS: OnceLock<&'static Words> = OnceLock::new();
+
+/// How many times `add` asks for the date before giving up
+const DATE_ATTEMPTS: usize = 3;
+
 #[derive(Subcommand)]
 enum Command {
     /// Displays the achivements
-    Achievements,
+    Achievements(AchievementsArgs),
     /// List days in the config
-    List,
-    /// Adds a day to the config
-    Add { label: String },
-    /// Removes a day from the config
-    Remove { label: String },
+    #[command(visible_alias = "ls")]
+    List(ListArgs),
+    /// Adds a day to the config, asks for the date if not given
+    #[command(visible_alias = "new")]
+    Add(AddArgs),
+    /// Removes days from the config, after asking for confirmation. Asks
+    /// to pick the day in a terminal when no label is given
+    #[command(visible_alias = "rm")]
+    Remove(RemoveArgs),
+    /// Displays a day with its date, weekday, number of days, tags and
+    /// notes. Asks to pick the day in a terminal when no label is given
+    Show {
+        label: Option<String>,
+        /// How the date is written
+        #[arg(long, value_enum, default_value_t = DateStyle::Long)]
+        date_format: DateStyle,
+    },
+    /// Adds a copy of a day with a new label
+    Duplicate { label: String, new_label: String },
+    /// Renames a day, keeping its date and other fields
+    #[command(visible_alias = "mv")]
+    Rename { label: String, new_label: String },
+    /// Compares two days, showing how long before the other each one is
+    Compare(CompareArgs),
+    /// Displays a one-line overview of the achievements
+    Summary,
+    /// Displays one of the days (not archived) picked at random, e.g. to
+    /// greet new terminals. Prints nothing when there are none
+    Random(RandomArgs),
+    /// Creates a config with a couple of example days
+    Init {
+        /// Overwrite the existing config, keeping a backup of it
+        #[arg(long)]
+        force: bool,
+        /// Create a config without days
+        #[arg(long)]
+        empty: bool,
+    },
+    /// Lists the round milestones (years, 100/500/1000 days) coming up soon
+    Upcoming(UpcomingArgs),
+    /// Displays the next milestone of each day, soonest first
+    Next(NextArgs),
+    /// Displays the days closest to their next milestone, with the time
+    /// elapsed so far and the days left
+    TopUpcoming {
+        /// Show at most N days, 0 means no limit
+        #[arg(long, value_name = "N", default_value_t = 5)]
+        count: usize,
+    },
+    /// Displays totals, averages and badge tiers of the days
+    Stats(StatsArgs),
+    /// Displays the achievements with labels containing the query, exits
+    /// with 1 if none does
+    Search(SearchArgs),
+    /// Prints the achievements on a single line for a shell prompt, e.g.
+    /// "🏃 412d · 💍 3y", nothing if there's no config
+    Prompt(PromptArgs),
+    /// Sends a desktop notification for each milestone reached today, e.g.
+    /// from a daily cron job or systemd timer, nothing if none is. Needs
+    /// the notifications feature, except with --dry-run
+    Notify {
+        /// Print the notifications instead of sending them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// POSTs the milestones reached today as JSON to a URL, nothing if none
+    /// is (see `achievements::webhook::Payload` for the schema). Needs the
+    /// webhook feature
+    Webhook(WebhookArgs),
+    /// Generates a shields.io-style SVG badge of a day, e.g. for a wiki
+    Svg(SvgArgs),
+    /// Prints a JSON object for a waybar (or polybar) custom module, with
+    /// the oldest achievement as text and all of them in the tooltip
+    Bar {
+        /// Label of the day shown as text, instead of the oldest
+        #[arg(long, value_name = "LABEL")]
+        entry: Option<String>,
+    },
+    /// Draws a bar per day, proportional to its number of days, followed
+    /// by its label and number of days
+    Chart {
+        #[command(flatten)]
+        sort: SortArgs,
+        /// Width of the chart in columns, default is the terminal width
+        /// (or 80)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u16).range(10..))]
+        width: Option<u16>,
+        /// ASCII-only output: bars made of '#' and non-ASCII characters in
+        /// labels replaced with '?'
+        #[arg(long)]
+        plain: bool,
+    },
+    /// Draws the days on a timeline from the oldest one to today, with
+    /// the labels too close to each other stacked on more rows
+    Timeline {
+        /// Width of the timeline in columns, default is the terminal width
+        /// (or 80)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u16).range(10..))]
+        width: Option<u16>,
+    },
+    /// Prints the 12 months of a year with the anniversaries of the days
+    /// marked by their icons (or letters), and a legend
+    Calendar {
+        /// Year of the calendar, default is the current one
+        #[arg(long, value_parser = clap::value_parser!(i32).range(1..=9999))]
+        year: Option<i32>,
+        /// ASCII-only output: letters as marks and non-ASCII characters in
+        /// labels replaced with '?'
+        #[arg(long)]
+        plain: bool,
+        /// Also highlight the anniversaries in reverse video. Default is
+        /// auto, i.e. when stdout is a terminal and NO_COLOR is not set
+        /// (never with --plain)
+        #[arg(long, value_enum, value_name = "WHEN")]
+        color: Option<ColorChoice>,
+    },
+    /// Clears the screen and displays the achievements every N seconds,
+    /// e.g. for a dashboard terminal, until Ctrl-C. The config is read
+    /// again each time and the days whose count changed are highlighted
+    Watch {
+        /// Seconds between updates, from 1 to 86400 (a day)
+        #[arg(long, value_name = "SECS", default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..=86400))]
+        interval: u64,
+    },
+    /// Browses the days in an interactive terminal UI, to filter, sort,
+    /// archive or delete them. Changes are saved on quit. Needs the tui
+    /// feature
+    Tui,
+    /// Imports days from a JSON (same as the config), TOML, CSV or
+    /// iCalendar file
+    Import(ImportArgs),
+    /// Exports the config as JSON, TOML or CSV, the anniversaries as
+    /// iCalendar or an HTML report
+    Export(ExportArgs),
+    /// Checks the config file for issues, exits with 1 if any is found
+    Doctor {
+        /// Trim the labels and remove exact duplicate days, keeping a
+        /// backup of the config
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Prints the shell completion script, e.g. for bash:
+    /// `achievements completions bash > ~/.local/share/bash-completion/completions/achievements`
+    Completions { shell: Shell },
+    /// Prints the man page, or writes the pages of all the subcommands
+    /// to a directory
+    Man {
+        /// Directory to write the man pages to, instead of printing the
+        /// main one
+        #[arg(long, value_name = "DIR")]
+        output_dir: Option<PathBuf>,
+    },
+    /// Prints the labels one per line, used by the completion scripts
+    #[command(name = "__labels", hide = true)]
+    Labels,
+    /// Inspects the config file
+    #[command(subcommand)]
+    Config(ConfigCommand),
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Prints the path of the config file
+    Path,
+    /// Prints the path and the content of the config file
+    Show,
+    /// Checks the config for duplicate or empty labels, dates in the
+    /// future or before --min-date and unknown fields. Exits with 1 if
+    /// there are warnings, 2 if the config can't be read (or with
+    /// --strict and warnings)
+    Validate {
+        /// Report the warnings as errors
+        #[arg(long)]
+        strict: bool,
+        /// Oldest valid date, older ones are likely typos
+        #[arg(long, value_name = "DATE", value_parser = parse_date_arg, default_value = "1900-01-01")]
+        min_date: OffsetDateTime,
+    },
+    /// Merges the days of another config file (e.g. a backup or an
+    /// export) into the config, the counterpart of `export`. Days with
+    /// labels already in the config are skipped unless --overwrite is given
+    Import {
+        /// JSON config file to import the days from
+        path: PathBuf,
+        /// Update the days already in the config with the imported ones
+        #[arg(long)]
+        overwrite: bool,
+    },
+    /// Deletes all the days in the config, keeping a backup of it
+    Reset {
+        /// Restore the config from the backup instead
+        #[arg(long)]
+        from_backup: bool,
+        /// Don't ask for confirmation
+        #[arg(long, short, visible_alias = "force")]
+        yes: bool,
+    },
+}
+
+#[derive(Args, Default)]
+struct AchievementsArgs {
+    #[command(flatten)]
+    filter: FilterArgs,
+    #[command(flatten)]
+    sort: SortArgs,
+    /// Show at most N achievements (after sorting), 0 means no limit
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    limit: usize,
+    /// Newest first, same as `--sort days`
+    #[arg(long, conflicts_with_all = ["sort", "oldest"])]
+    newest: bool,
+    /// Oldest first, same as `--sort days --reverse`
+    #[arg(long, conflicts_with = "sort")]
+    oldest: bool,
+    /// Output format
+    #[arg(long, visible_alias = "output", value_enum, default_value_t)]
+    format: Format,
+    /// Compact JSON output (one line) instead of pretty-printed, without
+    /// special messages in the words
+    #[arg(long)]
+    compact: bool,
+    /// Omit the CSV header row
+    #[arg(long)]
+    no_header: bool,
+    /// Don't show the badges in the text output (overrides the config
+    /// badge_style)
+    #[arg(long)]
+    no_badges: bool,
+    /// ASCII badges in the text output, e.g. "++" instead of "★★"
+    /// (overrides the config badge_style)
+    #[arg(long)]
+    ascii: bool,
+    /// Don't show special messages like "1 year, happy anniversary!"
+    #[arg(long)]
+    no_messages: bool,
+    /// Abbreviated interval words, e.g. "3y" or "5wk"
+    #[arg(long)]
+    short: bool,
+    /// Show a progress bar towards the next milestone after the badges in
+    /// the text output, e.g. "[████████░░] 75% to 1 year"
+    #[arg(long)]
+    progress: bool,
+    /// Width of the progress bars
+    #[arg(long, value_name = "W", default_value_t = 10, requires = "progress")]
+    progress_width: usize,
+    /// ASCII-only text output: ASCII badges, no special messages and
+    /// non-ASCII characters in labels replaced with '?'
+    #[arg(long)]
+    plain: bool,
+    /// Don't align the text output in columns
+    #[arg(long)]
+    no_align: bool,
+    /// Color the text output: bold labels and the interval words colored
+    /// by unit. Default is auto, i.e. when stdout is a terminal and
+    /// NO_COLOR is not set (never with --plain)
+    #[arg(long, value_enum, value_name = "WHEN")]
+    color: Option<ColorChoice>,
+    /// Render each achievement with a template, e.g.
+    /// "{label:<20} | {days:>6} days | {badges}" (overrides the config
+    /// template). Placeholders: {label}, {date}, {days}, {words},
+    /// {words_short}, {badges}, {weekday}, optionally with a fill, an
+    /// alignment (<, > or ^) and a width after a colon; use {{ and }} for
+    /// literal braces
+    #[arg(long, visible_alias = "format-string", conflicts_with = "format")]
+    template: Option<String>,
+    /// Only the achievements reaching a whole number of weeks, months,
+    /// years or decades today, nothing if none does
+    #[arg(long, conflicts_with_all = ["format", "template"])]
+    today_milestones: bool,
+    /// Only the achievements reaching a round milestone today (a whole
+    /// number of years or 100, 500, 1000, 2000... days, like `notify`),
+    /// nothing if none does
+    #[arg(long, conflicts_with = "today_milestones")]
+    only_milestones: bool,
+    /// Exit with 1 when --only-milestones shows nothing
+    #[arg(long, requires = "only_milestones")]
+    exit_code: bool,
+}
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum Format {
+    /// Human-readable text with badges
+    #[default]
+    Text,
+    /// Array of JSON objects, one per achievement
+    Json,
+    /// CSV with label, date, days and interval columns
+    Csv,
+    /// Markdown table
+    Markdown,
+    /// Markdown bullet list
+    MarkdownList,
+    /// Self-contained HTML page with a table, rows colored by tier
+    Html,
+}
+
+#[derive(Args)]
+struct RemoveArgs {
+    /// Labels of the days to remove, picked from a list when none is
+    /// given and stdin is a terminal
+    labels: Vec<String>,
+    /// Treat the labels as glob patterns, `*` matches any characters and
+    /// `?` a single one, e.g. "old project *"
+    #[arg(long)]
+    glob: bool,
+    /// Don't remove anything if any label is not found
+    #[arg(long)]
+    strict: bool,
+    /// Remove all the days matching the `--matching` pattern
+    #[arg(long, requires = "matching", conflicts_with_all = ["labels", "glob", "strict"])]
+    all: bool,
+    /// Labels containing PATTERN, or matching it if it's a glob pattern
+    /// (with `*` or `?`), ignoring case
+    #[arg(long, value_name = "PATTERN", requires = "all")]
+    matching: Option<String>,
+    /// Only show the days that would be removed
+    #[arg(long)]
+    dry_run: bool,
+    /// Don't ask for confirmation, required when stdin is not a
+    /// terminal
+    #[arg(short, long)]
+    yes: bool,
+}
+
+#[derive(Args)]
+struct ListArgs {
+    #[command(flatten)]
+    filter: FilterArgs,
+    #[command(flatten)]
+    sort: SortArgs,
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    format: ListFormat,
+    /// Omit the CSV header row
+    #[arg(long)]
+    no_header: bool,
+    /// Show the date, weekday, time elapsed (in all units and in days),
+    /// tags, notes and whether it's archived for each day
+    #[arg(long, short, conflicts_with = "format")]
+    verbose: bool,
+    /// Show the time elapsed since each day, in words, after its date
+    #[arg(long, conflicts_with_all = ["format", "verbose"])]
+    with_elapsed: bool,
+    /// How the dates are written, in the language of --lang (default: the
+    /// full timestamps, or short with --verbose)
+    #[arg(long, value_enum, conflicts_with_all = ["format", "with_elapsed"])]
+    date_format: Option<DateStyle>,
+}
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum ListFormat {
+    /// Label and date of each day
+    #[default]
+    Text,
+    /// CSV with label and date columns
+    Csv,
+    /// Array of the days as in the config, with the number of days
+    Json,
+}
+
+/// Day in the `list --format json` output
+#[derive(Serialize)]
+struct ListedDay<'a> {
+    #[serde(flatten)]
+    day: &'a Day,
+    days: usize,
+}
+
+#[derive(Args)]
+struct AddArgs {
+    /// Label of the day, asked for with --interactive if not given
+    #[arg(required_unless_present = "interactive")]
+    label: Option<String>,
+    /// Asks for the label, date, description and tags of the day, then
+    /// for confirmation
+    #[arg(short, long, conflicts_with_all = ["today", "at", "days_ago"])]
+    interactive: bool,
+    /// The day is today
+    #[arg(long, conflicts_with_all = ["at", "days_ago"])]
+    today: bool,
+    /// Date of the day, as YYYY-MM-DD or RFC3339
+    #[arg(long, value_name = "DATE", value_parser = parse_date_arg, conflicts_with = "days_ago")]
+    at: Option<OffsetDateTime>,
+    /// The day was N days ago
+    #[arg(long, value_name = "N")]
+    days_ago: Option<u32>,
+    /// Update the date if the config already has a day with the label
+    #[arg(long)]
+    force: bool,
+}
+
+#[derive(Args)]
+struct UpcomingArgs {
+    /// Window in days to look for milestones in
+    #[arg(long, value_name = "N", default_value_t = 30)]
+    days: usize,
+    /// Width of the progress bars
+    #[arg(long, value_name = "W", default_value_t = 10)]
+    bar_width: usize,
+    /// Characters of the filled and empty parts of the progress bars,
+    /// e.g. "=-" (default "█░")
+    #[arg(long, value_name = "CHARS", value_parser = parse_bar_chars)]
+    bar_chars: Option<[char; 2]>,
+    /// ASCII progress bars, "#" and "." unless --bar-chars is given
+    #[arg(long)]
+    ascii: bool,
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    format: UpcomingFormat,
+}
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum UpcomingFormat {
+    /// Label, milestone and days left
+    #[default]
+    Text,
+    /// Array of JSON objects, one per milestone
+    Json,
+}
+
+#[derive(Args)]
+struct NextArgs {
+    /// Show at most N days, 0 means no limit
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    limit: usize,
+    /// Compute the milestones at this date (YYYY-MM-DD or RFC3339) instead
+    /// of now
+    #[arg(long, value_name = "DATE", value_parser = parse_date_arg)]
+    now: Option<OffsetDateTime>,
+}
+
+#[derive(Args)]
+struct StatsArgs {
+    #[command(flatten)]
+    filter: FilterArgs,
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    format: StatsFormat,
+}
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum StatsFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// JSON object
+    Json,
+}
+
+#[derive(Args)]
+struct RandomArgs {
+    /// Seed of the random pick, the same seed picks the same day (for
+    /// the same days), random by default
+    #[arg(long, value_name = "N")]
+    seed: Option<u64>,
+    /// Pick the days close to their next milestone more often
+    #[arg(long)]
+    weighted: bool,
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+    /// Render the day with a template, see `achievements --template`
+    #[arg(long, conflicts_with = "format")]
+    template: Option<String>,
+    /// ASCII-only text output, see `achievements --plain`
+    #[arg(long)]
+    plain: bool,
+}
+
+#[derive(Args)]
+struct CompareArgs {
+    /// Label of a day
+    label_a: String,
+    /// Label of the other day
+    label_b: String,
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    format: StatsFormat,
+}
+
+#[derive(Args)]
+struct PromptArgs {
+    /// Labels of the days to show, in order (default is the first days in
+    /// the config, except the archived ones)
+    #[arg(long, value_name = "LABELS", value_delimiter = ',')]
+    entries: Vec<String>,
+    /// Show at most N days
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    max: usize,
+    /// Don't print the trailing newline
+    #[arg(short)]
+    n: bool,
+}
+
+#[derive(Args)]
+struct SvgArgs {
+    /// Label of the day, shown on the left of the badge
+    label: String,
+    /// File to write the badge to, overwritten if it exists (default is
+    /// stdout)
+    #[arg(long, short, value_name = "FILE")]
+    output: Option<PathBuf>,
+    /// Right-hand side of the badge
+    #[arg(long, value_enum, default_value_t)]
+    style: SvgStyle,
+}
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum SvgStyle {
+    /// Number of days, e.g. "412"
+    #[default]
+    Days,
+    /// Interval in words, e.g. "1 year"
+    Words,
+}
+
+#[derive(Args)]
+struct WebhookArgs {
+    /// URL to POST the JSON payload to
+    url: String,
+    /// Send all the achievements, with the milestones reached today
+    #[arg(long)]
+    all: bool,
+    /// Seconds to wait for the response before giving up
+    #[arg(long, value_name = "SECS", default_value_t = 10)]
+    timeout: u64,
+    /// Extra header of the request, e.g. "Authorization: Bearer TOKEN",
+    /// can be repeated
+    #[arg(long, value_name = "NAME: VALUE", value_parser = parse_header)]
+    header: Vec<(String, String)>,
+}
+
+#[derive(Args)]
+struct SearchArgs {
+    /// Text to search, case-insensitive
+    query: String,
+    /// Fields to search, comma separated
+    #[arg(
+        long = "in",
+        value_enum,
+        value_delimiter = ',',
+        default_value = "label"
+    )]
+    fields: Vec<SearchField>,
+    /// The query is a regular expression, e.g. "^moon"
+    #[arg(long)]
+    regex: bool,
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    format: SearchFormat,
+}
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum SearchFormat {
+    /// Same as the achievements text output
+    #[default]
+    Text,
+    /// Array of JSON objects, one per achievement
+    Json,
+}
+
+#[derive(Args)]
+struct ImportArgs {
+    /// File to import the days from
+    file: PathBuf,
+    /// Format of the file
+    #[arg(long, value_enum, default_value_t)]
+    format: ImportFormat,
+    /// What to do with days already in the config
+    #[arg(long, value_enum, default_value_t)]
+    on_conflict: OnConflict,
+    /// Only the days with labels containing TEXT, ignoring case
+    #[arg(long, value_name = "TEXT")]
+    filter: Option<String>,
+}
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum ImportFormat {
+    /// JSON with the same schema as the config
+    #[default]
+    Json,
+    /// TOML with the same schema as the config
+    Toml,
+    /// CSV with label and date (and optionally description and tags)
+    /// columns
+    Csv,
+    /// iCalendar, a day for each event with its summary and start
+    Ics,
 }
 
+#[derive(Args)]
+struct ExportArgs {
+    /// File to export the config to, overwritten if it exists (default is
+    /// stdout)
+    #[arg(long, short, value_name = "FILE", conflicts_with = "path")]
+    output: Option<PathBuf>,
+    /// Same as `--output`
+    #[arg(hide = true)]
+    path: Option<PathBuf>,
+    /// Format of the export
+    #[arg(long, value_enum, default_value_t)]
+    format: ExportFormat,
+    /// Years of anniversaries to export with `--format ics`
+    #[arg(long, value_name = "N", default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..=100))]
+    years: u16,
+    #[command(flatten)]
+    filter: FilterArgs,
+}
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum ExportFormat {
+    /// JSON, same as the config file
+    #[default]
+    Json,
+    /// TOML with the same schema as the config
+    Toml,
+    /// CSV with label, date, description and tags columns
+    Csv,
+    /// iCalendar with an all-day event for each upcoming anniversary
+    Ics,
+    /// Self-contained HTML page with a table of the days, see
+    /// `achievements --format html`
+    Html,
+}
+
+#[derive(Args, Default)]
+struct FilterArgs {
+    /// Only the days with this tag, can be repeated for the days with any
+    /// of the tags
+    #[arg(long)]
+    tag: Vec<String>,
+    /// Only the days with at least N days elapsed
+    #[arg(long, value_name = "N")]
+    min_days: Option<usize>,
+    /// Only the days with at most N days elapsed
+    #[arg(long, value_name = "N")]
+    max_days: Option<usize>,
+    /// Only the days before DATE (YYYY-MM-DD or RFC3339)
+    #[arg(long, value_name = "DATE", value_parser = parse_date_arg)]
+    before: Option<OffsetDateTime>,
+    /// Only the days on or after DATE (YYYY-MM-DD or RFC3339)
+    #[arg(long, value_name = "DATE", value_parser = parse_date_arg)]
+    after: Option<OffsetDateTime>,
+    /// Hide the day with this label (ignoring case), can be repeated to
+    /// hide several days
+    #[arg(long, value_name = "LABEL")]
+    exclude: Vec<String>,
+}
+
+impl FilterArgs {
+    fn filter(&self) -> Filter {
+        Filter {
+            tags: self.tag.clone(),
+            min_days: self.min_days,
+            max_days: self.max_days,
+            before: self.before,
+            after: self.after,
+            exclude: self.exclude.clone(),
+        }
+    }
+}
+
+#[derive(Args, Default)]
+struct SortArgs {
+    /// Sort the days by the given key (default is config order)
+    #[arg(long, value_enum)]
+    sort: Option<SortKey>,
+    /// Reverse the sort order
+    #[arg(long, requires = "sort")]
+    reverse: bool,
+}
+
+/// Config file and environment sections of `--help`, also rendered in the
+/// man page
+///
+/// Sections are separated by empty lines, the first line of each is its
+/// title.
+const FILES_HELP: &str = "\
+Config file:
+  The days are stored as JSON in the first of:
+    --config PATH
+    $ACHIEVEMENTS_CONFIG
+    $XDG_CONFIG_HOME/achievements/config.json
+    ~/.config/achievements/config.json
+
+  For example:
+    {
+      \"days\": [
+        {\"label\": \"Moon landing\", \"date\": \"1969-07-20T20:17:40Z\"}
+      ]
+    }
+
+  Days can also have an \"end\" date, a \"description\", \"tags\", an
+  \"icon\" (shown by `achievements prompt`) and be \"archived\". A
+  \"template\" sets the default achievements template and \"badge_style\"
+  (\"emoji\", \"ascii\" or \"none\") the style of the badges.
+
+Environment:
+  ACHIEVEMENTS_CONFIG  Path of the config file
+  XDG_CONFIG_HOME      Base directory of the config file, default ~/.config
+  ACHIEVEMENTS_NOW     Current time to use instead of now, RFC3339
+  NO_COLOR             Don't color the output, unless --color always
+  LC_ALL, LC_MESSAGES, LANG
+                       Language of the interval words, from the first one
+                       set, unless --lang (English if not supported)";
+
 #[derive(Parser)]
-#[command(author, version, about, long_about = None)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    after_long_help = FILES_HELP
+)]
 struct Cli {
     #[command(subcommand)]
     command: Option<Command>,
+    /// Same as the `achievements` subcommand, used when there's none
+    #[command(flatten)]
+    achievements: AchievementsArgs,
+    /// Compute the achievements as of DATE instead of now, e.g. 2020-01-01
+    /// or "1 year ago", skipping the days after it
+    #[arg(long, global = true, value_name = "DATE", value_parser = parse_date_arg)]
+    on: Option<OffsetDateTime>,
+    /// Path of the config file, overrides $ACHIEVEMENTS_CONFIG
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+    /// Language of the interval words. Default is from $LC_ALL, then
+    /// $LC_MESSAGES, then $LANG (the first one set), English if it's not
+    /// supported
+    #[arg(long, global = true, value_enum)]
+    lang: Option<Language>,
 }
 
 fn main() {
-    let cli = Cli::parse();
-    match cli.command.unwrap_or(Command::Achievements) {
-        Command::Achievements => display_achievements(),
-        Command::List => list_days(),
-        Command::Add { label } => add_day(label),
-        Command::Remove { label } => remove_day(label),
+    if let Err(e) = current_time() {
+        fail(e);
+    }
+    let matches = Cli::command().get_matches();
+    if let Some(arg) = achievements_arg_with_subcommand(&matches) {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "--{arg} can't be used with a subcommand, \
+                     use 'achievements achievements --{arg}'"
+                ),
+            )
+            .exit();
+    }
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    if let Some(path) = &cli.config {
+        config::set_path(path);
+    }
+    let language = cli
+        .lang
+        .unwrap_or_else(|| detect_locale(|name| env::var(name).ok()));
+    IntervalLocale::set_current(language.locale());
+    WORDS.get_or_init(|| language.words());
+    let Some(command) = cli.command else {
+        return default_achievements(cli.achievements, cli.on);
+    };
+    match command {
+        Command::Achievements(args) => display_achievements(args, cli.on),
+        Command::List(args) => list_days(args),
+        Command::Add(args) => add_day(args),
+        Command::Remove(args) => remove_days(args),
+        Command::Show { label, date_format } => show(label, date_format, cli.on),
+        Command::Duplicate { label, new_label } => duplicate_day(label, new_label),
+        Command::Rename { label, new_label } => rename_day(label, new_label),
+        Command::Compare(args) => compare(args),
+        Command::Summary => summary(),
+        Command::Random(args) => random(args, cli.on),
+        Command::Init { force, empty } => init(force, empty),
+        Command::Upcoming(args) => upcoming(args, cli.on),
+        Command::Next(args) => next(args, cli.on),
+        Command::TopUpcoming { count } => top_upcoming(count, cli.on),
+        Command::Stats(args) => stats(args, cli.on),
+        Command::Search(args) => search(args),
+        Command::Prompt(args) => prompt(args, cli.on),
+        Command::Notify { dry_run } => notify(dry_run, cli.on),
+        Command::Bar { entry } => bar(entry, cli.on),
+        Command::Webhook(args) => webhook(args, cli.on),
+        Command::Svg(args) => svg(args, cli.on),
+        Command::Tui => tui(cli.on),
+        Command::Chart { sort, width, plain } => chart(sort, width, plain, cli.on),
+        Command::Timeline { width } => timeline(width, cli.on),
+        Command::Calendar { year, plain, color } => calendar(year, plain, color, cli.on),
+        Command::Watch { interval } => watch(StdDuration::from_secs(interval), cli.on),
+        Command::Import(args) => import(args),
+        Command::Export(args) => export(args),
+        Command::Doctor { fix } => doctor(fix),
+        Command::Completions { shell } => completions(shell),
+        Command::Man { output_dir } => man(output_dir),
+        Command::Labels => labels(),
+        Command::Config(ConfigCommand::Path) => config_path(),
+        Command::Config(ConfigCommand::Show) => config_show(),
+        Command::Config(ConfigCommand::Validate { strict, min_date }) => {
+            config_validate(strict, min_date)
+        }
+        Command::Config(ConfigCommand::Import { path, overwrite }) => {
+            config_import(path, overwrite)
+        }
+        Command::Config(ConfigCommand::Reset { from_backup, yes }) => {
+            config_reset(from_backup, yes)
+        }
+    }
+}
+
+/// Options of the text output of the achievements view, from the args and
+/// the config
+fn text_options(args: &AchievementsArgs, config: &Config) -> TextOptions {
+    let mut options = if args.plain {
+        TextOptions::plain()
+    } else {
+        TextOptions {
+            badges: config.badge_style.unwrap_or_default(),
+            ..TextOptions::default()
+        }
+    };
+    if args.ascii {
+        options.badges = BadgeStyle::Ascii;
+    }
+    if args.no_badges {
+        options.badges = BadgeStyle::None;
+    }
+    // compact JSON is for other tools, without the special messages
+    if args.no_messages || args.compact {
+        options.messages = false;
+    }
+    options.align = !args.no_align;
+    if !args.plain {
+        options.color = ColorChoice::Auto;
+    }
+    if let Some(color) = args.color {
+        options.color = color;
     }
+    options.short = args.short;
+    if args.progress {
+        let bar = if args.plain {
+            ProgressBar::ascii()
+        } else {
+            ProgressBar::default()
+        };
+        options.progress = Some(ProgressBar {
+            width: args.progress_width,
+            ..bar
+        });
+    }
+    // `None` when stdout is not a terminal
+    options.terminal_width = terminal_size::terminal_size().map(|(width, _)| width.0);
+    options
+}
+
+fn display_achievements(args: AchievementsArgs, on: Option<OffsetDateTime>) {
+    let (config, now) = read_config_on(on);
+
+    let mut selection = if args.newest {
+        Selection::newest(args.limit)
+    } else if args.oldest {
+        Selection::oldest(args.limit)
+    } else {
+        Selection {
+            sort: args.sort.sort,
+            reverse: args.sort.reverse,
+            limit: args.limit,
+            ..Default::default()
+        }
+    };
+    selection.filter = args.filter.filter();
+    let mut achievements = selection.achievements(&config, now);
+    if args.only_milestones {
+        achievements.retain(|achievement| achievement.milestone_today().is_some());
+        if achievements.is_empty() {
+            process::exit(if args.exit_code { 1 } else { 0 });
+        }
+    }
+    set_interval_defaults(&text_options(&args, &config));
+    if args.today_milestones {
+        print!("{}", render::milestones_reached(&achievements));
+        return;
+    }
+    print_achievements(&achievements, args, &config, now);
+}
+
+/// Prints the achievements in the format (or with the template) of the
+/// args
+fn print_achievements(
+    achievements: &[Achievement],
+    args: AchievementsArgs,
+    config: &Config,
+    now: OffsetDateTime,
+) {
+    let options = text_options(&args, config);
+    match args.format {
+        Format::Text => match args.template.or(config.template.clone()) {
+            Some(template) => {
+                let template: Template = template
+                    .parse()
+                    .unwrap_or_else(|e| fail(format!("Invalid template: {e}")));
+                for achievement in achievements.iter() {
+                    println!("{}", template.render(achievement));
+                }
+            }
+            None => print!("{}", render::text(achievements, &options)),
+        },
+        Format::Json => {
+            let json = if args.compact {
+                serde_json::to_string(achievements)
+            } else {
+                serde_json::to_string_pretty(achievements)
+            };
+            println!("{}", json.expect("Failed to serialize achievements"));
+        }
+        Format::Csv => {
+            let header = (!args.no_header).then_some(["label", "date", "days", "interval"]);
+            let rows = achievements.iter().map(|Achievement { day, interval }| {
+                [
+                    day.label.clone(),
+                    format_date(day.date),
+                    interval.as_days().to_string(),
+                    options.words(interval),
+                ]
+            });
+            write_csv(header, rows).expect("Failed to write CSV");
+        }
+        Format::Markdown => print!("{}", render::markdown_table(achievements)),
+        Format::MarkdownList => print!("{}", render::markdown_list(achievements)),
+        Format::Html => print!("{}", render::html(achievements, now)),
+    }
+}
+
+fn random(args: RandomArgs, on: Option<OffsetDateTime>) {
+    let (config, now) = read_config_on(on);
+    let achievements: Vec<Achievement> = config
+        .days
+        .iter()
+        .filter(|day| !day.archived)
+        .map(|day| Achievement::new(day.clone(), now))
+        .collect();
+    let mut rng = match args.seed {
+        Some(seed) => fastrand::Rng::with_seed(seed),
+        None => fastrand::Rng::new(),
+    };
+    let Some(picked) = pick_random(&achievements, &mut rng, args.weighted) else {
+        return;
+    };
+
+    let args = AchievementsArgs {
+        format: args.format,
+        template: args.template,
+        plain: args.plain,
+        ..Default::default()
+    };
+    set_interval_defaults(&text_options(&args, &config));
+    print_achievements(std::slice::from_ref(picked), args, &config, now);
+}
+
+/// Displays the intervals with the badges, messages and words of the
+/// options by default
+fn set_interval_defaults(options: &TextOptions) {
+    IntervalOptions::set_default(IntervalOptions {
+        badge_style: options.badges,
+        messages: options.messages,
+        short: options.short,
+        ..Default::default()
+    });
 }
 
-fn display_achievements() {
+/// Displays the achievements, with a hint to get started when there are
+/// no days
+fn default_achievements(args: AchievementsArgs, on: Option<OffsetDateTime>) {
     let config = config::read().expect("Failed to read config");
+    // nothing at all for the daily notifications
+    if config.days.is_empty() && !args.today_milestones && !args.only_milestones {
+        println!("{}", words().no_achievements);
+        return;
+    }
+
+    display_achievements(args, on);
+}
 
-    for Day { label, date: day } in config.days.iter() {
-        let days = days_since(*day);
-        let achievement = Interval::from_days(days);
-        println!("{}: {}", label, achievement);
+/// Name of the top-level achievements arg given along with a subcommand,
+/// if any, e.g. `achievements --sort label list`
+///
+/// Global args like `--on` are fine with any subcommand.
+fn achievements_arg_with_subcommand(matches: &ArgMatches) -> Option<String> {
+    matches.subcommand_name()?;
+    Cli::command()
+        .get_arguments()
+        .filter(|arg| !arg.is_global_set())
+        .find(|arg| matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine))
+        .map(|arg| arg.get_long().unwrap_or(arg.get_id().as_str()).to_string())
+}
+
+fn list_days(args: ListArgs) {
+    let mut config = config::read().expect("Failed to read config");
+    let now = now();
+    args.filter.filter().retain(&mut config.days, now);
+    sort_config(&mut config, &args.sort, now);
+
+    match args.format {
+        ListFormat::Text if args.verbose => {
+            let achievements: Vec<Achievement> = config
+                .days
+                .into_iter()
+                .map(|day| Achievement::new(day, now))
+                .collect();
+            let style = args.date_format.unwrap_or_default();
+            print!("{}", render::list_verbose(&achievements, words(), style));
+        }
+        ListFormat::Text if args.with_elapsed => {
+            let achievements: Vec<Achievement> = config
+                .days
+                .into_iter()
+                .map(|day| Achievement::new(day, now))
+                .collect();
+            let options = TextOptions {
+                align: true,
+                ..TextOptions::default()
+            };
+            print!("{}", render::list_with_elapsed(&achievements, &options));
+        }
+        ListFormat::Text => {
+            for day in config.days.iter() {
+                match args.date_format {
+                    Some(style) => println!(
+                        "'{}': {}",
+                        day.label,
+                        locale::format_date(day.date.date(), words(), style)
+                    ),
+                    None => println!("'{}': {}", day.label, day.date),
+                }
+            }
+        }
+        ListFormat::Csv => {
+            let header = (!args.no_header).then_some(["label", "date"]);
+            let rows = config
+                .days
+                .iter()
+                .map(|day| [day.label.clone(), format_date(day.date)]);
+            write_csv(header, rows).expect("Failed to write CSV");
+        }
+        ListFormat::Json => {
+            let days: Vec<ListedDay> = config
+                .days
+                .iter()
+                .map(|day| ListedDay {
+                    day,
+                    days: days_since_at(day.date, now),
+                })
+                .collect();
+            let json = serde_json::to_string_pretty(&days);
+            println!("{}", json.expect("Failed to serialize days"));
+        }
+    }
+}
+
+/// Writes the (optional) header and rows as CSV to stdout
+fn write_csv<const N: usize>(
+    header: Option<[&str; N]>,
+    rows: impl Iterator<Item = [String; N]>,
+) -> csv::Result<()> {
+    let mut writer = csv::Writer::from_writer(io::stdout());
+    if let Some(header) = header {
+        writer.write_record(header)?;
+    }
+    for row in rows {
+        writer.write_record(row)?;
     }
+    writer.flush()?;
+
+    Ok(())
 }
 
-fn list_days() {
+/// Formats a date as RFC3339, e.g. "1969-07-20T20:17:40Z"
+fn format_date(date: OffsetDateTime) -> String {
+    date.format(&Rfc3339).expect("Failed to format date")
+}
+
+fn sort_config(config: &mut Config, sort: &SortArgs, now: OffsetDateTime) {
+    if let Some(key) = sort.sort {
+        config.sort_days(key, sort.reverse, now);
+    }
+}
+
+fn add_day(args: AddArgs) {
+    if args.interactive {
+        return add_day_interactive(args.label, args.force);
+    }
+    let label = args.label.expect("Label is required");
+    let now = now();
+    let date = if args.today {
+        now
+    } else if let Some(date) = args.at {
+        date
+    } else if let Some(days) = args.days_ago {
+        now - Duration::days(days.into())
+    } else {
+        prompt_date("Enter date (YYYY-MM-DD or press Enter for today):", now)
+    };
+
+    let mut config = config::read().expect("Failed to read config");
+    if args.force {
+        config.set_day(&label, date);
+    } else if !config.set_day_if_absent(&label, date) {
+        fail(already_exists(&label));
+    }
+    config::write(&config).expect("Failed to write config");
+
+    println!(
+        "Day with label '{}' set to {}",
+        label.trim(),
+        render::format_day(date)
+    );
+}
+
+/// Adds a day asking for its label (unless given), date, description and
+/// tags, then for confirmation before writing the config
+fn add_day_interactive(label: Option<String>, force: bool) {
+    let now = now();
+    let mut config = config::read().expect("Failed to read config");
+
+    let label = match label {
+        Some(label) => label.trim().to_string(),
+        None => loop {
+            let Some(label) = prompt_line("Label:") else {
+                fail("No label given");
+            };
+            if !label.is_empty() {
+                break label;
+            }
+        },
+    };
+    if !force && config.contains(&label) {
+        fail(already_exists(&label));
+    }
+    let date = prompt_date("Date (YYYY-MM-DD or 'today'):", now);
+    let description = prompt_line("Description (optional):").filter(|line| !line.is_empty());
+    let tags: Vec<String> = prompt_line("Tags (comma-separated, optional):")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(String::from)
+        .collect();
+
+    let question = format!("Add '{label}' on {}? {{yes_no}}:", render::format_day(date));
+    if !ask(&question, &mut io::stdin().lock(), &mut io::stdout()).expect("Failed to read answer") {
+        fail(words().aborted);
+    }
+
+    let day = config.set_day(&label, date);
+    if description.is_some() {
+        day.description = description;
+    }
+    if !tags.is_empty() {
+        day.tags = tags;
+    }
+    config::write(&config).expect("Failed to write config");
+
+    println!(
+        "Day with label '{label}' set to {}",
+        render::format_day(date)
+    );
+}
+
+/// Error when adding a day whose label is already in the config
+fn already_exists(label: &str) -> String {
+    format!(
+        "Day with label '{}' already exists, use --force to update its date",
+        label.trim()
+    )
+}
+
+/// Asks for a line of input, trimmed, `None` at the end of the input
+fn prompt_line(prompt: &str) -> Option<String> {
+    print!("{prompt} ");
+    io::stdout().flush().expect("Failed to flush stdout");
+
+    let mut input = String::new();
+    let read = io::stdin()
+        .read_line(&mut input)
+        .expect("Failed to read answer");
+    if read == 0 {
+        println!();
+        return None;
+    }
+    Some(input.trim().to_string())
+}
+
+/// Asks for the date of the day, `now` if the user enters an empty line
+///
+/// Asks again up to `DATE_ATTEMPTS` times if the date is invalid.
+fn prompt_date(prompt: &str, now: OffsetDateTime) -> OffsetDateTime {
+    for _ in 0..DATE_ATTEMPTS {
+        let Some(input) = prompt_line(prompt) else {
+            fail("No date given");
+        };
+
+        if input.is_empty() {
+            return now;
+        }
+        match parse_user_date(&input, now) {
+            Ok(date) => return date,
+            Err(e) => eprintln!("Invalid date '{input}': {e}"),
+        }
+    }
+
+    fail(format!("No valid date after {DATE_ATTEMPTS} attempts"));
+}
+
+/// Parses a `--header` as name and value, e.g. "Authorization: Bearer x"
+fn parse_header(input: &str) -> Result<(String, String), String> {
+    match input.split_once(':') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err("expected NAME: VALUE, e.g. \"Authorization: Bearer TOKEN\"".to_string()),
+    }
+}
+
+/// Parses the `--bar-chars` pair of characters
+fn parse_bar_chars(input: &str) -> Result<[char; 2], String> {
+    match input.chars().collect::<Vec<_>>()[..] {
+        [filled, empty] => Ok([filled, empty]),
+        _ => Err("expected 2 characters, filled and empty".to_string()),
+    }
+}
+
+/// Parses a date given by the user, absolute or relative to `now`
+///
+/// See `achievements::parse_date()` and `achievements::parse_relative_date()`.
+fn parse_user_date(input: &str, now: OffsetDateTime) -> Result<OffsetDateTime, String> {
+    parse_date(input).or_else(|e| {
+        parse_relative_date(input, now)
+            .ok_or_else(|| format!("{e}, expected YYYY-MM-DD, RFC3339 or e.g. \"1 year ago\""))
+    })
+}
+
+/// Parses the `--at` and `--on` dates, see `parse_user_date()`
+fn parse_date_arg(input: &str) -> Result<OffsetDateTime, String> {
+    parse_user_date(input, now())
+}
+
+/// Reads the config as of `on`, or now if not given
+///
+/// The days after `on` are skipped with a note, as they haven't happened
+/// yet at that date.
+fn read_config_on(on: Option<OffsetDateTime>) -> (Config, OffsetDateTime) {
+    let mut config = config::read().expect("Failed to read config");
+    let Some(on) = on else {
+        return (config, now());
+    };
+
+    config.days.retain(|day| {
+        if day.date <= on {
+            return true;
+        }
+        eprintln!(
+            "Skipped '{}', it's after {}",
+            day.label,
+            render::format_day(on)
+        );
+        false
+    });
+    (config, on)
+}
+
+fn remove_days(args: RemoveArgs) {
+    let mut config = config::read().expect("Failed to read config");
+
+    let mut days: Vec<&Day> = Vec::new();
+    let mut not_found = Vec::new();
+    if args.labels.is_empty() && !args.all {
+        days.push(pick_day_from_stdin(&config, "Day to remove"));
+    }
+    if let Some(pattern) = &args.matching {
+        days = config
+            .find_days_matching(pattern)
+            .unwrap_or_else(|e| fail(format!("Invalid --matching: {e}")));
+        if days.is_empty() {
+            not_found.push(format!("No day matches '{}'", pattern.trim()));
+        }
+    }
+    for label in args.labels.iter() {
+        let matched = if args.glob {
+            config.find_days_glob(label)
+        } else {
+            config.find_day(label).into_iter().collect()
+        };
+        if matched.is_empty() {
+            not_found.push(not_found_message(&config, label, args.glob));
+        }
+        for day in matched {
+            if !days.contains(&day) {
+                days.push(day);
+            }
+        }
+    }
+
+    for message in not_found.iter() {
+        eprintln!("Error: {message}");
+    }
+    if days.is_empty() || (args.strict && !not_found.is_empty()) {
+        process::exit(1);
+    }
+
+    if args.dry_run {
+        for day in days.iter() {
+            println!(
+                "Would remove '{}' ({})",
+                day.label,
+                render::format_day(day.date)
+            );
+        }
+        return;
+    }
+
+    if !args.yes {
+        if !io::stdin().is_terminal() {
+            fail("Not asking for confirmation as stdin is not a terminal, use --yes to remove");
+        }
+        let question = match days[..] {
+            [day] => Words::fill(
+                words().remove_day,
+                &[
+                    ("label", &day.label),
+                    ("date", &render::format_day(day.date)),
+                ],
+            ),
+            _ => {
+                for day in days.iter() {
+                    println!("'{}' ({})", day.label, render::format_day(day.date));
+                }
+                Words::fill(words().remove_days, &[("count", &days.len().to_string())])
+            }
+        };
+        let remove = ask(&question, &mut io::stdin().lock(), &mut io::stdout())
+            .expect("Failed to read answer");
+        if !remove {
+            fail(words().aborted);
+        }
+    }
+
+    let labels: Vec<String> = days.iter().map(|day| day.label.clone()).collect();
+    match &args.matching {
+        Some(pattern) => {
+            config
+                .remove_all_matching(pattern)
+                .expect("Pattern was already matched");
+        }
+        None => {
+            for label in labels.iter() {
+                config.remove_day(label);
+            }
+        }
+    }
+    config::write(&config).expect("Failed to write config");
+
+    for label in labels.iter() {
+        println!("Day with label '{label}' removed from config");
+    }
+    if !not_found.is_empty() {
+        process::exit(1);
+    }
+}
+
+/// Error message for a label not in the config, with up to three similar
+/// labels (not for glob patterns)
+fn not_found_message(config: &Config, label: &str, glob: bool) -> String {
+    if glob {
+        return format!("No day matches '{}'", label.trim());
+    }
+
+    let suggestions = config.closest_labels(label, 3);
+    if suggestions.is_empty() {
+        return format!("No day with label '{}'", label.trim());
+    }
+    let suggestions: Vec<String> = suggestions.iter().map(|l| format!("'{l}'")).collect();
+    format!(
+        "No day with label '{}', did you mean: {}?",
+        label.trim(),
+        suggestions.join(", ")
+    )
+}
+
+fn duplicate_day(label: String, new_label: String) {
+    let mut config = config::read().expect("Failed to read config");
+    if !config.contains(&label) {
+        fail(not_found_message(&config, &label, false));
+    }
+    let copy = config
+        .duplicate_day(&label, &new_label)
+        .unwrap_or_else(|e| fail(e));
+    println!(
+        "Day with label '{}' added, same date as '{}': {}",
+        copy.label,
+        label.trim(),
+        render::format_day(copy.date)
+    );
+    config::write(&config).expect("Failed to write config");
+}
+
+fn rename_day(label: String, new_label: String) {
+    let mut config = config::read().expect("Failed to read config");
+    match config.update_label(&label, &new_label) {
+        Ok(true) => println!(
+            "Day with label '{}' renamed to '{}'",
+            label.trim(),
+            new_label.trim()
+        ),
+        Ok(false) => fail(not_found_message(&config, &label, false)),
+        Err(e) => fail(e),
+    }
+    config::write(&config).expect("Failed to write config");
+}
+
+fn compare(args: CompareArgs) {
     let config = config::read().expect("Failed to read config");
-    for day in config.days.iter() {
-        println!("'{}': {}", day.label, day.date);
+    let find = |label: &str| -> Day {
+        match config.find_day(label) {
+            Some(day) => day.clone(),
+            None => fail(not_found_message(&config, label, false)),
+        }
+    };
+
+    let comparison = Comparison::new(find(&args.label_a), find(&args.label_b), now());
+    match args.format {
+        StatsFormat::Text => print!("{}", render::comparison(&comparison)),
+        StatsFormat::Json => {
+            let json = serde_json::to_string_pretty(&comparison);
+            println!("{}", json.expect("Failed to serialize comparison"));
+        }
     }
 }
 
-fn add_day(_label: String) {
-    let _config = config::read().expect("Failed to read config");
-    // TODO: Parse datetime somehow
-    // TODO: Call config.set_date(label, date)
+fn summary() {
+    let config = config::read().expect("Failed to read config");
+    let now = now();
+
+    let (Some(earliest), Some(latest)) = (config.earliest(), config.latest()) else {
+        println!("{}", words().no_achievements);
+        return;
+    };
+
+    let count = config.days.len();
+    let total_days: usize = config
+        .days
+        .iter()
+        .map(|day| days_since_at(day.date, now))
+        .sum();
+    let tracked = if count == 1 {
+        "1 achievement tracked".to_string()
+    } else {
+        format!("{count} achievements tracked")
+    };
 
     println!(
-        "TODO: Not implemented at this time, edit ~/.config/achievements/config.json manually"
+        "{tracked} · oldest: {} ({} days) · newest: {} ({} days) · average: {} days",
+        earliest.label,
+        days_since_at(earliest.date, now),
+        latest.label,
+        days_since_at(latest.date, now),
+        total_days / count,
     );
 }
 
-fn remove_day(label: String) {
+fn init(force: bool, empty: bool) {
+    let config_path = config::resolve_path();
+    if config_path.exists() {
+        if !force {
+            fail(format!(
+                "Config already exists at {}, use --force to overwrite it",
+                config_path.display()
+            ));
+        }
+        config::backup().expect("Failed to backup config");
+    }
+
+    let mut config = Config::default();
+    if !empty {
+        config.set_day_if_absent("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+        config.set_day_if_absent("Installed achievements", now());
+    }
+    config::write(&config).expect("Failed to write config");
+
+    println!("Config created at {}", config_path.display());
+    if !empty {
+        println!();
+        display_achievements(AchievementsArgs::default(), None);
+    }
+}
+
+fn upcoming(args: UpcomingArgs, on: Option<OffsetDateTime>) {
+    let (config, now) = read_config_on(on);
+    let upcoming = config.upcoming(now, args.days);
+
+    match args.format {
+        UpcomingFormat::Text if upcoming.is_empty() => {
+            let days = IntervalLocale::current().day(args.days);
+            println!(
+                "{}",
+                Words::fill(words().nothing_upcoming, &[("days", &days)])
+            );
+        }
+        UpcomingFormat::Text => {
+            let mut bar = if args.ascii {
+                ProgressBar::ascii()
+            } else {
+                ProgressBar::default()
+            };
+            bar.width = args.bar_width;
+            if let Some([filled, empty]) = args.bar_chars {
+                bar.filled = filled;
+                bar.empty = empty;
+            }
+            print!("{}", render::upcoming(&upcoming, words(), Some(&bar)));
+        }
+        UpcomingFormat::Json => {
+            let json = serde_json::to_string_pretty(&upcoming);
+            println!("{}", json.expect("Failed to serialize upcoming milestones"));
+        }
+    }
+}
+
+fn next(args: NextArgs, on: Option<OffsetDateTime>) {
+    let (config, now) = read_config_on(args.now.or(on));
+
+    let mut upcoming = config.next_milestones(now);
+    achievements::limit(&mut upcoming, args.limit);
+    print!("{}", render::upcoming(&upcoming, words(), None));
+
+    // closed periods have no next milestone, shown last
+    let completed = config
+        .days
+        .iter()
+        .filter(|day| !day.archived && day.is_closed());
+    let remaining = match args.limit {
+        0 => usize::MAX,
+        limit => limit - upcoming.len(),
+    };
+    for day in completed.take(remaining) {
+        println!("{}: {}", day.label, words().completed);
+    }
+}
+
+fn top_upcoming(count: usize, on: Option<OffsetDateTime>) {
+    let (config, now) = read_config_on(on);
+    if config.days.is_empty() {
+        println!("{}", words().no_achievements);
+        return;
+    }
+
+    let mut upcoming = config.next_milestones(now);
+    achievements::limit(&mut upcoming, count);
+    print!("{}", render::top_upcoming(&upcoming, words()));
+}
+
+fn stats(args: StatsArgs, on: Option<OffsetDateTime>) {
+    let (mut config, now) = read_config_on(on);
+    args.filter.filter().retain(&mut config.days, now);
+    let stats = config.stats(now);
+
+    match args.format {
+        StatsFormat::Text if stats.count == 0 => println!("{}", words().no_achievements),
+        StatsFormat::Text => print!("{}", render::stats(&stats, words())),
+        StatsFormat::Json => {
+            let json = serde_json::to_string_pretty(&stats);
+            println!("{}", json.expect("Failed to serialize stats"));
+        }
+    }
+}
+
+fn search(args: SearchArgs) {
+    let config = config::read().expect("Failed to read config");
+    let now = now();
+
+    let options = SearchOptions {
+        fields: args.fields,
+        regex: args.regex,
+    };
+    let days = config
+        .search(&args.query, &options)
+        .unwrap_or_else(|e| fail(format!("Invalid regular expression: {e}")));
+    if days.is_empty() {
+        process::exit(1);
+    }
+
+    let achievements: Vec<Achievement> = days
+        .into_iter()
+        .map(|day| Achievement::new(day.clone(), now))
+        .collect();
+    match args.format {
+        SearchFormat::Text => {
+            let options = TextOptions {
+                badges: config.badge_style.unwrap_or_default(),
+                ..TextOptions::default()
+            };
+            print!("{}", render::text(&achievements, &options));
+        }
+        SearchFormat::Json => {
+            let json = serde_json::to_string_pretty(&achievements);
+            println!("{}", json.expect("Failed to serialize achievements"));
+        }
+    }
+}
+
+fn import(args: ImportArgs) {
+    let file = fs::File::open(&args.file)
+        .unwrap_or_else(|e| fail(format!("Can't open {}: {e}", args.file.display())));
+    let days = match args.format {
+        ImportFormat::Json => io::read_to_string(file)
+            .map_err(ConfigError::from)
+            .and_then(|json| Config::from_json_str(&json))
+            .map(|config| config.days),
+        ImportFormat::Toml => io::read_to_string(file)
+            .map_err(ConfigError::from)
+            .and_then(|toml| Config::from_toml_str(&toml))
+            .map(|config| config.days),
+        ImportFormat::Csv => config::days_from_csv(file),
+        ImportFormat::Ics => io::read_to_string(file)
+            .map_err(ConfigError::from)
+            .map(|ics| {
+                let (days, skipped) = ics::days_from_ics(&ics);
+                for event in skipped {
+                    eprintln!("Warning: skipped event {}: {}", event.number, event.reason);
+                }
+                days
+            }),
+    };
+    let mut days = days.unwrap_or_else(|e| fail(e));
+    if let Some(filter) = &args.filter {
+        let filter = filter.to_lowercase();
+        days.retain(|day| day.label.to_lowercase().contains(&filter));
+    }
+
     let mut config = config::read().expect("Failed to read config");
-    config.remove_day(&label);
+    let summary = config
+        .merge(days, args.on_conflict)
+        .unwrap_or_else(|e| fail(format!("{e}, nothing imported")));
+    config::write(&config).expect("Failed to write config");
+
+    print_merge_summary(&summary);
+}
+
+/// Prints the days skipped by an import and how many were added, updated
+/// and skipped
+fn print_merge_summary(summary: &MergeSummary) {
+    for label in summary.skipped.iter() {
+        println!("Skipped '{label}': already in the config");
+    }
+    println!(
+        "{} added, {} updated, {} skipped",
+        summary.added.len(),
+        summary.updated.len(),
+        summary.skipped.len()
+    );
+}
+
+fn export(args: ExportArgs) {
+    let mut config = config::read().expect("Failed to read config");
+    let now = now();
+    args.filter.filter().retain(&mut config.days, now);
+
+    let exported = match args.format {
+        ExportFormat::Json => config.write_to_string().map(|json| json + "\n"),
+        ExportFormat::Toml => config.write_to_toml_string(),
+        ExportFormat::Csv => {
+            let mut csv = Vec::new();
+            config::days_to_csv(&config.days, &mut csv)
+                .map(|_| String::from_utf8(csv).expect("CSV is not UTF-8"))
+        }
+        ExportFormat::Ics => {
+            let events = ics::anniversaries(&config.days, now, args.years);
+            Ok(ics::calendar(&events, now))
+        }
+        ExportFormat::Html => {
+            let achievements: Vec<Achievement> = config
+                .days
+                .iter()
+                .map(|day| Achievement::new(day.clone(), now))
+                .collect();
+            Ok(render::html(&achievements, now))
+        }
+    };
+    let exported = exported.unwrap_or_else(|e| fail(format!("Can't export the config: {e}")));
+
+    match args.output.or(args.path) {
+        Some(path) => {
+            fs::write(&path, exported)
+                .unwrap_or_else(|e| fail(format!("Can't write {}: {e}", path.display())));
+            println!("Exported {} days to {}", config.days.len(), path.display());
+        }
+        None => print!("{exported}"),
+    }
+}
+
+fn doctor(fix: bool) {
+    let config_path = config::resolve_path();
+    if fix {
+        fix_config(&config_path);
+    }
+
+    let checks = doctor::run(&config_path, now());
+    print!("{}", render::doctor(&checks));
+    if !checks.iter().all(Check::is_ok) {
+        process::exit(1);
+    }
+}
+
+/// Prints the prompt line
+///
+/// Reads the config once without creating its directory, and prints
+/// nothing if it's missing or invalid so the prompt never breaks.
+fn prompt(args: PromptArgs, on: Option<OffsetDateTime>) {
+    let Ok(config) = Config::read_from_path(&config::resolve_path()) else {
+        return;
+    };
+    let now = on.unwrap_or_else(now);
+
+    let days: Vec<&Day> = if args.entries.is_empty() {
+        config.days.iter().filter(|day| !day.archived).collect()
+    } else {
+        args.entries
+            .iter()
+            .filter_map(|label| config.find_day(label))
+            .collect()
+    };
+    let achievements: Vec<Achievement> = days
+        .into_iter()
+        .filter(|day| day.date <= now)
+        .take(args.max)
+        .map(|day| Achievement::new(day.clone(), now))
+        .collect();
+    if achievements.is_empty() {
+        return;
+    }
+
+    print!("{}", render::prompt(&achievements));
+    if !args.n {
+        println!();
+    }
+}
+
+fn notify(dry_run: bool, on: Option<OffsetDateTime>) {
+    let (config, now) = read_config_on(on);
+
+    for day in &config.days {
+        let achievement = Achievement::new(day.clone(), now);
+        let Some(milestone) = achievement.milestone_today() else {
+            continue;
+        };
+        let summary = format!("🎉 {}", day.label);
+        let body = format!("{milestone} {}", words().today);
+        if dry_run {
+            println!("Would notify: {summary} — {body}");
+        } else {
+            send_notification(&summary, &body);
+        }
+    }
+}
+
+#[cfg(feature = "notifications")]
+fn send_notification(summary: &str, body: &str) {
+    let shown = notify_rust::Notification::new()
+        .appname("achievements")
+        .summary(summary)
+        .body(body)
+        .show();
+    if let Err(e) = shown {
+        fail(format!("Can't send the notification: {e}"));
+    }
+}
+
+#[cfg(not(feature = "notifications"))]
+fn send_notification(_summary: &str, _body: &str) {
+    fail(
+        "achievements was built without desktop notifications, reinstall it \
+         with --features notifications or use --dry-run",
+    );
+}
+
+fn webhook(args: WebhookArgs, on: Option<OffsetDateTime>) {
+    let (config, now) = read_config_on(on);
+    let payload = webhook::Payload::new(&config, now, args.all);
+    if payload.is_empty() {
+        return;
+    }
+
+    let body = serde_json::to_string(&payload).expect("Failed to serialize webhook payload");
+    let timeout = std::time::Duration::from_secs(args.timeout);
+    post_json(&args.url, &args.header, timeout, &body);
+}
+
+#[cfg(feature = "webhook")]
+fn post_json(url: &str, headers: &[(String, String)], timeout: std::time::Duration, body: &str) {
+    let mut request = ureq::post(url)
+        .timeout(timeout)
+        .set("Content-Type", "application/json");
+    for (name, value) in headers {
+        request = request.set(name, value);
+    }
+
+    match request.send_string(body) {
+        Ok(response) if (200..300).contains(&response.status()) => {}
+        Ok(response) | Err(ureq::Error::Status(_, response)) => fail(format!(
+            "Webhook responded {} {}",
+            response.status(),
+            response.status_text()
+        )),
+        Err(e) => fail(format!("Can't send the webhook: {e}")),
+    }
+}
+
+#[cfg(not(feature = "webhook"))]
+fn post_json(
+    _url: &str,
+    _headers: &[(String, String)],
+    _timeout: std::time::Duration,
+    _body: &str,
+) {
+    fail("achievements was built without webhooks, reinstall it with --features webhook");
+}
+
+fn show(label: Option<String>, date_format: DateStyle, on: Option<OffsetDateTime>) {
+    let (config, now) = read_config_on(on);
+    let day = match &label {
+        Some(label) => config
+            .find_day(label)
+            .unwrap_or_else(|| fail(not_found_message(&config, label, false))),
+        None => pick_day_from_stdin(&config, "Day to show"),
+    };
+
+    print!(
+        "{}",
+        render::list_verbose(&[Achievement::new(day.clone(), now)], words(), date_format)
+    );
+}
+
+fn svg(args: SvgArgs, on: Option<OffsetDateTime>) {
+    let (config, now) = read_config_on(on);
+    let Some(day) = config.find_day(&args.label) else {
+        fail(not_found_message(&config, &args.label, false));
+    };
+
+    let Achievement { day, interval } = Achievement::new(day.clone(), now);
+    let value = match args.style {
+        SvgStyle::Days => interval.as_days().to_string(),
+        SvgStyle::Words => interval.to_words_plain(),
+    };
+    let badge = render::svg::render_badge(&day.label, &value, render::svg::tier_color(&interval));
+
+    match args.output {
+        Some(path) => {
+            fs::write(&path, badge)
+                .unwrap_or_else(|e| fail(format!("Can't write {}: {e}", path.display())));
+            println!("Badge of '{}' written to {}", day.label, path.display());
+        }
+        None => print!("{badge}"),
+    }
+}
+
+fn chart(sort: SortArgs, width: Option<u16>, plain: bool, on: Option<OffsetDateTime>) {
+    let (config, now) = read_config_on(on);
+    if config.days.is_empty() {
+        println!("{}", words().no_achievements);
+        return;
+    }
+
+    let selection = Selection {
+        sort: sort.sort,
+        reverse: sort.reverse,
+        ..Default::default()
+    };
+    let width = width
+        .or_else(|| terminal_size::terminal_size().map(|(width, _)| width.0))
+        .unwrap_or(80);
+    let achievements = selection.achievements(&config, now);
+    for line in render::bars(&achievements, width.into(), plain) {
+        println!("{line}");
+    }
+}
+
+fn timeline(width: Option<u16>, on: Option<OffsetDateTime>) {
+    let (config, now) = read_config_on(on);
+    if config.days.is_empty() {
+        println!("{}", words().no_achievements);
+        return;
+    }
+
+    let width = width
+        .or_else(|| terminal_size::terminal_size().map(|(width, _)| width.0))
+        .unwrap_or(80);
+    for line in render::timeline::timeline(&config.days, now, width.into()) {
+        println!("{line}");
+    }
+}
+
+fn calendar(
+    year: Option<i32>,
+    plain: bool,
+    color: Option<ColorChoice>,
+    on: Option<OffsetDateTime>,
+) {
+    let (config, now) = read_config_on(on);
+    let days: Vec<Day> = config
+        .days
+        .into_iter()
+        .filter(|day| !day.archived)
+        .collect();
+    if days.is_empty() {
+        println!("{}", words().no_achievements);
+        return;
+    }
+
+    let color = match color {
+        Some(color) => color,
+        None if plain => ColorChoice::Never,
+        None => ColorChoice::Auto,
+    };
+    let year = year.unwrap_or(now.year());
+    for line in render::calendar::calendar(&days, year, plain, color.enabled()) {
+        println!("{line}");
+    }
+}
+
+fn watch(interval: StdDuration, on: Option<OffsetDateTime>) {
+    let stop = Arc::new(AtomicBool::new(false));
+    let stopping = Arc::clone(&stop);
+    ctrlc::set_handler(move || stopping.store(true, Ordering::Relaxed))
+        .unwrap_or_else(|e| fail(format!("Can't handle Ctrl-C: {e}")));
+    // Sleeps in short steps to stop soon after Ctrl-C
+    let sleep = |duration: StdDuration| {
+        let step = StdDuration::from_millis(100);
+        let start = Instant::now();
+        while !stop.load(Ordering::Relaxed) && start.elapsed() < duration {
+            thread::sleep(step.min(duration.saturating_sub(start.elapsed())));
+        }
+    };
+
+    print!("{}", watch::HIDE_CURSOR);
+    let mut previous: Vec<Achievement> = Vec::new();
+    let mut last_text: Option<String> = None;
+    while !stop.load(Ordering::Relaxed) {
+        let now = on.unwrap_or_else(now);
+        let (achievements, text) = match config::read() {
+            Ok(config) => {
+                let achievements = Selection::default().achievements(&config, now);
+                let options = text_options(&AchievementsArgs::default(), &config);
+                let text = render::text(&achievements, &options);
+                (achievements, text)
+            }
+            // e.g. while the config is being edited
+            Err(e) => (previous.clone(), format!("Error: {e}\n")),
+        };
+
+        let changed = watch::changed_lines(&previous, &achievements);
+        match watch::redraw(last_text.as_deref(), &text, &changed) {
+            Redraw::Nothing => {}
+            Redraw::Plain(plain) => print!("{}{plain}", watch::CLEAR),
+            Redraw::Highlighted { highlighted, plain } => {
+                print!("{}{highlighted}", watch::CLEAR);
+                io::stdout().flush().expect("Failed to flush stdout");
+                sleep(watch::HIGHLIGHT_FOR.min(interval));
+                print!("{}{plain}", watch::CLEAR);
+            }
+        }
+        io::stdout().flush().expect("Failed to flush stdout");
+
+        previous = achievements;
+        last_text = Some(text);
+        sleep(interval);
+    }
+    println!("{}", watch::SHOW_CURSOR);
+}
+
+fn tui(on: Option<OffsetDateTime>) {
+    let (config, now) = read_config_on(on);
+
+    let state = run_tui(TuiState::new(config, now));
+    if state.modified {
+        config::write(&state.config).expect("Failed to write config");
+        println!("Config saved to {}", config::resolve_path().display());
+    }
+}
+
+#[cfg(feature = "tui")]
+fn run_tui(state: TuiState) -> TuiState {
+    achievements::tui::view::run(state).unwrap_or_else(|e| fail(format!("Terminal error: {e}")))
+}
+
+#[cfg(not(feature = "tui"))]
+fn run_tui(_state: TuiState) -> TuiState {
+    fail("achievements was built without the TUI, reinstall it with --features tui");
+}
+
+fn bar(entry: Option<String>, on: Option<OffsetDateTime>) {
+    let (config, now) = read_config_on(on);
+    let achievements = Selection::default().achievements(&config, now);
+
+    let module = render::bar(&achievements, entry.as_deref());
+    let json = serde_json::to_string(&module);
+    println!("{}", json.expect("Failed to serialize bar module"));
+}
+
+/// Applies `Config::fix()` to the config, after backing it up
+///
+/// Does nothing if the config can't be read, the checks report why.
+fn fix_config(config_path: &std::path::Path) {
+    let Ok(mut config) = Config::read_from_path(config_path) else {
+        return;
+    };
+
+    let summary = config.fix();
+    if summary.is_empty() {
+        println!("Nothing to fix");
+        return;
+    }
+
+    config::backup().expect("Failed to backup config");
+    config
+        .write_to_path(config_path)
+        .expect("Failed to write config");
+
+    for label in summary.trimmed.iter() {
+        println!("Trimmed label '{label}'");
+    }
+    for label in summary.deduped.iter() {
+        println!("Removed duplicate of '{label}'");
+    }
+    println!("Backup saved to {}\n", config::backup_path().display());
+}
+
+fn completions(shell: Shell) {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, &name, &mut io::stdout());
+
+    // complete the labels of `remove` and `duplicate` with the ones in the config
+    if shell == Shell::Fish {
+        println!(
+            "complete -c {name} -n '__fish_seen_subcommand_from remove rm duplicate' -f -a '({name} __labels)'"
+        );
+    }
+}
+
+fn man(output_dir: Option<PathBuf>) {
+    let mut command = Cli::command().disable_help_subcommand(true);
+    command.build();
+
+    let Some(output_dir) = output_dir else {
+        render_man(&command, &mut io::stdout()).expect("Failed to write man page");
+        return;
+    };
+
+    let mut file = fs::File::create(output_dir.join("achievements.1"))
+        .unwrap_or_else(|e| fail(format!("Can't write man page: {e}")));
+    render_man(&command, &mut file).expect("Failed to write man page");
+    for subcommand in command.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        generate_man_pages(subcommand, &output_dir)
+            .unwrap_or_else(|e| fail(format!("Can't write man page: {e}")));
+    }
+}
+
+/// Renders the main man page, with `FILES_HELP` as sections
+fn render_man(command: &clap::Command, w: &mut dyn Write) -> io::Result<()> {
+    let man = clap_mangen::Man::new(command.clone());
+    man.render_title(w)?;
+    man.render_name_section(w)?;
+    man.render_synopsis_section(w)?;
+    man.render_description_section(w)?;
+    man.render_options_section(w)?;
+    man.render_subcommands_section(w)?;
+
+    // section titles are the lines that aren't indented
+    let mut lines = FILES_HELP.lines().peekable();
+    while let Some(title) = lines.next() {
+        writeln!(w, ".SH \"{}\"", title.trim_end_matches(':').to_uppercase())?;
+        writeln!(w, ".nf")?;
+        while let Some(line) = lines.next_if(|line| line.is_empty() || line.starts_with(' ')) {
+            if !(line.is_empty() && lines.peek().is_some_and(|next| !next.starts_with(' '))) {
+                writeln!(w, "{}", line.replace('\\', "\\e"))?;
+            }
+        }
+        writeln!(w, ".fi")?;
+    }
+
+    man.render_version_section(w)?;
+    man.render_authors_section(w)
+}
+
+/// Writes the man pages of the subcommand and of its subcommands
+fn generate_man_pages(command: &clap::Command, output_dir: &std::path::Path) -> io::Result<()> {
+    for subcommand in command.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        generate_man_pages(subcommand, output_dir)?;
+    }
+    clap_mangen::Man::new(command.clone()).generate_to(output_dir)?;
+
+    Ok(())
+}
+
+fn labels() {
+    let config = config::read().expect("Failed to read config");
+    for day in config.days.iter() {
+        println!("{}", day.label);
+    }
+}
+
+fn config_path() {
+    println!("{}", config::resolve_path().display());
+}
+
+fn config_show() {
+    let config = config::read().expect("Failed to read config");
+    let json = config
+        .write_to_string()
+        .expect("Failed to serialize config");
+
+    println!("{}", config::resolve_path().display());
+    println!("{json}");
+}
+
+fn config_validate(strict: bool, min_date: OffsetDateTime) {
+    let path = config::resolve_path();
+    let json = match fs::read_to_string(&path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            println!("No config at {}, nothing to validate", path.display());
+            return;
+        }
+        Err(e) => {
+            eprintln!("Error: can't read {}: {e}", path.display());
+            process::exit(2);
+        }
+    };
+    let config = Config::from_json_str(&json).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        process::exit(2);
+    });
+
+    let unknown = Config::unknown_fields(&json).expect("Config JSON already parsed");
+    let mut issues = config.validate(now(), min_date);
+    issues.extend(unknown.into_iter().map(ValidationIssue::UnknownField));
+    if issues.is_empty() {
+        println!("Config is valid, {} days", config.days.len());
+        return;
+    }
+
+    let level = if strict { "Error" } else { "Warning" };
+    for issue in &issues {
+        eprintln!("{level}: {issue}");
+    }
+    process::exit(if strict { 2 } else { 1 });
+}
+
+fn config_import(path: PathBuf, overwrite: bool) {
+    let imported = fs::read_to_string(&path)
+        .map_err(ConfigError::from)
+        .and_then(|json| Config::from_json_str(&json))
+        .unwrap_or_else(|e| fail(format!("Can't import {}: {e}", path.display())));
+    let on_conflict = if overwrite {
+        OnConflict::Overwrite
+    } else {
+        OnConflict::Skip
+    };
+
+    let mut config = config::read().expect("Failed to read config");
+    let summary = config
+        .merge(imported.days, on_conflict)
+        .unwrap_or_else(|e| fail(format!("{e}, nothing imported")));
     config::write(&config).expect("Failed to write config");
 
-    println!("Day with label '{label}' removed from config");
+    print_merge_summary(&summary);
+}
+
+fn config_reset(from_backup: bool, yes: bool) {
+    if from_backup {
+        let backup_path = config::backup_path();
+        if !backup_path.exists() {
+            fail(format!("No backup found at {}", backup_path.display()));
+        }
+
+        if !yes && !confirm(words().restore_backup) {
+            fail(words().aborted);
+        }
+        let config = config::restore_backup().unwrap_or_else(|e| fail(e));
+        println!(
+            "Config restored from {} ({} days)",
+            backup_path.display(),
+            config.days.len()
+        );
+    } else {
+        if !yes && !confirm(words().delete_all) {
+            fail(words().aborted);
+        }
+        config::backup().expect("Failed to backup config");
+        config::write(&Config::default()).expect("Failed to write config");
+        println!(
+            "Config reset, backup saved to {}",
+            config::backup_path().display()
+        );
+    }
+}
+
+/// Asks the user to type 'yes' (in their language) to continue
+fn confirm(message: &str) -> bool {
+    let words = words();
+    let question = Words::fill(words.type_to_continue, &[("word", words.confirm_word)]);
+    print!("{message} {question} ");
+    io::stdout().flush().expect("Failed to flush stdout");
+
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .expect("Failed to read answer");
+
+    answer.trim() == words.confirm_word
+}
+
+/// Asks a yes/no question, the answer is no unless it's "y" or "yes" (in
+/// the language of the messages)
+///
+/// The choices, e.g. "[y/N]", replace the `{yes_no}` placeholder of the
+/// question, or follow it if there's none.
+fn ask(question: &str, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<bool> {
+    let words = words();
+    if question.contains("{yes_no}") {
+        let prompt = Words::fill(question, &[("yes_no", words.yes_no)]);
+        write!(output, "{prompt} ")?;
+    } else {
+        write!(output, "{question} {} ", words.yes_no)?;
+    }
+    output.flush()?;
+
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+
+    Ok(words
+        .yes_answers
+        .contains(&answer.trim().to_lowercase().as_str()))
+}
+
+/// Words of the messages, English until the language is picked
+fn words() -> &'static Words {
+    WORDS.get().copied().unwrap_or(&Words::ENGLISH)
+}
+
+/// Asks to pick a day from a numbered list, by its number or a part of
+/// its label (ignoring case)
+///
+/// Asks again if the answer matches no day or more than one, `None` if
+/// the answer is empty (or at the end of the input).
+fn pick_day<'a>(
+    config: &'a Config,
+    prompt: &str,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> io::Result<Option<&'a Day>> {
+    for (i, day) in config.days.iter().enumerate() {
+        writeln!(
+            output,
+            "{:>3}. {} ({})",
+            i + 1,
+            day.label,
+            render::format_day(day.date)
+        )?;
+    }
+
+    loop {
+        write!(output, "{prompt} [1-{}]: ", config.days.len())?;
+        output.flush()?;
+
+        let mut answer = String::new();
+        input.read_line(&mut answer)?;
+        let answer = answer.trim();
+        if answer.is_empty() {
+            return Ok(None);
+        }
+
+        if let Ok(number) = answer.parse::<usize>() {
+            match number.checked_sub(1).and_then(|i| config.days.get(i)) {
+                Some(day) => return Ok(Some(day)),
+                None => writeln!(output, "No day number {number}")?,
+            }
+            continue;
+        }
+
+        let query = answer.to_lowercase();
+        let matched: Vec<&Day> = config
+            .days
+            .iter()
+            .filter(|day| day.label.to_lowercase().contains(&query))
+            .collect();
+        match matched[..] {
+            [day] => return Ok(Some(day)),
+            [] => writeln!(output, "No day matches '{answer}'")?,
+            _ => writeln!(
+                output,
+                "'{answer}' matches {} days, be more specific",
+                matched.len()
+            )?,
+        }
+    }
+}
+
+/// Picks a day with `pick_day()` on stdin, exits with an error if stdin is
+/// not a terminal or no day is picked
+fn pick_day_from_stdin<'a>(config: &'a Config, prompt: &str) -> &'a Day {
+    if !io::stdin().is_terminal() {
+        fail("No label given, one is required when stdin is not a terminal");
+    }
+    if config.days.is_empty() {
+        fail("No days to pick from");
+    }
+
+    match pick_day(config, prompt, &mut io::stdin().lock(), &mut io::stdout()) {
+        Ok(Some(day)) => day,
+        Ok(None) => fail("No day picked"),
+        Err(e) => fail(format!("Failed to read answer: {e}")),
+    }
+}
+
+/// The current time, see `achievements::current_time()`
+///
+/// `ACHIEVEMENTS_NOW` is checked at the start of `main()`.
+fn now() -> OffsetDateTime {
+    current_time().unwrap_or_else(|e| fail(e))
+}
+
+/// Prints the error message and exits with status code 1
+fn fail(message: impl Display) -> ! {
+    eprintln!("Error: {message}");
+    process::exit(1);
+}
+
+#[test]
+fn ask_test() {
+    let ask = |answer: &str| -> (bool, String) {
+        let mut output = Vec::new();
+        let yes = ask(
+            "Remove 'Moon landing' (1969-07-20)?",
+            &mut answer.as_bytes(),
+            &mut output,
+        )
+        .expect("Failed to ask");
+        (yes, String::from_utf8(output).unwrap())
+    };
+
+    let (yes, output) = ask("y\n");
+    assert!(yes);
+    assert_eq!(output, "Remove 'Moon landing' (1969-07-20)? [y/N] ");
+
+    assert!(ask(" YES \n").0);
+    assert!(!ask("\n").0);
+    assert!(!ask("no\n").0);
+    assert!(!ask("").0);
+
+    // the choices in the middle of the question
+    let mut output = Vec::new();
+    let yes = crate::ask(
+        "Add 'Wedding'? {yes_no}:",
+        &mut "y\n".as_bytes(),
+        &mut output,
+    );
+    assert!(yes.expect("Failed to ask"));
+    assert_eq!(String::from_utf8(output).unwrap(), "Add 'Wedding'? [y/N]: ");
+}
+
+#[test]
+fn pick_day_test() {
+    let mut config = Config::default();
+    config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    config.set_day("Berlin Wall Fall", datetime!(1989-11-09 0:00 UTC));
+    config.set_day("apollo 11 launch", datetime!(1969-07-16 13:32 UTC));
+
+    let pick = |answers: &str| -> (Option<String>, String) {
+        let mut output = Vec::new();
+        let day = pick_day(&config, "Day to show", &mut answers.as_bytes(), &mut output)
+            .expect("Failed to pick");
+        (
+            day.map(|day| day.label.clone()),
+            String::from_utf8(output).unwrap(),
+        )
+    };
+
+    let (day, output) = pick("2\n");
+    assert_eq!(day.as_deref(), Some("Berlin Wall Fall"));
+    assert_eq!(
+        output,
+        "  1. Moon landing (1969-07-20)\n  \
+           2. Berlin Wall Fall (1989-11-09)\n  \
+           3. apollo 11 launch (1969-07-16)\n\
+         Day to show [1-3]: "
+    );
+
+    assert_eq!(pick(" APOLLO \n").0.as_deref(), Some("apollo 11 launch"));
+    assert_eq!(pick("").0, None);
+    assert_eq!(pick("\n3\n").0, None);
+
+    let (day, output) = pick("4\nmars\na\nwall\n");
+    assert_eq!(day.as_deref(), Some("Berlin Wall Fall"));
+    assert!(output.ends_with(
+        "Day to show [1-3]: No day number 4\n\
+         Day to show [1-3]: No day matches 'mars'\n\
+         Day to show [1-3]: 'a' matches 3 days, be more specific\n\
+         Day to show [1-3]: "
+    ));
+
+    // Picking then confirming on the same input, as `remove` does
+    let mut input = "2\ny\n".as_bytes();
+    let mut output = Vec::new();
+    let label = pick_day(&config, "Day to remove", &mut input, &mut output)
+        .expect("Failed to pick")
+        .map(|day| day.label.clone())
+        .expect("No day picked");
+    assert!(ask("Remove?", &mut input, &mut output).expect("Failed to ask"));
+    config.remove_day(&label);
+    let labels: Vec<&str> = config.days.iter().map(|day| day.label.as_str()).collect();
+    assert_eq!(labels, ["Moon landing", "apollo 11 launch"]);
 }
@@ -0,0 +1,1635 @@
+//! Render achievements in different formats
+//!
+//! Functions in this module take the already computed achievements so that
+//! they can be tested without reading the config or depending on the
+//! current time.
+
+use std::io::IsTerminal;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use time::{format_description::FormatItem, macros::format_description, OffsetDateTime, Time};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{
+    doctor::{Check, Status},
+    locale::{format_date, DateStyle, IntervalLocale, Words},
+    Achievement, BadgeStyle, Comparison, Interval, Milestone, Stats, Upcoming,
+};
+
+pub mod calendar;
+pub mod svg;
+pub mod template;
+pub mod timeline;
+
+const DATE_FORMAT: &[FormatItem<'static>] = format_description!("[year]-[month]-[day]");
+const TIME_FORMAT: &[FormatItem<'static>] = format_description!("[hour]:[minute]:[second]");
+const DATE_MINUTE_FORMAT: &[FormatItem<'static>] =
+    format_description!("[year]-[month]-[day] [hour]:[minute]");
+
+/// Labels wider than this are truncated with an ellipsis in aligned output
+pub const MAX_LABEL_WIDTH: usize = 32;
+
+/// Options for the plain text rendering
+#[derive(Debug, Clone)]
+pub struct TextOptions {
+    /// Style of the badges after the interval words
+    pub badges: BadgeStyle,
+    /// Show the special messages, e.g. "1 year, happy anniversary!"
+    pub messages: bool,
+    /// Replace non-ASCII characters in labels with `?`
+    pub ascii: bool,
+    /// Align labels, interval words and badges in columns
+    pub align: bool,
+    /// Color the labels and interval words, see `ColorChoice`
+    pub color: ColorChoice,
+    /// Abbreviated interval words, see `Interval::to_words_short()`
+    pub short: bool,
+    /// Width of the terminal, to show only the badges fitting on each line
+    pub terminal_width: Option<u16>,
+    /// Progress bar towards the next milestone after the badges, see
+    /// `ProgressBar::render_to()`
+    pub progress: Option<ProgressBar>,
+}
+
+/// Whether to color the text output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and `NO_COLOR` is not set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to actually color the output
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => {
+                let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+                !no_color && std::io::stdout().is_terminal()
+            }
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self {
+            badges: BadgeStyle::Emoji,
+            messages: true,
+            ascii: false,
+            align: false,
+            color: ColorChoice::Never,
+            short: false,
+            terminal_width: None,
+            progress: None,
+        }
+    }
+}
+
+impl TextOptions {
+    /// Options for a fully ASCII output
+    ///
+    /// ASCII badges, no special messages and non-ASCII characters in labels
+    /// replaced with `?`, safe for dumb terminals and simple parsers.
+    pub fn plain() -> Self {
+        Self {
+            badges: BadgeStyle::Ascii,
+            messages: false,
+            ascii: true,
+            align: false,
+            color: ColorChoice::Never,
+            short: false,
+            terminal_width: None,
+            progress: None,
+        }
+    }
+
+    /// Interval in words, abbreviated or with or without the special
+    /// messages, in the locale of the current thread (the abbreviations
+    /// are always English), see `IntervalLocale`
+    pub fn words(&self, interval: &Interval) -> String {
+        let locale = IntervalLocale::current();
+        if self.short {
+            interval.to_words_short()
+        } else if self.messages {
+            interval.to_words_in(locale)
+        } else {
+            interval.to_words_plain_in(locale)
+        }
+    }
+}
+
+/// Renders the achievements as text, one "Label: interval" line each
+///
+/// For example:
+///
+/// ```text
+/// Moon landing: 19985 days 💎💎💎💎💎
+/// Berlin Wall Fall: 12568 days 💎💎💎
+/// ```
+///
+/// When `options.align` is `true` the labels, interval words and badges
+/// are aligned in columns, with labels wider than `MAX_LABEL_WIDTH`
+/// truncated:
+///
+/// ```text
+/// Moon landing:     19985 days 💎💎💎💎💎
+/// Berlin Wall Fall: 12568 days 💎💎💎
+/// ```
+///
+/// Widths are measured in terminal columns with the `unicode-width` crate,
+/// e.g. most emoji are 2 columns wide. Terminals may disagree on the width
+/// of some characters, in which case the columns will be slightly off.
+///
+/// With `options.color` the labels are bold and the interval words colored
+/// by the biggest unit reached, from dim for days to magenta for decades.
+///
+/// With `options.progress` each line ends with a progress bar towards the
+/// next milestone, except for archived days and closed periods:
+///
+/// ```text
+/// Quit smoking: 299 days ⭐⭐⭐⭐⭐⭐⭐⭐⭐ [████████░░] 75% to 1 year
+/// Moved:        1 year   🌟                 [██████████] 🎉 today
+/// ```
+///
+/// With `options.terminal_width` lines show only the badges fitting in
+/// the terminal, measured once after the widest label and interval words
+/// so that the badges stay in a column.
+///
+/// See `TextOptions` for more ways to customise the output.
+/// Lines never have trailing whitespace.
+pub fn text(achievements: &[Achievement], options: &TextOptions) -> String {
+    let color = options.color.enabled();
+    let mut rows: Vec<[String; 3]> = achievements
+        .iter()
+        .map(|Achievement { day, interval }| {
+            let label = if options.ascii {
+                ascii_only(&day.label)
+            } else {
+                day.label.clone()
+            };
+            let label = if options.align {
+                truncate(&label, MAX_LABEL_WIDTH, options.ascii)
+            } else {
+                label
+            };
+
+            [
+                format!("{label}:"),
+                options.words(interval),
+                interval.badges_in_style(options.badges),
+            ]
+        })
+        .collect();
+
+    let (label_width, words_width) = if options.align {
+        (
+            max_width(rows.iter().map(|[label, _, _]| label)),
+            max_width(rows.iter().map(|[_, words, _]| words)),
+        )
+    } else {
+        (0, 0)
+    };
+    if let Some(terminal_width) = options.terminal_width {
+        let label_width = max_width(rows.iter().map(|[label, _, _]| label));
+        let words_width = max_width(rows.iter().map(|[_, words, _]| words));
+        let locale = IntervalLocale::current();
+        for ([_, words, badges], Achievement { interval, .. }) in rows.iter_mut().zip(achievements)
+        {
+            // as if the words were as wide as the widest ones, so that the
+            // badges stay in a column
+            let padding = words_width - words.width();
+            *badges = interval
+                .formatter()
+                .locale(locale)
+                .badge_style(options.badges)
+                .short(options.short)
+                .messages(options.messages)
+                .auto_cap_badges(terminal_width, label_width + padding)
+                .build()
+                .badges();
+        }
+    }
+    // The badges are a column only when followed by the progress bars
+    let badges_width = if options.align && options.progress.is_some() {
+        max_width(rows.iter().map(|[_, _, badges]| badges))
+    } else {
+        0
+    };
+
+    let mut text = String::new();
+    for ([label, words, badges], Achievement { day, interval }) in
+        rows.into_iter().zip(achievements)
+    {
+        let (label, words) = if color {
+            (
+                paint(&label, BOLD, label_width),
+                paint(&words, tier_color(interval), words_width),
+            )
+        } else {
+            (pad(&label, label_width), pad(&words, words_width))
+        };
+        let mut line = format!("{label} {words} {}", pad(&badges, badges_width));
+        match &options.progress {
+            Some(bar) if !day.archived && !day.is_closed() => {
+                line.push(' ');
+                line.push_str(&bar.render_to(
+                    interval.progress(),
+                    interval.next_milestone(),
+                    options.ascii,
+                ));
+            }
+            _ => {}
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Renders the achievements as a GitHub-flavored Markdown table
+///
+/// Columns are Label, Since (date as `YYYY-MM-DD`), Elapsed and Badges,
+/// for example:
+///
+/// ```Markdown
+/// | Label | Since | Elapsed | Badges |
+/// | --- | --- | --- | --- |
+/// | Moon landing | 1969-07-20 | 19985 days | 💎💎💎💎💎 |
+/// ```
+pub fn markdown_table(achievements: &[Achievement]) -> String {
+    let mut table =
+        String::from("| Label | Since | Elapsed | Badges |\n| --- | --- | --- | --- |\n");
+
+    for Achievement { day, interval } in achievements {
+        table.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            escape_markdown(&day.label),
+            format_day(day.date),
+            interval.to_words_in(IntervalLocale::current()),
+            interval.badges(),
+        ));
+    }
+
+    table
+}
+
+/// Style of the HTML report, see `html()`
+const HTML_STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; }
+table { border-collapse: collapse; }
+th, td { padding: 0.4rem 0.8rem; text-align: left; border-bottom: 1px solid #ddd; }
+td.elapsed { text-align: right; }
+tr.gold { background: #fff4c2; }
+tr.silver { background: #eceff1; }
+tr.bronze { background: #f6e0cc; }
+.notes { font-size: 0.85em; color: #666; }
+.generated { font-size: 0.85em; color: #666; }";
+
+/// Renders the achievements as a self-contained HTML page, with inline
+/// CSS and nothing to download
+///
+/// The table has the same columns as `markdown_table()`, with the notes
+/// under the label, and the progress to the next milestone (none for
+/// archived days and closed periods). The rows are colored by tier:
+/// decades in gold, years in silver, months in bronze and days (or weeks)
+/// plain. For example:
+///
+/// ```HTML
+/// <tr class="gold"><td>Moon landing</td><td>1969-07-20</td><td class="elapsed">19985 days</td><td>💎💎💎💎💎</td><td><progress value="0.61" max="1"></progress> 20000 days</td></tr>
+/// ```
+///
+/// The page ends with the time it was generated at, `now`.
+pub fn html(achievements: &[Achievement], now: OffsetDateTime) -> String {
+    let mut page = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Achievements</title>\n<style>\n{HTML_STYLE}\n</style>\n</head>\n<body>\n\
+         <h1>Achievements</h1>\n<table>\n\
+         <tr><th>Label</th><th>Since</th><th>Elapsed</th><th>Badges</th><th>Next milestone</th></tr>\n"
+    );
+
+    for Achievement { day, interval } in achievements {
+        let notes = match &day.description {
+            Some(notes) => format!("<div class=\"notes\">{}</div>", escape_html(notes)),
+            None => String::new(),
+        };
+        let next = if day.archived || day.is_closed() {
+            "—".to_string()
+        } else {
+            let upcoming = Upcoming::new(day.clone(), now);
+            format!(
+                "<progress value=\"{:.2}\" max=\"1\"></progress> {}",
+                upcoming.progress(),
+                upcoming.milestone.to_words()
+            )
+        };
+        page.push_str(&format!(
+            "<tr class=\"{}\"><td>{}{notes}</td><td>{}</td><td class=\"elapsed\">{}</td>\
+             <td>{}</td><td>{next}</td></tr>\n",
+            tier_class(interval),
+            escape_html(&day.label),
+            format_day(day.date),
+            interval.to_words(),
+            interval.badges(),
+        ));
+    }
+
+    page.push_str(&format!(
+        "</table>\n<p class=\"generated\">Generated at {}</p>\n</body>\n</html>\n",
+        now.format(DATE_MINUTE_FORMAT)
+            .expect("Failed to format date"),
+    ));
+    page
+}
+
+/// Renders the achievements as a Markdown bullet list
+///
+/// For example:
+///
+/// ```Markdown
+/// - Moon landing: 19985 days 💎💎💎💎💎 (since 1969-07-20)
+/// ```
+pub fn markdown_list(achievements: &[Achievement]) -> String {
+    let mut list = String::new();
+
+    for Achievement { day, interval } in achievements {
+        list.push_str(&format!(
+            "- {}: {} (since {})\n",
+            escape_markdown(&day.label),
+            interval,
+            format_day(day.date),
+        ));
+    }
+
+    list
+}
+
+/// Renders the days as in `list`, with the interval in words after the
+/// date, for `list --with-elapsed`
+///
+/// The dates and intervals are aligned in columns when `options.align` is
+/// `true`:
+///
+/// ```text
+/// 'Moon landing':     1969-07-20 20:17:40.0 +00:00:00 19985 days
+/// 'Berlin Wall Fall': 1989-11-09 18:53:00.0 +01:00:00 12568 days
+/// ```
+pub fn list_with_elapsed(achievements: &[Achievement], options: &TextOptions) -> String {
+    let rows: Vec<[String; 3]> = achievements
+        .iter()
+        .map(|Achievement { day, interval }| {
+            [
+                format!("'{}':", day.label),
+                day.date.to_string(),
+                options.words(interval),
+            ]
+        })
+        .collect();
+
+    let (label_width, date_width) = if options.align {
+        (
+            max_width(rows.iter().map(|[label, _, _]| label)),
+            max_width(rows.iter().map(|[_, date, _]| date)),
+        )
+    } else {
+        (0, 0)
+    };
+
+    let mut list = String::new();
+    for [label, date, words] in rows {
+        list.push_str(&format!(
+            "{} {} {words}\n",
+            pad(&label, label_width),
+            pad(&date, date_width)
+        ));
+    }
+
+    list
+}
+
+/// Renders the days with their computed context, for `list --verbose` and
+/// `show`
+///
+/// One line per day with the date (and time, unless it's midnight) in the
+/// style, with the month names of the locale, the weekday and the interval
+/// (see `Interval::to_verbose_string_in()`) in the locale too, followed by
+/// the tags, the notes and whether it's archived, when set:
+///
+/// ```text
+/// Moon landing: 1969-07-20 20:17:40, Sunday, 54 years, 9 months, 5 days (19985 days total)
+///   tags: space, history
+///   notes: One small step
+///   archived
+/// ```
+pub fn list_verbose(achievements: &[Achievement], locale: &Words, style: DateStyle) -> String {
+    let mut list = String::new();
+
+    for Achievement { day, interval } in achievements {
+        list.push_str(&format!(
+            "{}: {}, {}, {}\n",
+            day.label,
+            format_day_time(day.date, locale, style),
+            locale.weekday(day.date.weekday()),
+            interval.to_verbose_string_in(locale)
+        ));
+        if !day.tags.is_empty() {
+            list.push_str(&format!("  tags: {}\n", day.tags.join(", ")));
+        }
+        if let Some(description) = &day.description {
+            list.push_str(&format!("  notes: {description}\n"));
+        }
+        if day.archived {
+            list.push_str("  archived\n");
+        }
+    }
+
+    list
+}
+
+/// Renders the achievements on a single line for a shell prompt, without
+/// a trailing newline
+///
+/// Each achievement is its icon (see `Day::icon_or_initial()`) and the
+/// abbreviated interval, e.g. "🏃 412d · 💍 3y".
+pub fn prompt(achievements: &[Achievement]) -> String {
+    achievements
+        .iter()
+        .map(|Achievement { day, interval }| {
+            format!("{} {}", day.icon_or_initial(), interval.to_words_short())
+        })
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+/// Status bar module, in the JSON format of waybar's custom modules
+///
+/// Serialized on one line, e.g.:
+///
+/// ```JSON
+/// {"text":"💍 3y","tooltip":"Wedding: 3 years 🌟🌟🌟\nRunning: 412 days 🌟","class":"achievements"}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BarModule {
+    /// Icon and abbreviated interval of one achievement, see `prompt()`
+    pub text: String,
+    /// All the achievements, one per line, see `text()`
+    pub tooltip: String,
+    pub class: &'static str,
+}
+
+/// Builds the status bar module of the achievements
+///
+/// The text shows the achievement with the `entry` label (ignoring case
+/// and leading/trailing whitespace), the oldest one by default. It's
+/// empty when there are no achievements or none has that label.
+pub fn bar(achievements: &[Achievement], entry: Option<&str>) -> BarModule {
+    let selected = match entry {
+        Some(label) => {
+            let label = label.trim().to_lowercase();
+            achievements
+                .iter()
+                .find(|achievement| achievement.day.label.trim().to_lowercase() == label)
+        }
+        None => achievements
+            .iter()
+            .min_by_key(|achievement| achievement.day.date),
+    };
+
+    BarModule {
+        text: selected.map_or_else(String::new, |achievement| {
+            prompt(std::slice::from_ref(achievement))
+        }),
+        tooltip: text(achievements, &TextOptions::default())
+            .trim_end()
+            .to_string(),
+        class: "achievements",
+    }
+}
+
+/// Renders the achievements that reached a whole number of weeks, months,
+/// years or decades, see `Interval::is_exact_unit()`
+///
+/// The others are skipped, e.g. to notify milestones once a day:
+///
+/// ```text
+/// 🎉 Milestone reached: Wedding — 5 years 🌟🌟🌟🌟🌟
+/// ```
+pub fn milestones_reached(achievements: &[Achievement]) -> String {
+    achievements
+        .iter()
+        .filter(|achievement| achievement.interval.is_exact_unit())
+        .map(|Achievement { day, interval }| {
+            format!("🎉 Milestone reached: {} — {interval}\n", day.label)
+        })
+        .collect()
+}
+
+/// Horizontal bar chart of the achievements, one line each:
+///
+/// ```text
+/// ████████████████████ Moon landing (19888 days)
+/// █████████████ Berlin Wall Fall (12470 days)
+/// █ Moved (3 days)
+/// ```
+///
+/// The bars are proportional to the number of days, the longest one
+/// filling the `width` left by the widest label and count. Every bar is at
+/// least one character, so the days just started still show. With `ascii`
+/// the bars are made of `#` and the labels are ASCII only.
+pub fn bars(achievements: &[Achievement], width: usize, ascii: bool) -> Vec<String> {
+    let fill = if ascii { "#" } else { "█" };
+    let locale = IntervalLocale::current();
+    let ends: Vec<String> = achievements
+        .iter()
+        .map(|Achievement { day, interval }| {
+            let label = if ascii {
+                ascii_only(&day.label)
+            } else {
+                day.label.clone()
+            };
+            format!("{label} ({})", locale.day(interval.as_days()))
+        })
+        .collect();
+    let widest = ends.iter().map(|end| end.width()).max().unwrap_or_default();
+    let bar_width = width.saturating_sub(widest + 1).max(1);
+    let most = achievements
+        .iter()
+        .map(|achievement| achievement.interval.as_days())
+        .max()
+        .unwrap_or_default();
+
+    achievements
+        .iter()
+        .zip(ends)
+        .map(|(achievement, end)| {
+            let days = achievement.interval.as_days();
+            let length = if most == 0 {
+                1
+            } else {
+                (days as f64 / most as f64 * bar_width as f64).round() as usize
+            };
+            format!("{} {end}", fill.repeat(length.max(1)))
+        })
+        .collect()
+}
+
+/// Progress bar towards the next milestone, e.g. `[████████░░] 80%`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgressBar {
+    /// Number of characters between the brackets
+    pub width: usize,
+    /// Character of the reached part of the bar
+    pub filled: char,
+    /// Character of the part of the bar still to go
+    pub empty: char,
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self {
+            width: 10,
+            filled: '█',
+            empty: '░',
+        }
+    }
+}
+
+impl ProgressBar {
+    /// ASCII progress bar, e.g. `[########..] 80%`
+    pub fn ascii() -> Self {
+        Self {
+            filled: '#',
+            empty: '.',
+            ..Default::default()
+        }
+    }
+
+    /// Renders the bar for a progress from 0.0 to 1.0
+    ///
+    /// The filled part is `progress * width` rounded to the nearest
+    /// character, the percentage is rounded down so 100% means reached.
+    ///
+    /// ```
+    /// use achievements::render::ProgressBar;
+    ///
+    /// assert_eq!("[████████░░] 80%", ProgressBar::default().render(0.8));
+    /// let ascii = ProgressBar { width: 5, ..ProgressBar::ascii() };
+    /// assert_eq!("[##...] 40%", ascii.render(0.4));
+    /// ```
+    pub fn render(&self, progress: f64) -> String {
+        let progress = progress.clamp(0.0, 1.0);
+        let filled = (progress * self.width as f64).round() as usize;
+        format!(
+            "[{}{}] {}%",
+            self.filled.to_string().repeat(filled),
+            self.empty.to_string().repeat(self.width - filled),
+            (progress * 100.0).floor()
+        )
+    }
+
+    /// Renders the bar for a progress towards `milestone`, e.g.
+    /// `[████████░░] 83% to 1 year`
+    ///
+    /// A progress of 1.0 means the milestone is reached today, shown as a
+    /// full bar followed by "🎉 today" (just "today" with `ascii`).
+    ///
+    /// ```
+    /// use achievements::{render::ProgressBar, Milestone};
+    ///
+    /// let bar = ProgressBar::default();
+    /// assert_eq!("[█████░░░░░] 50% to 100 days", bar.render_to(0.5, Milestone::Days(100), false));
+    /// assert_eq!("[██████████] 🎉 today", bar.render_to(1.0, Milestone::Years(1), false));
+    /// ```
+    pub fn render_to(&self, progress: f64, milestone: Milestone, ascii: bool) -> String {
+        if progress < 1.0 {
+            return format!("{} to {milestone}", self.render(progress));
+        }
+
+        let party = if ascii { "" } else { "🎉 " };
+        format!(
+            "[{}] {party}today",
+            self.filled.to_string().repeat(self.width)
+        )
+    }
+}
+
+/// Renders the upcoming milestones, one line each, with the progress
+/// towards the milestone when `bar` is given
+///
+/// The intervals are in the locale of the current thread (see
+/// `IntervalLocale`), the words around them in `locale`. For example:
+///
+/// ```text
+/// Moved: 1 year today [██████████] 100%
+/// Wedding anniversary: 5 years in 6 days [██████████] 98%
+/// Quit smoking: 1000 days in 12 days [██████████] 95%
+/// ```
+pub fn upcoming(upcoming: &[Upcoming], locale: &Words, bar: Option<&ProgressBar>) -> String {
+    let mut text = String::new();
+
+    for upcoming in upcoming {
+        text.push_str(&format!(
+            "{}: {} {}",
+            upcoming.day.label,
+            upcoming.milestone,
+            when(upcoming, locale)
+        ));
+        if let Some(bar) = bar {
+            text.push(' ');
+            text.push_str(&bar.render(upcoming.progress()));
+        }
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Renders the milestones coming up with the time elapsed so far, one
+/// line each, for `achievements top-upcoming`
+///
+/// In the same locales as `upcoming()`, for example:
+///
+/// ```text
+/// Moved: 1 year now, 1 year today
+/// Wedding anniversary: 1824 days now, 5 years in 1 day
+/// Quit smoking: 988 days now, 1000 days in 12 days
+/// ```
+pub fn top_upcoming(upcoming: &[Upcoming], locale: &Words) -> String {
+    let intervals = IntervalLocale::current();
+    let mut text = String::new();
+
+    for upcoming in upcoming {
+        // "0 days now" rather than "Recently now"
+        let elapsed = match upcoming.interval.as_days() {
+            0 => intervals.day(0),
+            _ => upcoming.interval.to_words_plain_in(intervals),
+        };
+        text.push_str(&format!(
+            "{}: {}, {} {}\n",
+            upcoming.day.label,
+            Words::fill(locale.elapsed_now, &[("elapsed", &elapsed)]),
+            upcoming.milestone,
+            when(upcoming, locale)
+        ));
+    }
+
+    text
+}
+
+/// When the milestone is reached, "today" or "in 6 days"
+fn when(upcoming: &Upcoming, locale: &Words) -> String {
+    if upcoming.is_today() {
+        locale.today.to_string()
+    } else {
+        let days = IntervalLocale::current().day(upcoming.in_days());
+        Words::fill(locale.in_days, &[("days", &days)])
+    }
+}
+
+/// Renders the stats of the config, with the intervals in the locale of
+/// the current thread (see `IntervalLocale`) and the words around them in
+/// `locale`, for example:
+///
+/// ```text
+/// Entries: 2
+/// Oldest:  Moon landing, 19985 days 💎💎💎💎💎 (since 1969-07-20)
+/// Newest:  Berlin Wall Fall, 12568 days 💎💎💎 (since 1989-11-09)
+/// Total:   32553 days
+/// Average: 16276 days
+/// Tiers:   💎 2 · 🌟 0 · ⭐ 0 · ★ 0 · ☆ 0
+/// ```
+pub fn stats(stats: &Stats, locale: &Words) -> String {
+    let intervals = IntervalLocale::current();
+    let achievement = |achievement: &Option<Achievement>| match achievement {
+        Some(Achievement { day, interval }) => format!(
+            "{}, {} ({})",
+            day.label,
+            interval.formatter().locale(intervals),
+            Words::fill(locale.since, &[("date", &format_day(day.date))])
+        ),
+        None => "-".to_string(),
+    };
+    let tiers = &stats.tiers;
+
+    format!(
+        "Entries: {}
+Oldest:  {}
+Newest:  {}
+Total:   {}
+Average: {}
+Tiers:   💎 {} · 🌟 {} · ⭐ {} · ★ {} · ☆ {}
+",
+        stats.count,
+        achievement(&stats.oldest),
+        achievement(&stats.newest),
+        intervals.day(stats.total_days),
+        intervals.day(stats.average_days),
+        tiers.decade,
+        tiers.year,
+        tiers.month,
+        tiers.week,
+        tiers.day,
+    )
+}
+
+/// Renders the doctor checks, one per line with a mark for the status
+///
+/// ```text
+/// ✓ Config file: ~/.config/achievements/config.json exists
+/// ! Permissions: 644, the config is readable by other users
+/// ✗ JSON: invalid config JSON: expected value at line 1 column 1
+/// ```
+pub fn doctor(checks: &[Check]) -> String {
+    checks
+        .iter()
+        .map(|check| {
+            let mark = match check.status {
+                Status::Ok => '✓',
+                Status::Warning => '!',
+                Status::Error => '✗',
+            };
+            format!("{mark} {}: {}\n", check.name, check.message)
+        })
+        .collect()
+}
+
+/// Renders the two days of the comparison and the gap between them
+///
+/// ```text
+/// Moon landing:     1969-07-20, 19985 days 💎💎💎💎💎
+/// Berlin Wall Fall: 1989-11-09, 12568 days 💎💎💎
+/// Moon landing was 20 years, 3 months, 3 weeks, 5 days before Berlin Wall Fall
+/// ```
+pub fn comparison(comparison: &Comparison) -> String {
+    let Comparison {
+        earlier,
+        later,
+        gap,
+    } = comparison;
+    let width = earlier.day.label.width().max(later.day.label.width()) + 1;
+
+    let mut text = String::new();
+    for Achievement { day, interval } in [earlier, later] {
+        let label = format!("{}:", day.label);
+        text.push_str(&format!(
+            "{label}{} {}, {interval}\n",
+            " ".repeat(width - label.width()),
+            format_day(day.date),
+        ));
+    }
+    if gap.as_days() == 0 {
+        text.push_str(&format!(
+            "{} and {} are on the same day\n",
+            earlier.day.label, later.day.label
+        ));
+    } else {
+        text.push_str(&format!(
+            "{} was {} before {}\n",
+            earlier.day.label,
+            gap.to_compound_string(),
+            later.day.label
+        ));
+    }
+
+    text
+}
+
+/// Formats the date as `YYYY-MM-DD`
+pub fn format_day(date: OffsetDateTime) -> String {
+    date.format(DATE_FORMAT).expect("Failed to format date")
+}
+
+/// Formats the date in the style, followed by the time when it's not
+/// midnight, e.g. "1969-07-20 20:17:40"
+fn format_day_time(date: OffsetDateTime, locale: &Words, style: DateStyle) -> String {
+    let day = format_date(date.date(), locale, style);
+    if date.time() == Time::MIDNIGHT {
+        return day;
+    }
+    let time = date.format(TIME_FORMAT).expect("Failed to format time");
+    format!("{day} {time}")
+}
+
+/// Widest of the given texts, in terminal columns
+fn max_width<'a>(texts: impl Iterator<Item = &'a String>) -> usize {
+    texts.map(|text| text.width()).max().unwrap_or_default()
+}
+
+/// Pads the text with spaces up to the given width (in terminal columns)
+fn pad(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(text.width());
+    format!("{text}{}", " ".repeat(padding))
+}
+
+const BOLD: &str = "1";
+
+/// ANSI color of the interval words, by the biggest unit reached
+fn tier_color(interval: &Interval) -> &'static str {
+    if interval.decade_count() > 0 {
+        "35" // magenta
+    } else if interval.year_count() > 0 {
+        "36" // cyan
+    } else if interval.month_count() > 0 {
+        "33" // yellow
+    } else if interval.week_count() > 0 {
+        "32" // green
+    } else {
+        "2" // dim
+    }
+}
+
+/// Class of the HTML row, see `html()`
+fn tier_class(interval: &Interval) -> &'static str {
+    if interval.decade_count() > 0 {
+        "gold"
+    } else if interval.year_count() > 0 {
+        "silver"
+    } else if interval.month_count() > 0 {
+        "bronze"
+    } else {
+        "plain"
+    }
+}
+
+/// Wraps the text in the ANSI escape codes of the given style, padded with
+/// spaces (outside the style) up to the given width
+fn paint(text: &str, style: &str, width: usize) -> String {
+    let padding = width.saturating_sub(text.width());
+    format!("\x1b[{style}m{text}\x1b[0m{}", " ".repeat(padding))
+}
+
+/// Truncates the text to the given width (in terminal columns), ending it
+/// with an ellipsis (`...` when `ascii` is `true`) when truncated
+fn truncate(text: &str, width: usize, ascii: bool) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+
+    let ellipsis = if ascii { "..." } else { "…" };
+    let mut truncated = String::new();
+    let mut truncated_width = ellipsis.width();
+    for c in text.chars() {
+        truncated_width += c.width().unwrap_or_default();
+        if truncated_width > width {
+            break;
+        }
+        truncated.push(c);
+    }
+    truncated.push_str(ellipsis);
+
+    truncated
+}
+
+/// Replaces non-ASCII characters with `?`
+fn ascii_only(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_ascii() { c } else { '?' })
+        .collect()
+}
+
+/// Escapes backslashes and pipes so they don't break Markdown tables
+fn escape_markdown(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+/// Escapes the characters with a special meaning in HTML text and
+/// attributes
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+fn test_achievements() -> Vec<Achievement> {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    vec![
+        Achievement::new(
+            Day::new("Moon landing", datetime!(2023-12-18 12:00 UTC)),
+            now,
+        ),
+        Achievement::new(
+            Day::new(r"Either | or \ both", datetime!(2023-12-29 18:00 +02:00)),
+            now,
+        ),
+    ]
+}
+
+#[test]
+fn text_test() {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let mut achievements = test_achievements();
+    achievements.push(Achievement::new(
+        Day::new("Just now", datetime!(2024-01-01 11:00 UTC)),
+        now,
+    ));
+    achievements.push(Achievement::new(
+        Day::new("First anniversary", datetime!(2023-01-01 11:00 UTC)),
+        now,
+    ));
+
+    assert_eq!(
+        text(&achievements, &TextOptions::default()),
+        r"Moon landing: 2 weeks ★★
+Either | or \ both: 2 days ☆☆
+Just now: Recently
+First anniversary: 1 year, happy anniversary! 🌟
+"
+    );
+
+    let options = TextOptions {
+        badges: BadgeStyle::None,
+        ..Default::default()
+    };
+    assert_eq!(
+        text(&achievements, &options),
+        r"Moon landing: 2 weeks
+Either | or \ both: 2 days
+Just now: Recently
+First anniversary: 1 year, happy anniversary!
+"
+    );
+
+    let options = TextOptions {
+        messages: false,
+        ..Default::default()
+    };
+    assert_eq!(
+        text(&achievements, &options),
+        r"Moon landing: 2 weeks ★★
+Either | or \ both: 2 days ☆☆
+Just now: Recently
+First anniversary: 1 year 🌟
+"
+    );
+
+    achievements.push(Achievement::new(
+        Day::new("🚀 Moon landing", datetime!(1969-07-20 20:17:40 UTC)),
+        now,
+    ));
+    let plain = text(&achievements, &TextOptions::plain());
+    assert_eq!(
+        plain,
+        r"Moon landing: 2 weeks ++
+Either | or \ both: 2 days ..
+Just now: Recently
+First anniversary: 1 year @
+? Moon landing: 2841 weeks #####
+"
+    );
+    assert!(plain.is_ascii());
+}
+
+#[test]
+fn text_color_test() {
+    let achievements = test_achievements();
+    let options = |color| TextOptions {
+        color,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        text(&achievements, &options(ColorChoice::Always)),
+        "\x1b[1mMoon landing:\x1b[0m \x1b[32m2 weeks\x1b[0m ★★
+\x1b[1mEither | or \\ both:\x1b[0m \x1b[2m2 days\x1b[0m ☆☆
+"
+    );
+    assert!(!text(&achievements, &options(ColorChoice::Never)).contains('\x1b'));
+}
+
+#[test]
+fn text_aligned_test() {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let achievement = |label: &str, date| Achievement::new(Day::new(label, date), now);
+    let achievements = vec![
+        achievement("Run", datetime!(2023-12-29 12:00 UTC)),
+        achievement(
+            "A very long label that doesn't fit in the column",
+            datetime!(2023-12-18 12:00 UTC),
+        ),
+        achievement("🚀 Moon landing", datetime!(1969-07-20 20:17:40 UTC)),
+        achievement("Today", datetime!(2024-01-01 11:00 UTC)),
+    ];
+
+    let options = TextOptions {
+        align: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        text(&achievements, &options),
+        "\
+Run:                              3 days     ☆☆☆
+A very long label that doesn't …: 2 weeks    ★★
+🚀 Moon landing:                  2841 weeks 💎💎💎💎💎
+Today:                            Recently
+"
+    );
+
+    let options = TextOptions {
+        align: true,
+        ..TextOptions::plain()
+    };
+    assert_eq!(
+        text(&achievements, &options),
+        "\
+Run:                              3 days     ...
+A very long label that doesn'...: 2 weeks    ++
+? Moon landing:                   2841 weeks #####
+Today:                            Recently
+"
+    );
+
+    // 45 columns for the labels and words
+    let options = |terminal_width| TextOptions {
+        align: true,
+        terminal_width: Some(terminal_width),
+        ..Default::default()
+    };
+    assert_eq!(
+        text(&achievements, &options(52)),
+        "\
+Run:                              3 days     ☆☆☆
+A very long label that doesn't …: 2 weeks    ★★
+🚀 Moon landing:                  2841 weeks 💎💎💎
+Today:                            Recently
+"
+    );
+    assert_eq!(
+        text(&achievements, &options(46)),
+        "\
+Run:                              3 days     ☆
+A very long label that doesn't …: 2 weeks    ★
+🚀 Moon landing:                  2841 weeks
+Today:                            Recently
+"
+    );
+}
+
+#[test]
+fn markdown_table_test() {
+    assert_eq!(
+        markdown_table(&test_achievements()),
+        r"| Label | Since | Elapsed | Badges |
+| --- | --- | --- | --- |
+| Moon landing | 2023-12-18 | 2 weeks | ★★ |
+| Either \| or \\ both | 2023-12-29 | 2 days | ☆☆ |
+"
+    );
+    assert_eq!(
+        markdown_table(&[]),
+        "| Label | Since | Elapsed | Badges |\n| --- | --- | --- | --- |\n"
+    );
+}
+
+#[test]
+fn html_test() {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let script = Day {
+        description: Some("<script>alert('notes')</script>".to_string()),
+        ..Day::new("<script>alert(1)</script>", datetime!(2020-01-01 12:00 UTC))
+    };
+    let archived = Day {
+        archived: true,
+        ..Day::new("Moved", datetime!(2023-10-01 12:00 UTC))
+    };
+    let achievements = [
+        Achievement::new(
+            Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC)),
+            now,
+        ),
+        Achievement::new(script, now),
+        Achievement::new(archived, now),
+        Achievement::new(Day::new("Run", datetime!(2023-12-18 12:00 UTC)), now),
+    ];
+
+    let page = html(&achievements, now);
+    assert!(page.starts_with("<!DOCTYPE html>\n"));
+    assert!(page
+        .ends_with("<p class=\"generated\">Generated at 2024-01-01 12:00</p>\n</body>\n</html>\n"));
+    assert!(page.contains("<style>\n"));
+    assert!(!page.contains("http"));
+    assert!(!page.contains("<script>"));
+
+    let rows: Vec<&str> = page
+        .lines()
+        .filter(|line| line.starts_with("<tr class"))
+        .collect();
+    assert_eq!(
+        rows,
+        [
+            "<tr class=\"gold\"><td>Moon landing</td><td>1969-07-20</td>\
+             <td class=\"elapsed\">2841 weeks</td><td>💎💎💎💎💎</td>\
+             <td><progress value=\"0.61\" max=\"1\"></progress> 20000 days</td></tr>",
+            "<tr class=\"silver\"><td>&lt;script&gt;alert(1)&lt;/script&gt;\
+             <div class=\"notes\">&lt;script&gt;alert(&#39;notes&#39;)&lt;/script&gt;</div></td>\
+             <td>2020-01-01</td><td class=\"elapsed\">1461 days</td><td>🌟🌟🌟🌟</td>\
+             <td><progress value=\"0.00\" max=\"1\"></progress> 5 years</td></tr>",
+            "<tr class=\"bronze\"><td>Moved</td><td>2023-10-01</td>\
+             <td class=\"elapsed\">92 days</td><td>⭐⭐⭐</td><td>—</td></tr>",
+            "<tr class=\"plain\"><td>Run</td><td>2023-12-18</td>\
+             <td class=\"elapsed\">2 weeks</td><td>★★</td>\
+             <td><progress value=\"0.14\" max=\"1\"></progress> 100 days</td></tr>",
+        ]
+    );
+
+    assert!(html(&[], now).contains("<th>Next milestone</th></tr>\n</table>"));
+}
+
+#[test]
+fn markdown_list_test() {
+    assert_eq!(
+        markdown_list(&test_achievements()),
+        r"- Moon landing: 2 weeks ★★ (since 2023-12-18)
+- Either \| or \\ both: 2 days ☆☆ (since 2023-12-29)
+"
+    );
+}
+
+#[test]
+fn list_with_elapsed_test() {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let achievements = [
+        Achievement::new(
+            Day::new("Moon landing", datetime!(2023-12-18 12:00 UTC)),
+            now,
+        ),
+        Achievement::new(Day::new("New year", datetime!(2023-01-01 0:00 +01:00)), now),
+    ];
+
+    assert_eq!(
+        list_with_elapsed(&achievements, &TextOptions::default()),
+        "\
+'Moon landing': 2023-12-18 12:00:00.0 +00:00:00 2 weeks
+'New year': 2023-01-01 0:00:00.0 +01:00:00 1 year, happy anniversary!
+"
+    );
+
+    let options = TextOptions {
+        align: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        list_with_elapsed(&achievements, &options),
+        "\
+'Moon landing': 2023-12-18 12:00:00.0 +00:00:00 2 weeks
+'New year':     2023-01-01 0:00:00.0 +01:00:00  1 year, happy anniversary!
+"
+    );
+}
+
+#[test]
+fn prompt_test() {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let running = Day {
+        icon: Some("🏃".to_string()),
+        ..Day::new("Running", datetime!(2022-11-15 12:00 UTC))
+    };
+    let achievements = [
+        Achievement::new(running, now),
+        Achievement::new(Day::new("wedding", datetime!(2021-01-01 0:00 UTC)), now),
+    ];
+
+    assert_eq!(prompt(&achievements), "🏃 412d · w 3y");
+    assert_eq!(prompt(&achievements[..1]), "🏃 412d");
+    assert_eq!(prompt(&[]), "");
+}
+
+#[test]
+fn bar_test() {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let wedding = Day {
+        icon: Some("💍".to_string()),
+        ..Day::new("Wedding", datetime!(2021-01-01 0:00 UTC))
+    };
+    let achievements = [
+        Achievement::new(Day::new("Running", datetime!(2022-11-15 12:00 UTC)), now),
+        Achievement::new(wedding, now),
+    ];
+
+    let module = bar(&achievements, None);
+    assert_eq!(module.text, "💍 3y");
+    assert_eq!(
+        serde_json::to_string(&module).unwrap(),
+        r#"{"text":"💍 3y","tooltip":"Running: 412 days 🌟\nWedding: 3 years 🌟🌟🌟","class":"achievements"}"#
+    );
+
+    assert_eq!(bar(&achievements, Some(" running ")).text, "R 412d");
+    assert_eq!(bar(&achievements, Some("Mars")).text, "");
+    assert_eq!(
+        bar(&[], None),
+        BarModule {
+            text: String::new(),
+            tooltip: String::new(),
+            class: "achievements",
+        }
+    );
+}
+
+#[test]
+fn bars_test() {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let achievements = [
+        Achievement::new(Day::new("Ten", datetime!(2023-12-22 12:00 UTC)), now),
+        Achievement::new(Day::new("Five", datetime!(2023-12-27 12:00 UTC)), now),
+        Achievement::new(Day::new("Two", datetime!(2023-12-30 12:00 UTC)), now),
+        Achievement::new(Day::new("Now", datetime!(2024-01-01 12:00 UTC)), now),
+    ];
+
+    // 10 columns left for the bars after "Five (5 days)"
+    assert_eq!(
+        bars(&achievements, 24, false),
+        [
+            "██████████ Ten (10 days)",
+            "█████ Five (5 days)",
+            "██ Two (2 days)",
+            "█ Now (0 days)",
+        ]
+    );
+    assert_eq!(
+        bars(&achievements[..2], 34, true),
+        [
+            "#################### Ten (10 days)",
+            "########## Five (5 days)"
+        ]
+    );
+    // Too narrow
+    assert_eq!(bars(&achievements[..2], 5, true)[0], "# Ten (10 days)");
+
+    // All the same age
+    let achievements = [
+        Achievement::new(Day::new("Café", datetime!(2023-12-22 12:00 UTC)), now),
+        Achievement::new(Day::new("Run", datetime!(2023-12-22 12:00 UTC)), now),
+    ];
+    assert_eq!(
+        bars(&achievements, 20, true),
+        ["##### Caf? (10 days)", "##### Run (10 days)"]
+    );
+    let achievements = [Achievement::new(
+        Day::new("Now", datetime!(2024-01-01 12:00 UTC)),
+        now,
+    )];
+    assert_eq!(bars(&achievements, 20, false), ["█ Now (0 days)"]);
+    assert!(bars(&[], 20, false).is_empty());
+}
+
+#[test]
+fn list_verbose_test() {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let mut moon_landing = Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    moon_landing.tags = vec!["space".to_string(), "history".to_string()];
+    moon_landing.description = Some("One small step".to_string());
+    moon_landing.archived = true;
+    let achievements = [
+        Achievement::new(moon_landing, now),
+        Achievement::new(Day::new("New year", datetime!(2024-01-01 0:00 UTC)), now),
+    ];
+
+    assert_eq!(
+        list_verbose(&achievements, &Words::ENGLISH, DateStyle::Short),
+        "\
+Moon landing: 1969-07-20 20:17:40, Sunday, 54 years, 5 months, 3 weeks, 6 days (19887 days total)
+  tags: space, history
+  notes: One small step
+  archived
+New year: 2024-01-01, Monday, Recently (0 days total)
+"
+    );
+
+    let list = list_verbose(&achievements, &Words::ITALIAN, DateStyle::Long);
+    assert!(list.starts_with("Moon landing: 20 luglio 1969 20:17:40, domenica, 54 "));
+    assert!(list.ends_with("New year: 1 gennaio 2024, lunedì, Di recente (0 giorni in tutto)\n"));
+}
+
+#[test]
+fn upcoming_test() {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let upcoming_day = |label: &str, date| Upcoming::new(Day::new(label, date), now);
+    let days = [
+        upcoming_day("Moved", datetime!(2023-01-01 12:00 UTC)),
+        upcoming_day("Wedding anniversary", datetime!(2019-01-03 12:00 UTC)),
+        upcoming_day("Quit smoking", datetime!(2021-04-18 12:00 UTC)),
+    ];
+
+    assert_eq!(
+        upcoming(&days, &Words::ENGLISH, None),
+        "Moved: 1 year today
+Wedding anniversary: 5 years in 1 day
+Quit smoking: 1000 days in 12 days
+"
+    );
+    assert_eq!(
+        upcoming(&days, &Words::ENGLISH, Some(&ProgressBar::ascii())),
+        "Moved: 1 year today [##########] 100%
+Wedding anniversary: 5 years in 1 day [##########] 99%
+Quit smoking: 1000 days in 12 days [##########] 95%
+"
+    );
+    assert_eq!(upcoming(&[], &Words::ENGLISH, None), "");
+}
+
+#[test]
+fn top_upcoming_test() {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let upcoming_day = |label: &str, date| Upcoming::new(Day::new(label, date), now);
+    let days = [
+        upcoming_day("Moved", datetime!(2023-01-01 12:00 UTC)),
+        upcoming_day("Wedding anniversary", datetime!(2019-01-03 12:00 UTC)),
+        upcoming_day("Quit smoking", datetime!(2021-04-18 12:00 UTC)),
+        upcoming_day("Today", datetime!(2024-01-01 9:00 UTC)),
+    ];
+
+    assert_eq!(
+        top_upcoming(&days, &Words::ENGLISH),
+        "Moved: 1 year now, 1 year today
+Wedding anniversary: 1824 days now, 5 years in 1 day
+Quit smoking: 988 days now, 1000 days in 12 days
+Today: 0 days now, 100 days in 100 days
+"
+    );
+    assert_eq!(top_upcoming(&[], &Words::ENGLISH), "");
+}
+
+#[test]
+fn progress_bar_test() {
+    let bar = ProgressBar::default();
+    assert_eq!(bar.render(0.0), "[░░░░░░░░░░] 0%");
+    assert_eq!(bar.render(0.04), "[░░░░░░░░░░] 4%");
+    assert_eq!(bar.render(0.05), "[█░░░░░░░░░] 5%");
+    assert_eq!(bar.render(0.8), "[████████░░] 80%");
+    assert_eq!(bar.render(0.999), "[██████████] 99%");
+    assert_eq!(bar.render(1.0), "[██████████] 100%");
+    assert_eq!(bar.render(1.5), "[██████████] 100%");
+
+    let bar = ProgressBar {
+        width: 4,
+        filled: '=',
+        empty: ' ',
+    };
+    assert_eq!(bar.render(0.5), "[==  ] 50%");
+    let bar = ProgressBar {
+        width: 0,
+        ..ProgressBar::ascii()
+    };
+    assert_eq!(bar.render(0.5), "[] 50%");
+}
+
+#[test]
+fn render_to_test() {
+    let bar = ProgressBar::default();
+    let render_to = |days| {
+        let interval = Interval::from_days(days);
+        bar.render_to(interval.progress(), interval.next_milestone(), false)
+    };
+    assert_eq!(render_to(0), "[░░░░░░░░░░] 0% to 100 days");
+    assert_eq!(render_to(50), "[█████░░░░░] 50% to 100 days");
+    assert_eq!(render_to(99), "[██████████] 99% to 100 days");
+    assert_eq!(render_to(100), "[██████████] 🎉 today");
+    assert_eq!(render_to(365), "[██████████] 🎉 today");
+
+    let bar = ProgressBar {
+        width: 4,
+        ..ProgressBar::ascii()
+    };
+    assert_eq!(
+        bar.render_to(0.5, Milestone::Years(1), true),
+        "[##..] 50% to 1 year"
+    );
+    assert_eq!(
+        bar.render_to(1.0, Milestone::Years(1), true),
+        "[####] today"
+    );
+}
+
+#[test]
+fn text_progress_test() {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let achievement = |label: &str, date| Achievement::new(Day::new(label, date), now);
+    let mut achievements = vec![
+        achievement("Quit smoking", datetime!(2023-03-08 12:00 UTC)),
+        achievement("Moved", datetime!(2023-01-01 12:00 UTC)),
+        achievement("Run", datetime!(2023-12-29 12:00 UTC)),
+        achievement("Old job", datetime!(2020-01-01 12:00 UTC)),
+    ];
+    achievements[3].day.archived = true;
+
+    let options = TextOptions {
+        align: true,
+        messages: false,
+        progress: Some(ProgressBar::default()),
+        ..Default::default()
+    };
+    assert_eq!(
+        text(&achievements, &options),
+        "\
+Quit smoking: 299 days  ⭐⭐⭐⭐⭐⭐⭐⭐⭐ [████████░░] 75% to 1 year
+Moved:        1 year    🌟                 [██████████] 🎉 today
+Run:          3 days    ☆☆☆                [░░░░░░░░░░] 3% to 100 days
+Old job:      1461 days 🌟🌟🌟🌟
+"
+    );
+
+    let options = TextOptions {
+        progress: Some(ProgressBar::ascii()),
+        ..TextOptions::plain()
+    };
+    assert_eq!(
+        text(&achievements, &options),
+        "\
+Quit smoking: 299 days ********* [########..] 75% to 1 year
+Moved: 1 year @ [##########] today
+Run: 3 days ... [..........] 3% to 100 days
+Old job: 1461 days @@@@
+"
+    );
+}
+
+#[test]
+fn stats_test() {
+    use time::macros::datetime;
+
+    use crate::config::Config;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let mut config = Config::default();
+    config.set_day("Moon landing", datetime!(2023-12-18 12:00 UTC));
+    config.set_day("Today", datetime!(2024-01-01 11:00 UTC));
+
+    assert_eq!(
+        stats(&config.stats(now), &Words::ENGLISH),
+        "Entries: 2
+Oldest:  Moon landing, 2 weeks ★★ (since 2023-12-18)
+Newest:  Today, Recently (since 2024-01-01)
+Total:   14 days
+Average: 7 days
+Tiers:   💎 0 · 🌟 0 · ⭐ 0 · ★ 1 · ☆ 1
+"
+    );
+}
+
+#[test]
+fn doctor_test() {
+    let check = |name, status, message: &str| Check {
+        name,
+        status,
+        message: message.to_string(),
+    };
+
+    assert_eq!(doctor(&[]), "");
+    assert_eq!(
+        doctor(&[
+            check("Config file", Status::Ok, "config.json exists"),
+            check("Permissions", Status::Warning, "644"),
+            check("JSON", Status::Error, "invalid"),
+        ]),
+        "✓ Config file: config.json exists
+! Permissions: 644
+✗ JSON: invalid
+"
+    );
+}
+
+#[test]
+fn milestones_reached_test() {
+    use time::macros::datetime;
+
+    use crate::config::Config;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let mut config = Config::default();
+    config.set_day("Wedding", datetime!(2019-01-02 12:00 UTC));
+    config.set_day("Moved", datetime!(2023-12-18 12:00 UTC));
+    config.set_day("Started running", datetime!(2023-12-17 12:00 UTC));
+    config.set_day("Today", datetime!(2024-01-01 11:00 UTC));
+
+    assert_eq!(
+        milestones_reached(&config.achievements(now)),
+        "🎉 Milestone reached: Wedding — 5 years 🌟🌟🌟🌟🌟
+🎉 Milestone reached: Moved — 2 weeks ★★
+"
+    );
+    assert_eq!(milestones_reached(&[]), "");
+}
+
+#[test]
+fn comparison_test() {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 12:00 UTC);
+    let moon_landing = Day::new("Moon landing", datetime!(2023-11-20 12:00 UTC));
+    let moved = Day::new("Moved", datetime!(2023-12-18 12:00 UTC));
+
+    assert_eq!(
+        comparison(&Comparison::new(moved.clone(), moon_landing.clone(), now)),
+        "Moon landing: 2023-11-20, 6 weeks ⭐
+Moved:        2023-12-18, 2 weeks ★★
+Moon landing was 4 weeks before Moved
+"
+    );
+
+    let same_day = Day::new("Same day", moved.date);
+    assert_eq!(
+        comparison(&Comparison::new(moved, same_day, now)),
+        "Moved:    2023-12-18, 2 weeks ★★
+Same day: 2023-12-18, 2 weeks ★★
+Moved and Same day are on the same day
+"
+    );
+}
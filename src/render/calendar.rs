@@ -0,0 +1,346 @@
+//! Year calendar of the anniversaries, for `achievements calendar`
+//!
+//! The 12 months are laid out 3 per row, weeks starting on Monday. The
+//! anniversary of a day is marked with its icon (or a letter, and always
+//! a letter in ASCII), a `+` when several days share it, and a legend
+//! tells which mark is which day.
+//!
+//! ```text
+//!       January                February                March
+//! Mo Tu We Th Fr Sa Su   Mo Tu We Th Fr Sa Su   Mo Tu We Th Fr Sa Su
+//!        1  2  3  4  5                   1  2                   1  2
+//!  6  7  8  9 10 11 12    3  4  5  6  7  8  9    3  4  5  6  7  8  9
+//! 13 14 15 16 17 18 19   10 11 12 13 14 15 16   10 11 12 13 14 15 16
+//! 20 21 22 23 24 25 26   17 18 19 20 21 22 23   17 18 19 20 21 22 23
+//! 27 28 29 30 31         24 25 26 27  A         24 25 26 27 28 29 30
+//!                                               31
+//! ...
+//!
+//! 🚀 Moon landing (July 20)
+//!  A Leap day (February 29)
+//!  B Moved (July 20)
+//!  + several days
+//! ```
+
+use time::{util::days_in_year_month, Date, Month};
+use unicode_width::UnicodeWidthStr;
+
+use super::ascii_only;
+use crate::{config::Day, ics::anniversary_in};
+
+/// Months per row of the calendar
+const MONTHS_PER_ROW: usize = 3;
+/// Width of a month: 7 cells of 2 columns and the spaces between them
+const MONTH_WIDTH: usize = 20;
+/// Weeks a month spans at most, shorter months are padded to line up
+const MAX_WEEKS: usize = 6;
+const WEEKDAYS: &str = "Mo Tu We Th Fr Sa Su";
+/// Marks of the days without an icon (or all of them in ASCII), in order
+const LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+/// Mark of the days after the letters run out
+const OTHER_MARK: &str = "#";
+/// Mark of the anniversaries of several days
+const SEVERAL_MARK: &str = "+";
+
+/// A cell of the grid of a month
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cell {
+    /// Before the first or after the last day of the month
+    Empty,
+    /// A day of the month, with the indexes of the days whose
+    /// anniversary it is
+    Day { day: u8, days: Vec<usize> },
+}
+
+/// Weeks of the month, Monday first, with the anniversaries of the days
+///
+/// The anniversary of a day on February 29th is on February 28th in
+/// years that are not leap years. Days after the year have no
+/// anniversary in it.
+pub fn month(days: &[Day], year: i32, month: Month) -> Vec<[Cell; 7]> {
+    let first = Date::from_calendar_date(year, month, 1).expect("Invalid year");
+    let offset = usize::from(first.weekday().number_days_from_monday());
+    let length = days_in_year_month(year, month);
+
+    let mut cells = vec![Cell::Empty; offset];
+    cells.extend((1..=length).map(|day| Cell::Day {
+        day,
+        days: Vec::new(),
+    }));
+    cells.resize(cells.len().div_ceil(7) * 7, Cell::Empty);
+
+    for (i, day) in days.iter().enumerate() {
+        let date = day.date.date();
+        if date.year() > year {
+            continue;
+        }
+        let anniversary = anniversary_in(date, year);
+        if anniversary.month() == month {
+            if let Cell::Day { days, .. } = &mut cells[offset + usize::from(anniversary.day()) - 1]
+            {
+                days.push(i);
+            }
+        }
+    }
+
+    cells
+        .chunks(7)
+        .map(|week| week.to_vec().try_into().expect("Weeks have 7 days"))
+        .collect()
+}
+
+/// Marks of the days, in the same order: their icons when they fit in a
+/// cell, letters otherwise or when `ascii` is `true`
+pub fn marks(days: &[Day], ascii: bool) -> Vec<String> {
+    let mut letters = LETTERS.chars();
+
+    days.iter()
+        .map(|day| match &day.icon {
+            Some(icon) if !ascii && (1..=2).contains(&icon.trim().width()) => {
+                icon.trim().to_string()
+            }
+            _ => letters.next().map_or(OTHER_MARK.to_string(), String::from),
+        })
+        .collect()
+}
+
+/// Lines of the calendar of the year with the anniversaries of the days,
+/// followed by the legend
+///
+/// With `ascii` the marks are letters and the labels ASCII only, with
+/// `color` the anniversaries are also in reverse video.
+pub fn calendar(days: &[Day], year: i32, ascii: bool, color: bool) -> Vec<String> {
+    let marks = marks(days, ascii);
+    let total_width = MONTHS_PER_ROW * MONTH_WIDTH + (MONTHS_PER_ROW - 1) * 3;
+    let mut lines = vec![format!("{year:^total_width$}").trim_end().to_string()];
+    let mut several = false;
+
+    let months: Vec<Month> = (1..=12)
+        .map(|number| Month::try_from(number).expect("Invalid month"))
+        .collect();
+    for row in months.chunks(MONTHS_PER_ROW) {
+        let mut blocks: Vec<Vec<String>> = Vec::new();
+        for &month_of_row in row {
+            let mut block = vec![
+                format!("{:^MONTH_WIDTH$}", month_of_row.to_string()),
+                WEEKDAYS.to_string(),
+            ];
+            for week in month(days, year, month_of_row) {
+                let cells: Vec<String> = week
+                    .iter()
+                    .map(|cell| match cell {
+                        Cell::Empty => "  ".to_string(),
+                        Cell::Day { day, days } if days.is_empty() => format!("{day:>2}"),
+                        Cell::Day { days, .. } => {
+                            several |= days.len() > 1;
+                            let mark = match days.as_slice() {
+                                [i] => marks[*i].as_str(),
+                                _ => SEVERAL_MARK,
+                            };
+                            let mark = pad(mark);
+                            if color {
+                                format!("\x1b[7m{mark}\x1b[0m")
+                            } else {
+                                mark
+                            }
+                        }
+                    })
+                    .collect();
+                block.push(cells.join(" "));
+            }
+            block.resize(MAX_WEEKS + 2, " ".repeat(MONTH_WIDTH));
+            blocks.push(block);
+        }
+
+        lines.push(String::new());
+        for i in 0..MAX_WEEKS + 2 {
+            let line: Vec<&str> = blocks.iter().map(|block| block[i].as_str()).collect();
+            lines.push(line.join("   ").trim_end().to_string());
+        }
+    }
+
+    lines.push(String::new());
+    for (day, mark) in days.iter().zip(&marks) {
+        if day.date.year() > year {
+            continue;
+        }
+        let label = if ascii {
+            ascii_only(&day.label)
+        } else {
+            day.label.clone()
+        };
+        let date = day.date.date();
+        lines.push(format!(
+            "{} {label} ({} {})",
+            pad(mark),
+            date.month(),
+            date.day()
+        ));
+    }
+    if several {
+        lines.push(format!("{} several days", pad(SEVERAL_MARK)));
+    }
+
+    lines
+}
+
+/// Right-aligns the mark in the 2 columns of a cell
+fn pad(mark: &str) -> String {
+    format!("{}{mark}", " ".repeat(2usize.saturating_sub(mark.width())))
+}
+
+#[test]
+fn month_test() {
+    use time::macros::datetime;
+
+    let days = [
+        Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC)),
+        Day::new("Moved", datetime!(2023-07-20 12:00 UTC)),
+        Day::new("Run", datetime!(2023-07-01 12:00 UTC)),
+        Day::new("Future", datetime!(2030-07-04 12:00 UTC)),
+    ];
+    let day = |day: u8, days: &[usize]| Cell::Day {
+        day,
+        days: days.to_vec(),
+    };
+
+    // July 2024 starts on a Monday and has 5 weeks
+    let weeks = month(&days, 2024, Month::July);
+    assert_eq!(weeks.len(), 5);
+    assert_eq!(weeks[0][0], day(1, &[2]));
+    assert_eq!(weeks[0][3], day(4, &[]));
+    // Two days sharing a date
+    assert_eq!(weeks[2][5], day(20, &[0, 1]));
+    assert_eq!(weeks[4][2], day(31, &[]));
+    assert_eq!(weeks[4][3], Cell::Empty);
+
+    // June 2024 starts on a Saturday
+    let weeks = month(&days, 2024, Month::June);
+    assert_eq!(weeks.len(), 5);
+    assert_eq!(weeks[0][4], Cell::Empty);
+    assert_eq!(weeks[0][5], day(1, &[]));
+    assert_eq!(weeks[4][6], day(30, &[]));
+}
+
+#[test]
+fn month_leap_test() {
+    use time::macros::datetime;
+
+    let days = [
+        Day::new("Leap day", datetime!(2020-02-29 0:00 UTC)),
+        Day::new("Last day", datetime!(2021-02-28 0:00 UTC)),
+    ];
+    let last_day = |weeks: &[[Cell; 7]]| {
+        weeks
+            .iter()
+            .flatten()
+            .rfind(|cell| **cell != Cell::Empty)
+            .cloned()
+    };
+
+    let weeks = month(&days, 2024, Month::February);
+    assert_eq!(
+        last_day(&weeks),
+        Some(Cell::Day {
+            day: 29,
+            days: vec![0]
+        })
+    );
+    assert_eq!(
+        weeks[4][2],
+        Cell::Day {
+            day: 28,
+            days: vec![1]
+        }
+    );
+
+    // On February 28th when it's not a leap year
+    let weeks = month(&days, 2025, Month::February);
+    assert_eq!(weeks.len(), 5);
+    assert_eq!(
+        last_day(&weeks),
+        Some(Cell::Day {
+            day: 28,
+            days: vec![0, 1]
+        })
+    );
+}
+
+#[test]
+fn marks_test() {
+    use time::macros::datetime;
+
+    let date = datetime!(2020-01-01 0:00 UTC);
+    let days = [
+        Day {
+            icon: Some("🚀".to_string()),
+            ..Day::new("Moon landing", date)
+        },
+        Day::new("Moved", date),
+        Day {
+            icon: Some("too wide".to_string()),
+            ..Day::new("Run", date)
+        },
+    ];
+    assert_eq!(marks(&days, false), ["🚀", "A", "B"]);
+    assert_eq!(marks(&days, true), ["A", "B", "C"]);
+
+    let many: Vec<Day> = (0..53).map(|i| Day::new(i.to_string(), date)).collect();
+    let marks = marks(&many, true);
+    assert_eq!(marks[51], "z");
+    assert_eq!(marks[52], "#");
+}
+
+#[test]
+fn calendar_test() {
+    use time::macros::datetime;
+
+    let days = [
+        Day {
+            icon: Some("🚀".to_string()),
+            ..Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC))
+        },
+        Day::new("Moved", datetime!(2023-07-20 12:00 UTC)),
+        Day::new("Café", datetime!(2023-01-02 12:00 UTC)),
+    ];
+
+    let lines = calendar(&days, 2024, false, false);
+    assert_eq!(lines[0], "                               2024");
+    assert_eq!(
+        lines[2],
+        "      January                February                March"
+    );
+    assert_eq!(
+        lines[3],
+        "Mo Tu We Th Fr Sa Su   Mo Tu We Th Fr Sa Su   Mo Tu We Th Fr Sa Su"
+    );
+    assert_eq!(
+        lines[4],
+        " 1  B  3  4  5  6  7             1  2  3  4                1  2  3"
+    );
+    assert_eq!(
+        lines[8],
+        "29 30 31               26 27 28 29            25 26 27 28 29 30 31"
+    );
+    // Padded to 6 weeks
+    assert_eq!(lines[9], "");
+    assert_eq!(lines.len(), 1 + 4 * 9 + 1 + 4);
+    assert_eq!(
+        lines[lines.len() - 4..],
+        [
+            "🚀 Moon landing (July 20)",
+            " A Moved (July 20)",
+            " B Café (January 2)",
+            " + several days",
+        ]
+    );
+
+    let lines = calendar(&days, 2024, true, true);
+    assert!(lines[4].starts_with(" 1 \x1b[7m C\x1b[0m  3"));
+    assert_eq!(lines[lines.len() - 2], " C Caf? (January 2)");
+
+    // Nothing to mark before the days
+    let lines = calendar(&days, 1960, false, false);
+    assert!(lines.iter().all(|line| !line.contains('🚀')));
+    assert_eq!(lines.last().unwrap(), "");
+}
@@ -0,0 +1,127 @@
+//! SVG badges in the style of shields.io, e.g. for a wiki page
+//!
+//! A badge has two segments, the label on a grey background on the left
+//! and the value on a colored background on the right. There's no font to
+//! measure the text with, so its width is estimated from the number of
+//! characters.
+
+use crate::Interval;
+
+/// Estimated width of a character, in pixels, for 11px Verdana
+const CHAR_WIDTH: usize = 7;
+/// Horizontal padding of each segment, in pixels
+const PADDING: usize = 10;
+
+/// Renders a two-segment badge, `color` is a CSS color for the value
+/// segment, e.g. `"#4c1"`
+///
+/// Example:
+///
+/// ```
+/// use achievements::render::svg::render_badge;
+///
+/// let svg = render_badge("Days since last incident", "412", "#4c1");
+/// assert!(svg.starts_with("<svg "));
+/// assert!(svg.contains(">Days since last incident</text>"));
+/// assert!(svg.contains(">412</text>"));
+/// ```
+pub fn render_badge(label: &str, value: &str, color: &str) -> String {
+    let label_width = text_width(label);
+    let value_width = text_width(value);
+    let width = label_width + value_width;
+    let (label, value, color) = (escape_xml(label), escape_xml(value), escape_xml(color));
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {value}">
+<title>{label}: {value}</title>
+<linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>
+<clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+<g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{value_width}" height="20" fill="{color}"/><rect width="{width}" height="20" fill="url(#s)"/></g>
+<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+<text x="{label_x}" y="14">{label}</text>
+<text x="{value_x}" y="14">{value}</text>
+</g>
+</svg>
+"##,
+        label_x = label_width / 2,
+        value_x = label_width + value_width / 2,
+    )
+}
+
+/// Color of the value segment for the interval, by unit: grey for days,
+/// then green, blue, orange and red for decades
+pub fn tier_color(interval: &Interval) -> &'static str {
+    if interval.decade_count() > 0 {
+        "#e05d44"
+    } else if interval.year_count() > 0 {
+        "#fe7d37"
+    } else if interval.month_count() > 0 {
+        "#007ec6"
+    } else if interval.week_count() > 0 {
+        "#4c1"
+    } else {
+        "#9f9f9f"
+    }
+}
+
+/// Estimated width of the text with the padding, in pixels
+fn text_width(text: &str) -> usize {
+    text.chars().count() * CHAR_WIDTH + PADDING
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Checks the tags of the SVG are balanced, a rough well-formedness test
+#[cfg(test)]
+fn assert_balanced(svg: &str) {
+    let mut open: Vec<&str> = Vec::new();
+    for tag in svg.split('<').skip(1) {
+        let (tag, _) = tag.split_once('>').expect("Unclosed tag");
+        let name = tag
+            .trim_start_matches('/')
+            .split([' ', '/'])
+            .next()
+            .expect("Tag name");
+        if let Some(closed) = tag.strip_prefix('/') {
+            assert_eq!(open.pop(), Some(closed), "Mismatched </{closed}>");
+        } else if !tag.ends_with('/') {
+            open.push(name);
+        }
+    }
+    assert!(open.is_empty(), "Unclosed tags {open:?}");
+}
+
+#[test]
+fn render_badge_test() {
+    let svg = render_badge("Days since last incident", "412", "#4c1");
+    assert_balanced(&svg);
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"209\" "));
+    assert!(svg.contains("<text x=\"89\" y=\"14\">Days since last incident</text>"));
+    assert!(svg.contains("<text x=\"193\" y=\"14\">412</text>"));
+    assert!(svg.contains("fill=\"#4c1\""));
+    assert!(svg.contains("<title>Days since last incident: 412</title>"));
+
+    let svg = render_badge("Tom & \"Jerry\" <3", "1 year", "#fe7d37");
+    assert_balanced(&svg);
+    assert!(svg.contains(">Tom &amp; &quot;Jerry&quot; &lt;3</text>"));
+    assert!(svg.contains(">1 year</text>"));
+}
+
+#[test]
+fn tier_color_test() {
+    use crate::{MONTH, WEEK, YEAR};
+
+    let color = |days| tier_color(&Interval::from_days(days));
+    assert_eq!(color(0), "#9f9f9f");
+    assert_eq!(color(WEEK - 1), "#9f9f9f");
+    assert_eq!(color(WEEK), "#4c1");
+    assert_eq!(color(MONTH + 1), "#007ec6");
+    assert_eq!(color(412), "#fe7d37");
+    assert_eq!(color(10 * YEAR), "#e05d44");
+}
@@ -0,0 +1,426 @@
+//! Custom output templates
+//!
+//! A template is a string with placeholders in braces substituted with the
+//! values of each achievement, for example `"{label} — {days}d {badges}"`.
+//! Use `{{` and `}}` for literal braces.
+//!
+//! Like in Python format strings, a placeholder can be padded to a width
+//! with an optional fill character and alignment, e.g. `{label:<20}`,
+//! `{days:>6}` or `{words:*^12}`. Text is left-aligned and `{days}`
+//! right-aligned by default, longer values are never truncated.
+
+use std::{fmt::Display, str::FromStr};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::{locale::IntervalLocale, Achievement, IntervalOptions};
+
+use super::format_day;
+
+/// Placeholders available in templates
+pub const PLACEHOLDERS: [&str; 7] = [
+    "label",
+    "date",
+    "days",
+    "words",
+    "words_short",
+    "badges",
+    "weekday",
+];
+
+/// A parsed template
+///
+/// Example:
+///
+/// ```
+/// use achievements::{config::Day, render::template::Template, Achievement};
+/// use time::macros::datetime;
+///
+/// let day = Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+/// let achievement = Achievement::new(day, datetime!(1969-08-03 20:17:40 UTC));
+///
+/// let template: Template = "{label} — {days}d {{{words}}}".parse().unwrap();
+/// assert_eq!("Moon landing — 14d {2 weeks}", template.render(&achievement));
+///
+/// let template: Template = "{label:.<15}{days:>4}d".parse().unwrap();
+/// assert_eq!("Moon landing...  14d", template.render(&achievement));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Placeholder(Placeholder, Option<Spec>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Label,
+    Date,
+    Days,
+    Words,
+    WordsShort,
+    Badges,
+    Weekday,
+}
+
+/// Fill, alignment and width of a placeholder, e.g. `*^12`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Spec {
+    fill: char,
+    align: Option<Align>,
+    width: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+impl Align {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '<' => Some(Align::Left),
+            '>' => Some(Align::Right),
+            '^' => Some(Align::Center),
+            _ => None,
+        }
+    }
+}
+
+impl Spec {
+    /// Parses `[[fill]align][width]`, `None` if it's not valid
+    fn parse(spec: &str) -> Option<Self> {
+        let mut chars = spec.chars();
+        let (fill, align, width) = match (chars.next(), chars.next()) {
+            (Some(fill), Some(align)) if Align::from_char(align).is_some() => {
+                (fill, Align::from_char(align), chars.as_str())
+            }
+            (Some(align), _) if Align::from_char(align).is_some() => {
+                (' ', Align::from_char(align), &spec[align.len_utf8()..])
+            }
+            _ => (' ', None, spec),
+        };
+
+        let width = match width {
+            "" => 0,
+            width if width.bytes().all(|b| b.is_ascii_digit()) => width.parse().ok()?,
+            _ => return None,
+        };
+        Some(Spec { fill, align, width })
+    }
+
+    /// Pads the value to the width, aligned with `default` unless the spec
+    /// has an alignment
+    fn pad(&self, value: String, default: Align) -> String {
+        let padding = self.width.saturating_sub(value.width());
+        if padding == 0 {
+            return value;
+        }
+
+        let (before, after) = match self.align.unwrap_or(default) {
+            Align::Left => (0, padding),
+            Align::Right => (padding, 0),
+            Align::Center => (padding / 2, padding - padding / 2),
+        };
+        let fill = self.fill.to_string();
+        format!("{}{value}{}", fill.repeat(before), fill.repeat(after))
+    }
+}
+
+impl FromStr for Placeholder {
+    type Err = TemplateError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "label" => Ok(Placeholder::Label),
+            "date" => Ok(Placeholder::Date),
+            "days" => Ok(Placeholder::Days),
+            "words" => Ok(Placeholder::Words),
+            "words_short" => Ok(Placeholder::WordsShort),
+            "badges" => Ok(Placeholder::Badges),
+            "weekday" => Ok(Placeholder::Weekday),
+            _ => Err(TemplateError::UnknownPlaceholder(name.to_string())),
+        }
+    }
+}
+
+/// Errors parsing a template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// A `{placeholder}` not in `PLACEHOLDERS`
+    UnknownPlaceholder(String),
+    /// A `{` without the closing `}`
+    UnclosedPlaceholder,
+    /// A `}` without the opening `{` (use `}}` for a literal `}`)
+    UnmatchedBrace,
+    /// A `{placeholder:spec}` whose spec isn't `[[fill]align][width]`
+    InvalidSpec(String),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::UnknownPlaceholder(name) => write!(
+                f,
+                "unknown placeholder '{{{name}}}', valid placeholders are: {}",
+                PLACEHOLDERS.map(|p| format!("{{{p}}}")).join(", ")
+            ),
+            TemplateError::UnclosedPlaceholder => {
+                write!(
+                    f,
+                    "unclosed '{{' in template, use '{{{{' for a literal '{{'"
+                )
+            }
+            TemplateError::UnmatchedBrace => {
+                write!(
+                    f,
+                    "unmatched '}}' in template, use '}}}}' for a literal '}}'"
+                )
+            }
+            TemplateError::InvalidSpec(placeholder) => write!(
+                f,
+                "invalid format in '{{{placeholder}}}', expected [[fill]align][width] \
+                 with align one of '<', '>' or '^', e.g. {{label:<20}} or {{days:>6}}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    /// Parses a template, returning an error for unknown placeholders,
+    /// invalid specs or unbalanced braces
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(TemplateError::UnclosedPlaceholder),
+                        }
+                    }
+
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    let (placeholder, spec) = match name.split_once(':') {
+                        Some((placeholder, "")) => (placeholder.trim().parse()?, None),
+                        Some((placeholder, spec)) => {
+                            let parsed = placeholder.trim().parse()?;
+                            let spec = Spec::parse(spec)
+                                .ok_or_else(|| TemplateError::InvalidSpec(name.clone()))?;
+                            (parsed, Some(spec))
+                        }
+                        None => (name.trim().parse()?, None),
+                    };
+                    parts.push(Part::Placeholder(placeholder, spec));
+                }
+                '}' => return Err(TemplateError::UnmatchedBrace),
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+
+        Ok(Template { parts })
+    }
+}
+
+impl Template {
+    /// Renders the template for the given achievement
+    ///
+    /// `{badges}` are in the style of `IntervalOptions::current()`.
+    pub fn render(&self, achievement: &Achievement) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Part::Literal(literal) => literal.clone(),
+                Part::Placeholder(placeholder, spec) => {
+                    let value = Self::value(*placeholder, achievement);
+                    let default = match placeholder {
+                        Placeholder::Days => Align::Right,
+                        _ => Align::Left,
+                    };
+                    match spec {
+                        Some(spec) => spec.pad(value, default),
+                        None => value,
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn value(placeholder: Placeholder, achievement: &Achievement) -> String {
+        let Achievement { day, interval } = achievement;
+
+        match placeholder {
+            Placeholder::Label => day.label.clone(),
+            Placeholder::Date => format_day(day.date),
+            Placeholder::Days => interval.as_days().to_string(),
+            Placeholder::Words => {
+                IntervalOptions::current().words(interval, IntervalLocale::current())
+            }
+            Placeholder::WordsShort => interval.to_words_short(),
+            Placeholder::Badges => interval.badges_in_style(IntervalOptions::current().badge_style),
+            Placeholder::Weekday => day.date.weekday().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+fn test_achievement() -> Achievement {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    Achievement::new(
+        Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC)),
+        datetime!(1969-08-19 20:17:40 UTC),
+    )
+}
+
+#[test]
+fn substitution_test() {
+    let achievement = test_achievement();
+    let render = |template: &str| -> String {
+        let template: Template = template.parse().expect("Valid template");
+        template.render(&achievement)
+    };
+
+    assert_eq!(
+        render("{label}|{date}|{days}|{words}|{words_short}|{badges}|{weekday}"),
+        "Moon landing|1969-07-20|30|1 month|1mo|⭐|Sunday"
+    );
+    assert_eq!(
+        render("{label} — {days}d {badges}"),
+        "Moon landing — 30d ⭐"
+    );
+    assert_eq!(render("{ label }"), "Moon landing");
+    assert_eq!(render("no placeholders"), "no placeholders");
+    assert_eq!(render(""), "");
+}
+
+#[test]
+fn words_options_test() {
+    use time::macros::datetime;
+
+    use crate::{
+        config::Day,
+        locale::{English, Italian},
+    };
+
+    let template: Template = "{words}".parse().expect("Valid template");
+    let year = Achievement::new(
+        Day::new("Moon landing", datetime!(1969-07-20 20:17:40 UTC)),
+        datetime!(1970-07-20 20:17:40 UTC),
+    );
+    assert_eq!(template.render(&year), "1 year, happy anniversary!");
+
+    // Like the words of the text output
+    IntervalOptions::set_default(IntervalOptions {
+        messages: false,
+        ..IntervalOptions::default()
+    });
+    assert_eq!(template.render(&year), "1 year");
+    IntervalLocale::set_current(Box::new(Italian));
+    assert_eq!(template.render(&year), "1 anno");
+
+    IntervalOptions::reset_default();
+    assert_eq!(template.render(&year), "1 anno, buon anniversario!");
+    IntervalLocale::set_current(Box::new(English));
+}
+
+#[test]
+fn brace_escaping_test() {
+    let achievement = test_achievement();
+    let render = |template: &str| -> String {
+        let template: Template = template.parse().expect("Valid template");
+        template.render(&achievement)
+    };
+
+    assert_eq!(render("{{label}}"), "{label}");
+    assert_eq!(render("{{{label}}}"), "{Moon landing}");
+    assert_eq!(render("}}{{"), "}{");
+
+    assert_eq!(
+        "{label".parse::<Template>(),
+        Err(TemplateError::UnclosedPlaceholder)
+    );
+    assert_eq!(
+        "label}".parse::<Template>(),
+        Err(TemplateError::UnmatchedBrace)
+    );
+}
+
+#[test]
+fn unknown_placeholder_test() {
+    let error = "{label} {nope}"
+        .parse::<Template>()
+        .expect_err("Unknown placeholder");
+    assert_eq!(error, TemplateError::UnknownPlaceholder("nope".to_string()));
+    assert_eq!(
+        error.to_string(),
+        "unknown placeholder '{nope}', valid placeholders are: \
+         {label}, {date}, {days}, {words}, {words_short}, {badges}, {weekday}"
+    );
+}
+
+#[test]
+fn spec_test() {
+    let achievement = test_achievement();
+    let render = |template: &str| -> String {
+        let template: Template = template.parse().expect("Valid template");
+        template.render(&achievement)
+    };
+
+    assert_eq!(
+        render("{label:<15}|{days:>6} days|{badges}"),
+        "Moon landing   |    30 days|⭐"
+    );
+    assert_eq!(render("{label:15}|{days:6}|"), "Moon landing   |    30|");
+    assert_eq!(render("{days:<4}|{words:*^11}|"), "30  |**1 month**|");
+    assert_eq!(render("{words:-^10}"), "-1 month--");
+    assert_eq!(render("{label:>4}"), "Moon landing");
+    assert_eq!(render("{ label :}"), "Moon landing");
+    assert_eq!(render("{badges:>3}"), " ⭐");
+    assert_eq!(render("{days::>4}"), "::30");
+
+    for template in ["{label:x}", "{label:<-5}", "{label:=20}", "{days:>6.2}"] {
+        let error = template.parse::<Template>().expect_err(template);
+        assert!(matches!(error, TemplateError::InvalidSpec(_)), "{template}");
+    }
+    assert_eq!(
+        "{label:x}".parse::<Template>(),
+        Err(TemplateError::InvalidSpec("label:x".to_string()))
+    );
+    assert_eq!(
+        "{nope:<3}".parse::<Template>(),
+        Err(TemplateError::UnknownPlaceholder("nope".to_string()))
+    );
+}
@@ -0,0 +1,255 @@
+//! ASCII timeline of the days, for `achievements timeline`
+//!
+//! The timeline goes from the oldest day to now over a fixed number of
+//! columns. Each day is a `*` on the axis with its label above it, labels
+//! too close to fit on the same row are stacked on rows further up. Below
+//! the axis a few years are marked with a `+` and labelled.
+//!
+//! ```text
+//!                           * Windows 95
+//! * Moon landing      * Berlin Wall Fall   Pandemic *
+//! *---------+---------*-----*---+---------+---------*
+//!           1980      1990      2000      2010   2020
+//! ```
+
+use time::{Date, Month, OffsetDateTime};
+use unicode_width::UnicodeWidthStr;
+
+use super::truncate;
+use crate::config::Day;
+
+/// Steps in years between two ticks, the smallest one fitting is used
+const TICK_STEPS: [i32; 10] = [1, 2, 5, 10, 20, 25, 50, 100, 200, 500];
+/// Columns per tick at least, room for a year and a space
+const TICK_SPACING: usize = 8;
+
+/// Column of `date` on a timeline `width` columns wide from `start` to
+/// `end`, rounded to the nearest one
+///
+/// Dates outside of the timeline are on its first or last column, as are
+/// all the dates when it has no length.
+pub fn column(
+    date: OffsetDateTime,
+    start: OffsetDateTime,
+    end: OffsetDateTime,
+    width: usize,
+) -> usize {
+    let last = width.saturating_sub(1);
+    let span = (end - start).as_seconds_f64();
+    if span <= 0.0 {
+        return 0;
+    }
+
+    let position = (date - start).as_seconds_f64() / span;
+    (position.clamp(0.0, 1.0) * last as f64).round() as usize
+}
+
+/// Years marked on the axis, as many as fit, on multiples of a round step
+/// (1, 2, 5, 10... years)
+pub fn ticks(start: OffsetDateTime, end: OffsetDateTime, width: usize) -> Vec<i32> {
+    let max_ticks = (width / TICK_SPACING).max(1);
+    let years = |step: i32| {
+        let first = (start.year() / step + 1) * step;
+        (first..=end.year())
+            .step_by(step as usize)
+            .collect::<Vec<i32>>()
+    };
+
+    TICK_STEPS
+        .into_iter()
+        .map(years)
+        .find(|years| years.len() <= max_ticks)
+        .unwrap_or_default()
+}
+
+/// Lines of the timeline of the days, `width` columns wide, from the
+/// oldest day to `now`
+///
+/// The label rows come first, the lowest one closest to the axis, then
+/// the axis and its years. Labels are truncated to the width, and drawn
+/// on the left of their day when they would go past the end. No lines
+/// without days or with no width.
+pub fn timeline(days: &[Day], now: OffsetDateTime, width: usize) -> Vec<String> {
+    let Some(start) = days.iter().map(|day| day.date).min() else {
+        return Vec::new();
+    };
+    if width == 0 {
+        return Vec::new();
+    }
+    let end = now.max(start);
+    let mut days: Vec<&Day> = days.iter().collect();
+    days.sort_by_key(|day| day.date);
+
+    // Segments of text (starting column, text) of each row of labels
+    let mut rows: Vec<Vec<(usize, String)>> = Vec::new();
+    let mut axis = vec!['-'; width];
+    let years = ticks(start, end, width);
+    let tick_columns: Vec<usize> = years
+        .iter()
+        .map(|year| column(year_start(*year, start), start, end, width))
+        .collect();
+    for &tick in tick_columns.iter() {
+        axis[tick] = '+';
+    }
+
+    for day in days {
+        let column = column(day.date, start, end, width);
+        axis[column] = '*';
+
+        let label = truncate(&day.label, width.saturating_sub(2), false);
+        let text = format!("* {label}");
+        let (start, text) = if column + text.width() <= width {
+            (column, text)
+        } else {
+            let text = format!("{label} *");
+            ((column + 1).saturating_sub(text.width()), text)
+        };
+        let end = start + text.width();
+
+        let free = |row: &Vec<(usize, String)>| {
+            row.iter()
+                .all(|(other, text)| end < *other || other + text.width() < start)
+        };
+        match rows.iter_mut().find(|row| free(row)) {
+            Some(row) => row.push((start, text)),
+            None => rows.push(vec![(start, text)]),
+        }
+    }
+
+    let mut lines: Vec<String> = rows.iter().rev().map(|row| draw_row(row)).collect();
+    lines.push(axis.into_iter().collect());
+    let year_labels: Vec<(usize, String)> = tick_columns
+        .into_iter()
+        .zip(years)
+        .map(|(column, year)| (column, year.to_string()))
+        .scan(0, |free_from, (column, year)| {
+            let column = column.min(width.saturating_sub(year.len()));
+            if column < *free_from {
+                return Some(None);
+            }
+            *free_from = column + year.len() + 1;
+            Some(Some((column, year)))
+        })
+        .flatten()
+        .collect();
+    if !year_labels.is_empty() {
+        lines.push(draw_row(&year_labels));
+    }
+
+    lines
+}
+
+/// First instant of the year, in the offset of `like`
+fn year_start(year: i32, like: OffsetDateTime) -> OffsetDateTime {
+    let date = Date::from_calendar_date(year, Month::January, 1).expect("Invalid year");
+    date.midnight().assume_offset(like.offset())
+}
+
+/// Draws the segments of a row, sorted by column
+fn draw_row(segments: &[(usize, String)]) -> String {
+    let mut segments: Vec<&(usize, String)> = segments.iter().collect();
+    segments.sort_by_key(|(column, _)| *column);
+
+    let mut line = String::new();
+    for (column, text) in segments {
+        let padding = column.saturating_sub(line.width());
+        line.push_str(&" ".repeat(padding));
+        line.push_str(text);
+    }
+    line
+}
+
+#[test]
+fn column_test() {
+    use time::macros::datetime;
+
+    let start = datetime!(2000-01-01 0:00 UTC);
+    let end = datetime!(2010-01-01 0:00 UTC);
+    assert_eq!(column(start, start, end, 11), 0);
+    assert_eq!(column(end, start, end, 11), 10);
+    assert_eq!(column(datetime!(2005-01-01 0:00 UTC), start, end, 11), 5);
+    assert_eq!(column(datetime!(2001-01-01 0:00 UTC), start, end, 11), 1);
+    // Outside of the timeline
+    assert_eq!(column(datetime!(1990-01-01 0:00 UTC), start, end, 11), 0);
+    assert_eq!(column(datetime!(2020-01-01 0:00 UTC), start, end, 11), 10);
+    // No length
+    assert_eq!(column(end, start, start, 11), 0);
+    assert_eq!(column(start, start, end, 0), 0);
+}
+
+#[test]
+fn ticks_test() {
+    use time::macros::datetime;
+
+    let start = datetime!(1969-07-20 0:00 UTC);
+    let end = datetime!(2024-01-01 0:00 UTC);
+    assert_eq!(ticks(start, end, 50), [1970, 1980, 1990, 2000, 2010, 2020]);
+    assert_eq!(ticks(start, end, 30), [1980, 2000, 2020]);
+    assert_eq!(ticks(start, end, 8), [2000]);
+
+    let start = datetime!(2022-03-01 0:00 UTC);
+    assert_eq!(ticks(start, end, 80), [2023, 2024]);
+    assert!(ticks(end, end, 80).is_empty());
+}
+
+#[test]
+fn timeline_test() {
+    use time::macros::datetime;
+
+    let now = datetime!(2020-01-01 0:00 UTC);
+    let days = [
+        Day::new("Windows 95", datetime!(1995-08-24 0:00 UTC)),
+        Day::new("Moon landing", datetime!(1970-01-01 0:00 UTC)),
+        Day::new("Berlin Wall Fall", datetime!(1989-11-09 0:00 UTC)),
+        Day::new("Pandemic", datetime!(2019-12-31 0:00 UTC)),
+    ];
+
+    assert_eq!(
+        timeline(&days, now, 51),
+        [
+            "                          * Windows 95",
+            "* Moon landing      * Berlin Wall Fall   Pandemic *",
+            "*---------+---------*-----*---+---------+---------*",
+            "          1980      1990      2000      2010   2020",
+        ]
+    );
+}
+
+#[test]
+fn timeline_edge_cases_test() {
+    use time::macros::datetime;
+
+    let now = datetime!(2024-01-01 0:00 UTC);
+    assert!(timeline(&[], now, 40).is_empty());
+
+    let days = [Day::new("Run", datetime!(2023-12-01 0:00 UTC))];
+    assert!(timeline(&days, now, 0).is_empty());
+    // Too narrow for anything useful, but no panics
+    for width in 1..4 {
+        assert!(!timeline(&days, now, width).is_empty());
+    }
+    assert_eq!(
+        timeline(&days, now, 20),
+        ["* Run", "*------------------+", "                2024"]
+    );
+
+    // Same date, and in the future
+    let days = [
+        Day::new("Launch", datetime!(2025-01-01 0:00 UTC)),
+        Day::new("Party", datetime!(2025-01-01 0:00 UTC)),
+    ];
+    assert_eq!(
+        timeline(&days, now, 10),
+        ["* Party", "* Launch", "*---------"]
+    );
+
+    // Labels wider than the timeline
+    let days = [Day::new(
+        "A very long label",
+        datetime!(2023-12-01 0:00 UTC),
+    )];
+    assert_eq!(
+        timeline(&days, now, 10),
+        ["* A very …", "*--------+", "      2024"]
+    );
+}
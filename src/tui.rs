@@ -0,0 +1,427 @@
+//! State of the interactive browser, `achievements tui`
+//!
+//! Each key press is mapped to an `Action` and applied with
+//! `TuiState::update()`, so the selection, filter and pending actions are
+//! tested without a terminal. Drawing the state and reading the keys is in
+//! `view`, with the tui feature.
+
+use time::OffsetDateTime;
+
+use crate::{
+    config::{Config, Day, SortKey},
+    render::format_day,
+    Achievement, Upcoming,
+};
+
+#[cfg(feature = "tui")]
+pub mod view;
+
+/// What the keys do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Moving through the list
+    Browse,
+    /// Typing the filter, after `/`
+    Filter,
+    /// Waiting for the confirmation to delete the selected day
+    ConfirmDelete,
+}
+
+/// Something the user asked for, usually with a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Up,
+    Down,
+    First,
+    Last,
+    /// Starts typing the filter
+    StartFilter,
+    /// Types a character of the filter
+    Type(char),
+    /// Deletes the last character of the filter
+    Backspace,
+    /// Keeps the filter and goes back to browsing
+    ApplyFilter,
+    /// Clears the filter and goes back to browsing
+    ClearFilter,
+    /// Sorts by the next key: config order, label, date, days
+    CycleSort,
+    /// Archives the selected day, or unarchives it
+    ToggleArchived,
+    /// Asks to delete the selected day
+    Delete,
+    Confirm,
+    Cancel,
+    Quit,
+}
+
+/// State of the browser: the config being browsed and edited, the
+/// filter, the sort order and the selected day
+#[derive(Debug)]
+pub struct TuiState {
+    pub config: Config,
+    pub now: OffsetDateTime,
+    /// Text the labels must contain (case-insensitive), empty for all
+    pub filter: String,
+    /// Sort order of the list, `None` for the config order
+    pub sort: Option<SortKey>,
+    pub mode: Mode,
+    /// Whether the config changed, to write it on quit
+    pub modified: bool,
+    /// Outcome of the last action, e.g. "Archived 'Run'"
+    pub status: Option<String>,
+    /// Position of the selected day in `visible()`
+    selected: usize,
+}
+
+impl TuiState {
+    pub fn new(config: Config, now: OffsetDateTime) -> Self {
+        Self {
+            config,
+            now,
+            filter: String::new(),
+            sort: None,
+            mode: Mode::Browse,
+            modified: false,
+            status: None,
+            selected: 0,
+        }
+    }
+
+    /// Indices in `config.days` of the days in the list, filtered and
+    /// sorted
+    pub fn visible(&self) -> Vec<usize> {
+        let filter = self.filter.trim().to_lowercase();
+        let mut visible: Vec<usize> = (0..self.config.days.len())
+            .filter(|&i| self.config.days[i].label.to_lowercase().contains(&filter))
+            .collect();
+
+        if let Some(key) = self.sort {
+            let days = &self.config.days;
+            let mut compare = key.comparator(false, self.now);
+            visible.sort_by(|&a, &b| compare(&days[a], &days[b]));
+        }
+        visible
+    }
+
+    /// Position of the selected day in the list, `None` if it's empty
+    pub fn selected_index(&self) -> Option<usize> {
+        (self.selected < self.visible().len()).then_some(self.selected)
+    }
+
+    /// The selected day, `None` if the list is empty
+    pub fn selected(&self) -> Option<&Day> {
+        let index = *self.visible().get(self.selected)?;
+        self.config.days.get(index)
+    }
+
+    /// Applies the action, returns `false` to quit
+    pub fn update(&mut self, action: Action) -> bool {
+        match (self.mode, action) {
+            (_, Action::Quit) => return false,
+            (Mode::Browse, Action::Up) => self.selected = self.selected.saturating_sub(1),
+            (Mode::Browse, Action::Down) => self.selected += 1,
+            (Mode::Browse, Action::First) => self.selected = 0,
+            (Mode::Browse, Action::Last) => self.selected = usize::MAX,
+            (Mode::Browse, Action::StartFilter) => {
+                self.mode = Mode::Filter;
+                self.status = None;
+            }
+            (Mode::Filter, Action::Type(c)) => {
+                self.filter.push(c);
+                self.selected = 0;
+            }
+            (Mode::Filter, Action::Backspace) => {
+                self.filter.pop();
+                self.selected = 0;
+            }
+            (Mode::Filter, Action::ApplyFilter) => self.mode = Mode::Browse,
+            (Mode::Filter | Mode::Browse, Action::ClearFilter) => {
+                self.filter.clear();
+                self.mode = Mode::Browse;
+                self.selected = 0;
+            }
+            (Mode::Browse, Action::CycleSort) => {
+                self.sort = match self.sort {
+                    None => Some(SortKey::Label),
+                    Some(SortKey::Label) => Some(SortKey::Date),
+                    Some(SortKey::Date) => Some(SortKey::Days),
+                    Some(SortKey::Days) => None,
+                };
+                self.selected = 0;
+            }
+            (Mode::Browse, Action::ToggleArchived) => self.toggle_archived(),
+            (Mode::Browse, Action::Delete) => {
+                if let Some(day) = self.selected() {
+                    self.status = Some(format!("Delete '{}'? y/n", day.label));
+                    self.mode = Mode::ConfirmDelete;
+                }
+            }
+            (Mode::ConfirmDelete, Action::Confirm) => self.delete(),
+            (Mode::ConfirmDelete, Action::Cancel) => {
+                self.status = None;
+                self.mode = Mode::Browse;
+            }
+            _ => {}
+        }
+
+        self.selected = self.selected.min(self.visible().len().saturating_sub(1));
+        true
+    }
+
+    /// Lines of the detail pane for the selected day: date, weekday,
+    /// breakdown, next milestone, notes and tags
+    pub fn details(&self) -> Vec<String> {
+        let Some(day) = self.selected() else {
+            return vec!["No days".to_string()];
+        };
+        let achievement = Achievement::new(day.clone(), self.now);
+
+        let mut lines = vec![
+            day.label.clone(),
+            String::new(),
+            format!("Date: {}", format_day(day.date)),
+            format!("Weekday: {}", day.date.weekday()),
+            format!("Elapsed: {}", achievement.interval.to_words_plain()),
+            format!("Breakdown: {}", achievement.interval.to_compound_string()),
+        ];
+        if let Some(end) = day.end {
+            lines.push(format!("Ended: {}", format_day(end)));
+        }
+        if day.archived || day.is_closed() {
+            lines.push("Next milestone: —".to_string());
+        } else {
+            let upcoming = Upcoming::new(day.clone(), self.now);
+            lines.push(format!(
+                "Next milestone: {} in {} days ({:.0}%)",
+                upcoming.milestone,
+                upcoming.in_days(),
+                upcoming.progress() * 100.0
+            ));
+        }
+        if !day.tags.is_empty() {
+            lines.push(format!("Tags: {}", day.tags.join(", ")));
+        }
+        if day.archived {
+            lines.push("Archived".to_string());
+        }
+        if let Some(notes) = &day.description {
+            lines.push(String::new());
+            lines.push(notes.clone());
+        }
+        lines
+    }
+
+    /// Text of the status line: the filter being typed, the outcome of the
+    /// last action or the keys
+    pub fn status_line(&self) -> String {
+        let line = match (self.mode, &self.status) {
+            (Mode::Filter, _) => format!("/{}▏ Enter to apply, Esc to clear", self.filter),
+            (_, Some(status)) => status.clone(),
+            (_, None) => "↑↓ move · / filter · s sort · a archive · d delete · q quit".to_string(),
+        };
+
+        if self.modified && self.mode == Mode::Browse {
+            format!("{line} · modified, saved on quit")
+        } else {
+            line
+        }
+    }
+
+    fn toggle_archived(&mut self) {
+        let Some(day) = self.selected() else {
+            return;
+        };
+        let (label, archived) = (day.label.clone(), !day.archived);
+
+        if self.config.set_archived(&label, archived).is_ok() {
+            self.modified = true;
+            let verb = if archived { "Archived" } else { "Unarchived" };
+            self.status = Some(format!("{verb} '{label}'"));
+        }
+    }
+
+    fn delete(&mut self) {
+        if let Some(label) = self.selected().map(|day| day.label.clone()) {
+            self.config.remove_day(&label);
+            self.modified = true;
+            self.status = Some(format!("Deleted '{label}'"));
+        }
+        self.mode = Mode::Browse;
+    }
+}
+
+#[cfg(test)]
+fn test_state() -> TuiState {
+    use time::macros::datetime;
+
+    let mut config = Config::default();
+    config.set_day("Moon landing", datetime!(1969-07-20 20:17:40 UTC));
+    config.set_day("Berlin Wall Fall", datetime!(1989-11-09 0:00 UTC));
+    config.set_day("apollo 11 launch", datetime!(1969-07-16 13:32 UTC));
+    TuiState::new(config, datetime!(2024-01-01 0:00 UTC))
+}
+
+#[cfg(test)]
+fn selected_label(state: &TuiState) -> Option<&str> {
+    state.selected().map(|day| day.label.as_str())
+}
+
+#[test]
+fn selection_test() {
+    let mut state = test_state();
+    assert_eq!(state.selected_index(), Some(0));
+    assert_eq!(selected_label(&state), Some("Moon landing"));
+
+    assert!(state.update(Action::Up));
+    assert_eq!(state.selected_index(), Some(0));
+    state.update(Action::Down);
+    assert_eq!(selected_label(&state), Some("Berlin Wall Fall"));
+    state.update(Action::Down);
+    state.update(Action::Down);
+    assert_eq!(state.selected_index(), Some(2));
+    state.update(Action::First);
+    assert_eq!(state.selected_index(), Some(0));
+    state.update(Action::Last);
+    assert_eq!(selected_label(&state), Some("apollo 11 launch"));
+
+    assert!(!state.update(Action::Quit));
+
+    let mut state = TuiState::new(Config::default(), state.now);
+    state.update(Action::Down);
+    assert_eq!(state.selected_index(), None);
+    assert_eq!(state.details(), ["No days"]);
+}
+
+#[test]
+fn filter_test() {
+    let mut state = test_state();
+    state.update(Action::Last);
+
+    // Keys are typed in the filter, not applied
+    state.update(Action::StartFilter);
+    assert_eq!(state.mode, Mode::Filter);
+    for c in "LAnd".chars() {
+        state.update(Action::Type(c));
+    }
+    state.update(Action::Delete);
+    assert_eq!(state.visible(), [0]);
+    assert_eq!(selected_label(&state), Some("Moon landing"));
+    assert_eq!(state.status_line(), "/LAnd▏ Enter to apply, Esc to clear");
+
+    state.update(Action::Backspace);
+    state.update(Action::Backspace);
+    state.update(Action::Backspace);
+    assert_eq!(state.filter, "L");
+    assert_eq!(state.visible(), [0, 1, 2]);
+
+    state.update(Action::Type('x'));
+    assert!(state.visible().is_empty());
+    assert_eq!(state.selected(), None);
+
+    state.update(Action::Backspace);
+    state.update(Action::Type('o'));
+    state.update(Action::ApplyFilter);
+    assert_eq!(state.mode, Mode::Browse);
+    assert_eq!(state.visible(), [2]);
+
+    state.update(Action::ClearFilter);
+    assert_eq!(state.filter, "");
+    assert_eq!(state.visible(), [0, 1, 2]);
+}
+
+#[test]
+fn sort_test() {
+    let mut state = test_state();
+    state.update(Action::Down);
+
+    let mut orders = Vec::new();
+    for _ in 0..4 {
+        state.update(Action::CycleSort);
+        assert_eq!(state.selected_index(), Some(0));
+        orders.push((state.sort, state.visible()));
+    }
+    assert_eq!(
+        orders,
+        [
+            (Some(SortKey::Label), vec![2, 1, 0]),
+            (Some(SortKey::Date), vec![2, 0, 1]),
+            (Some(SortKey::Days), vec![1, 0, 2]),
+            (None, vec![0, 1, 2]),
+        ]
+    );
+}
+
+#[test]
+fn archive_test() {
+    let mut state = test_state();
+    state.update(Action::Down);
+
+    state.update(Action::ToggleArchived);
+    assert!(state.config.days[1].archived);
+    assert!(state.modified);
+    assert_eq!(
+        state.status_line(),
+        "Archived 'Berlin Wall Fall' · modified, saved on quit"
+    );
+    assert!(state.details().contains(&"Archived".to_string()));
+    assert!(state.details().contains(&"Next milestone: —".to_string()));
+
+    state.update(Action::ToggleArchived);
+    assert!(!state.config.days[1].archived);
+    assert_eq!(
+        state.status.as_deref(),
+        Some("Unarchived 'Berlin Wall Fall'")
+    );
+}
+
+#[test]
+fn delete_test() {
+    let mut state = test_state();
+    state.update(Action::Last);
+
+    state.update(Action::Delete);
+    assert_eq!(state.mode, Mode::ConfirmDelete);
+    assert_eq!(state.status_line(), "Delete 'apollo 11 launch'? y/n");
+
+    // Only confirming or cancelling is possible
+    state.update(Action::Up);
+    state.update(Action::ToggleArchived);
+    assert_eq!(selected_label(&state), Some("apollo 11 launch"));
+    state.update(Action::Cancel);
+    assert_eq!(state.mode, Mode::Browse);
+    assert_eq!(state.config.days.len(), 3);
+    assert!(!state.modified);
+
+    state.update(Action::Delete);
+    state.update(Action::Confirm);
+    assert_eq!(state.mode, Mode::Browse);
+    assert_eq!(state.config.days.len(), 2);
+    assert!(state.modified);
+    assert_eq!(state.status.as_deref(), Some("Deleted 'apollo 11 launch'"));
+    // The selection moves to the new last day
+    assert_eq!(selected_label(&state), Some("Berlin Wall Fall"));
+}
+
+#[test]
+fn details_test() {
+    let mut state = test_state();
+    state.config.days[0].tags = vec!["space".to_string(), "history".to_string()];
+    state.config.days[0].description = Some("One small step".to_string());
+
+    assert_eq!(
+        state.details(),
+        [
+            "Moon landing",
+            "",
+            "Date: 1969-07-20",
+            "Weekday: Sunday",
+            "Elapsed: 2841 weeks",
+            "Breakdown: 54 years, 5 months, 3 weeks, 6 days",
+            "Next milestone: 20000 days in 113 days (61%)",
+            "Tags: space, history",
+            "",
+            "One small step",
+        ]
+    );
+}
@@ -0,0 +1,110 @@
+//! Terminal of `achievements tui`, with ratatui and crossterm
+
+use std::io;
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Style, Stylize},
+    widgets::{Block, List, ListItem, ListState, Paragraph, Wrap},
+    DefaultTerminal, Frame,
+};
+
+use super::{Action, Mode, TuiState};
+
+/// Runs the browser until the user quits, returns the final state
+///
+/// The terminal is restored even when reading a key fails.
+pub fn run(mut state: TuiState) -> io::Result<TuiState> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut state);
+    ratatui::restore();
+    result.map(|_| state)
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, state: &mut TuiState) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| draw(frame, state))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let Some(action) = action(state.mode, key) {
+                if !state.update(action) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// Maps a key to an action in the given mode, `None` for unbound keys
+fn action(mode: Mode, key: KeyEvent) -> Option<Action> {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        return Some(Action::Quit);
+    }
+
+    let action = match (mode, key.code) {
+        (Mode::Browse, KeyCode::Char('q')) => Action::Quit,
+        (Mode::Browse, KeyCode::Up | KeyCode::Char('k')) => Action::Up,
+        (Mode::Browse, KeyCode::Down | KeyCode::Char('j')) => Action::Down,
+        (Mode::Browse, KeyCode::Home | KeyCode::Char('g')) => Action::First,
+        (Mode::Browse, KeyCode::End | KeyCode::Char('G')) => Action::Last,
+        (Mode::Browse, KeyCode::Char('/')) => Action::StartFilter,
+        (Mode::Browse, KeyCode::Esc) => Action::ClearFilter,
+        (Mode::Browse, KeyCode::Char('s')) => Action::CycleSort,
+        (Mode::Browse, KeyCode::Char('a')) => Action::ToggleArchived,
+        (Mode::Browse, KeyCode::Char('d')) => Action::Delete,
+        (Mode::Filter, KeyCode::Char(c)) => Action::Type(c),
+        (Mode::Filter, KeyCode::Backspace) => Action::Backspace,
+        (Mode::Filter, KeyCode::Enter) => Action::ApplyFilter,
+        (Mode::Filter, KeyCode::Esc) => Action::ClearFilter,
+        (Mode::ConfirmDelete, KeyCode::Char('y')) => Action::Confirm,
+        (Mode::ConfirmDelete, KeyCode::Char('n') | KeyCode::Esc) => Action::Cancel,
+        _ => return None,
+    };
+    Some(action)
+}
+
+fn draw(frame: &mut Frame, state: &TuiState) {
+    let [main, status] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+    let [list_area, details_area] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(main);
+
+    let items: Vec<ListItem> = state
+        .visible()
+        .into_iter()
+        .map(|i| {
+            let day = &state.config.days[i];
+            let item = ListItem::new(format!(
+                "{}: {}",
+                day.label,
+                day.elapsed_at(state.now).to_words_plain()
+            ));
+            if day.archived {
+                item.dim()
+            } else {
+                item
+            }
+        })
+        .collect();
+    let title = match state.sort {
+        Some(key) => format!(" Days by {} ", format!("{key:?}").to_lowercase()),
+        None => " Days ".to_string(),
+    };
+    let list = List::new(items)
+        .block(Block::bordered().title(title))
+        .highlight_style(Style::new().reversed())
+        .highlight_symbol("> ");
+    let mut list_state = ListState::default().with_selected(state.selected_index());
+    frame.render_stateful_widget(list, list_area, &mut list_state);
+
+    let details = Paragraph::new(state.details().join("\n"))
+        .block(Block::bordered().title(" Details "))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(details, details_area);
+
+    frame.render_widget(Paragraph::new(state.status_line()), status);
+}
@@ -0,0 +1,149 @@
+//! Decisions of `achievements watch`, the live dashboard
+//!
+//! The binary renders the achievements view to a string every cycle, the
+//! functions here decide what to draw from it, so the loop itself only
+//! sleeps, renders and prints.
+
+use std::time::Duration;
+
+use crate::Achievement;
+
+/// How long the lines of the days whose count changed stay highlighted
+pub const HIGHLIGHT_FOR: Duration = Duration::from_secs(2);
+
+/// Clears the screen and moves the cursor to the top left corner
+pub const CLEAR: &str = "\x1b[2J\x1b[H";
+pub const HIDE_CURSOR: &str = "\x1b[?25l";
+pub const SHOW_CURSOR: &str = "\x1b[?25h";
+
+/// Reverse video, to highlight a line
+const REVERSE: &str = "\x1b[7m";
+const RESET: &str = "\x1b[0m";
+
+/// What to draw after rendering the view again
+#[derive(Debug, PartialEq, Eq)]
+pub enum Redraw {
+    /// Same text as the last frame, nothing to draw
+    Nothing,
+    /// New text, without days whose count changed
+    Plain(String),
+    /// Text with the lines of the days whose count changed highlighted,
+    /// to draw plain after `HIGHLIGHT_FOR`
+    Highlighted { highlighted: String, plain: String },
+}
+
+/// Lines (one per achievement) of the days whose number of days changed
+/// since the previous render
+///
+/// Days are matched by label, days not in the previous render (or the
+/// first render) aren't changed.
+pub fn changed_lines(previous: &[Achievement], current: &[Achievement]) -> Vec<usize> {
+    current
+        .iter()
+        .enumerate()
+        .filter(|(_, achievement)| {
+            previous
+                .iter()
+                .find(|old| old.day.label == achievement.day.label)
+                .is_some_and(|old| old.interval.as_days() != achievement.interval.as_days())
+        })
+        .map(|(line, _)| line)
+        .collect()
+}
+
+/// Highlights the given lines of the text in reverse video, keeping their
+/// colors
+pub fn highlight(text: &str, lines: &[usize]) -> String {
+    let mut highlighted = String::new();
+
+    for (i, line) in text.lines().enumerate() {
+        if lines.contains(&i) {
+            let line = line.replace(RESET, &format!("{RESET}{REVERSE}"));
+            highlighted.push_str(&format!("{REVERSE}{line}{RESET}"));
+        } else {
+            highlighted.push_str(line);
+        }
+        highlighted.push('\n');
+    }
+
+    highlighted
+}
+
+/// Decides what to draw from the text of the last frame (`None` before
+/// the first one), the new text and its changed lines
+pub fn redraw(last: Option<&str>, text: &str, changed: &[usize]) -> Redraw {
+    if !changed.is_empty() {
+        Redraw::Highlighted {
+            highlighted: highlight(text, changed),
+            plain: text.to_string(),
+        }
+    } else if last == Some(text) {
+        Redraw::Nothing
+    } else {
+        Redraw::Plain(text.to_string())
+    }
+}
+
+#[test]
+fn changed_lines_test() {
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let achievements = |now| {
+        vec![
+            Achievement::new(Day::new("Run", datetime!(2023-12-29 12:00 UTC)), now),
+            Achievement::new(Day::new("Moved", datetime!(2023-12-01 18:00 UTC)), now),
+        ]
+    };
+    let morning = achievements(datetime!(2024-01-01 9:00 UTC));
+
+    assert!(changed_lines(&[], &morning).is_empty());
+    assert!(changed_lines(&morning, &morning).is_empty());
+    // Only Run reaches a new day at noon
+    let noon = achievements(datetime!(2024-01-01 12:00 UTC));
+    assert_eq!(changed_lines(&morning, &noon), [0]);
+    let evening = achievements(datetime!(2024-01-01 18:00 UTC));
+    assert_eq!(changed_lines(&morning, &evening), [0, 1]);
+
+    // Matched by label, not by line
+    let mut reordered = achievements(datetime!(2024-01-01 12:00 UTC));
+    reordered.reverse();
+    assert_eq!(changed_lines(&morning, &reordered), [1]);
+    assert!(changed_lines(&morning[1..], &noon).is_empty());
+}
+
+#[test]
+fn highlight_test() {
+    let text = "Run:   3 days ☆☆☆\nMoved: 1 month ⭐\n";
+    assert_eq!(highlight(text, &[]), text);
+    assert_eq!(
+        highlight(text, &[1]),
+        "Run:   3 days ☆☆☆\n\x1b[7mMoved: 1 month ⭐\x1b[0m\n"
+    );
+
+    // The reverse video continues after the colors
+    let colored = "\x1b[1mRun:\x1b[0m \x1b[2m3 days\x1b[0m ☆☆☆\n";
+    assert_eq!(
+        highlight(colored, &[0]),
+        "\x1b[7m\x1b[1mRun:\x1b[0m\x1b[7m \x1b[2m3 days\x1b[0m\x1b[7m ☆☆☆\x1b[0m\n"
+    );
+}
+
+#[test]
+fn redraw_test() {
+    let text = "Run: 3 days ☆☆☆\n";
+    assert_eq!(redraw(None, text, &[]), Redraw::Plain(text.to_string()));
+    assert_eq!(redraw(Some(text), text, &[]), Redraw::Nothing);
+    assert_eq!(
+        redraw(Some("Run: 2 days ☆☆\n"), text, &[]),
+        Redraw::Plain(text.to_string())
+    );
+    assert_eq!(
+        redraw(Some("Run: 2 days ☆☆\n"), text, &[0]),
+        Redraw::Highlighted {
+            highlighted: "\x1b[7mRun: 3 days ☆☆☆\x1b[0m\n".to_string(),
+            plain: text.to_string(),
+        }
+    );
+}
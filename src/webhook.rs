@@ -0,0 +1,135 @@
+//! JSON payload of `achievements webhook`
+//!
+//! Only the payload is built here, the binary POSTs it (with the webhook
+//! feature), so its shape is the same for any HTTP client.
+
+use serde::Serialize;
+use time::OffsetDateTime;
+
+use crate::{config::Config, Achievement};
+
+/// Body of the webhook request
+///
+/// The achievements are the days reaching a milestone today, or all the
+/// days in the config with `all`, in config order. Each one is serialized
+/// like `Achievement` with the milestone reached today, `null` if none
+/// is, for example:
+///
+/// ```JSON
+/// {
+///   "sent_at": "2024-01-01T18:00:00Z",
+///   "achievements": [
+///     {
+///       "label": "Wedding",
+///       "date": "2019-01-02T12:00:00Z",
+///       "days": 1825,
+///       "words": "5 years",
+///       "badges": "🌟🌟🌟🌟🌟",
+///       "milestone": "5 years"
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct Payload {
+    #[serde(with = "time::serde::rfc3339")]
+    pub sent_at: OffsetDateTime,
+    pub achievements: Vec<Entry>,
+}
+
+/// An achievement in the webhook `Payload`
+#[derive(Debug, Clone, Serialize)]
+pub struct Entry {
+    #[serde(flatten)]
+    pub achievement: Achievement,
+    /// The milestone reached today in words, e.g. "1000 days"
+    pub milestone: Option<String>,
+}
+
+impl Payload {
+    /// Builds the payload of the milestones reached at `now`, of all the
+    /// days with `all`
+    ///
+    /// Archived days and closed periods never reach a milestone, see
+    /// `Achievement::milestone_today()`.
+    pub fn new(config: &Config, now: OffsetDateTime, all: bool) -> Self {
+        let achievements = config
+            .days
+            .iter()
+            .map(|day| {
+                let achievement = Achievement::new(day.clone(), now);
+                let milestone = achievement.milestone_today();
+                Entry {
+                    achievement,
+                    milestone: milestone.map(|milestone| milestone.to_words()),
+                }
+            })
+            .filter(|entry| all || entry.milestone.is_some())
+            .collect();
+
+        Payload {
+            sent_at: now,
+            achievements,
+        }
+    }
+
+    /// Whether there's nothing to send
+    pub fn is_empty(&self) -> bool {
+        self.achievements.is_empty()
+    }
+}
+
+#[test]
+fn payload_test() {
+    use serde_json::json;
+    use time::macros::datetime;
+
+    use crate::config::Day;
+
+    let now = datetime!(2024-01-01 18:00 UTC);
+    let mut config = Config::default();
+    config.set_day("Wedding", datetime!(2019-01-02 12:00 UTC));
+    config.set_day("Moved", datetime!(2023-09-24 12:00 UTC));
+    config.days.push(Day {
+        archived: true,
+        ..Day::new("Smoking", datetime!(2023-09-23 12:00 UTC))
+    });
+
+    let payload = Payload::new(&config, now, false);
+    assert_eq!(
+        serde_json::to_value(&payload).expect("Failed to serialize"),
+        json!({
+            "sent_at": "2024-01-01T18:00:00Z",
+            "achievements": [
+                {
+                    "label": "Wedding",
+                    "date": "2019-01-02T12:00:00Z",
+                    "days": 1825,
+                    "words": "5 years",
+                    "badges": "🌟🌟🌟🌟🌟",
+                    "milestone": "5 years"
+                }
+            ]
+        })
+    );
+
+    let payload = Payload::new(&config, now, true);
+    let json = serde_json::to_value(&payload).expect("Failed to serialize");
+    let entries: Vec<(&str, &serde_json::Value)> = json["achievements"]
+        .as_array()
+        .expect("Achievements array")
+        .iter()
+        .map(|entry| (entry["label"].as_str().unwrap(), &entry["milestone"]))
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            ("Wedding", &json!("5 years")),
+            ("Moved", &json!(null)),
+            ("Smoking", &json!(null)),
+        ]
+    );
+
+    assert!(Payload::new(&config, datetime!(2024-01-03 18:00 UTC), false).is_empty());
+    assert!(Payload::new(&Config::default(), now, true).is_empty());
+}
@@ -0,0 +1,2879 @@
+use std::{
+    collections::HashSet,
+    fs,
+    io::Write,
+    path::Path,
+    process::{Command, Output, Stdio},
+};
+
+use achievements::{config::Config, Interval};
+use tempfile::TempDir;
+
+/// Config with three days, in an order different from any sort key
+const CONFIG: &str = r#"{
+  "days": [
+    {
+      "label": "Moon landing",
+      "date": "1969-07-20T20:17:40+00:00"
+    },
+    {
+      "label": "Berlin Wall Fall",
+      "date": "1989-11-09T18:53:00+01:00"
+    },
+    {
+      "label": "apollo 11 launch",
+      "date": "1969-07-16T13:32:00+00:00"
+    }
+  ]
+}"#;
+
+/// Writes the given config in a new temporary directory
+fn temp_config(content: &str) -> TempDir {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    fs::write(dir.path().join("config.json"), content).expect("Failed to write config");
+    dir
+}
+
+/// Command of the binary, in English whatever the locale of the tests
+fn achievements_command() -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_achievements"));
+    for var in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+        command.env_remove(var);
+    }
+    command
+}
+
+/// Runs the binary against the config in `dir` and returns its stdout
+fn run(dir: &Path, args: &[&str]) -> String {
+    let output = achievements_command()
+        .args(args)
+        .env("ACHIEVEMENTS_CONFIG", dir.join("config.json"))
+        .output()
+        .expect("Failed to run achievements");
+    assert!(output.status.success(), "{output:?}");
+
+    String::from_utf8(output.stdout).expect("Output is not UTF-8")
+}
+
+/// Labels in the achievements output, in order
+fn achievement_labels(output: &str) -> Vec<&str> {
+    output
+        .lines()
+        .map(|line| line.split(':').next().unwrap_or_default())
+        .collect()
+}
+
+/// Labels in the `list` output, in order
+fn list_labels(output: &str) -> Vec<&str> {
+    output
+        .lines()
+        .map(|line| line.split('\'').nth(1).unwrap_or_default())
+        .collect()
+}
+
+#[test]
+fn achievements_sort() {
+    let dir = temp_config(CONFIG);
+
+    let labels = |args: &[&str]| -> Vec<String> {
+        let output = run(dir.path(), args);
+        achievement_labels(&output)
+            .into_iter()
+            .map(String::from)
+            .collect()
+    };
+
+    assert_eq!(
+        labels(&[]),
+        ["Moon landing", "Berlin Wall Fall", "apollo 11 launch"]
+    );
+    assert_eq!(
+        labels(&["achievements", "--sort", "label"]),
+        ["apollo 11 launch", "Berlin Wall Fall", "Moon landing"]
+    );
+    assert_eq!(
+        labels(&["achievements", "--sort", "date"]),
+        ["apollo 11 launch", "Moon landing", "Berlin Wall Fall"]
+    );
+    assert_eq!(
+        labels(&["achievements", "--sort", "days"]),
+        ["Berlin Wall Fall", "Moon landing", "apollo 11 launch"]
+    );
+    assert_eq!(
+        labels(&["achievements", "--sort", "elapsed"]),
+        ["Berlin Wall Fall", "Moon landing", "apollo 11 launch"]
+    );
+    assert_eq!(
+        labels(&["achievements", "--sort", "label", "--reverse"]),
+        ["Moon landing", "Berlin Wall Fall", "apollo 11 launch"]
+    );
+    assert_eq!(
+        labels(&["achievements", "--sort", "days", "--reverse"]),
+        ["apollo 11 launch", "Moon landing", "Berlin Wall Fall"]
+    );
+}
+
+#[test]
+fn list_sort() {
+    let dir = temp_config(CONFIG);
+
+    let labels = |args: &[&str]| -> Vec<String> {
+        let output = run(dir.path(), args);
+        list_labels(&output).into_iter().map(String::from).collect()
+    };
+
+    assert_eq!(
+        labels(&["list"]),
+        ["Moon landing", "Berlin Wall Fall", "apollo 11 launch"]
+    );
+    assert_eq!(
+        labels(&["list", "--sort", "label"]),
+        ["apollo 11 launch", "Berlin Wall Fall", "Moon landing"]
+    );
+    assert_eq!(
+        labels(&["list", "--sort", "date"]),
+        ["apollo 11 launch", "Moon landing", "Berlin Wall Fall"]
+    );
+    assert_eq!(
+        labels(&["list", "--sort", "days"]),
+        ["Berlin Wall Fall", "Moon landing", "apollo 11 launch"]
+    );
+    assert_eq!(
+        labels(&["list", "--sort", "date", "--reverse"]),
+        ["Berlin Wall Fall", "Moon landing", "apollo 11 launch"]
+    );
+}
+
+#[test]
+fn config_path() {
+    let dir = temp_config(CONFIG);
+
+    let output = run(dir.path(), &["config", "path"]);
+    assert_eq!(
+        output,
+        format!("{}\n", dir.path().join("config.json").display())
+    );
+}
+
+#[test]
+fn config_path_xdg() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+
+    let output = achievements_command()
+        .args(["config", "path"])
+        .env_remove("ACHIEVEMENTS_CONFIG")
+        .env("XDG_CONFIG_HOME", dir.path())
+        .output()
+        .expect("Failed to run achievements");
+    assert!(output.status.success(), "{output:?}");
+
+    let expected = dir.path().join("achievements").join("config.json");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("{}\n", expected.display())
+    );
+}
+
+#[test]
+fn achievements_json() {
+    let dir = temp_config(CONFIG);
+
+    for args in [
+        &["achievements", "--format", "json"][..],
+        &["achievements", "--format", "json", "--compact"][..],
+    ] {
+        let output = run(dir.path(), args);
+        let json: serde_json::Value = serde_json::from_str(&output).expect("Invalid JSON");
+
+        let achievements = json.as_array().expect("Should be an array");
+        assert_eq!(achievements.len(), 3);
+
+        let moon_landing = &achievements[0];
+        assert_eq!(moon_landing["label"], "Moon landing");
+        assert_eq!(moon_landing["date"], "1969-07-20T20:17:40Z");
+
+        let days = moon_landing["days"]
+            .as_u64()
+            .expect("days should be a number");
+        let interval = Interval::from_days(days as usize);
+        assert_eq!(moon_landing["words"], interval.to_words());
+        assert!(moon_landing["badges"]
+            .as_str()
+            .expect("badges should be a string")
+            .starts_with("💎💎💎💎💎"));
+
+        let berlin_wall_fall = &achievements[1];
+        assert_eq!(berlin_wall_fall["label"], "Berlin Wall Fall");
+        assert_eq!(berlin_wall_fall["date"], "1989-11-09T18:53:00+01:00");
+    }
+
+    // Badges in the style of the text output
+    let badges = |args: &[&str]| {
+        let output = run(dir.path(), args);
+        let json: serde_json::Value = serde_json::from_str(&output).expect("Invalid JSON");
+        json[0]["badges"].clone()
+    };
+    assert_eq!(badges(&["--format", "json", "--plain"]), "#####");
+    assert_eq!(badges(&["--format", "json", "--ascii"]), "#####");
+    assert_eq!(badges(&["--format", "json", "--no-badges"]), "");
+    assert_eq!(badges(&["--format", "json"]), "💎💎💎💎💎");
+
+    let compact = run(
+        dir.path(),
+        &["achievements", "--format", "json", "--compact"],
+    );
+    assert_eq!(compact.lines().count(), 1);
+
+    // compact JSON has no special messages
+    let dir = temp_config(r#"{"days": []}"#);
+    run(dir.path(), &["add", "Anniversary", "--days-ago", "365"]);
+    let words = |args: &[&str]| -> String {
+        let json: serde_json::Value =
+            serde_json::from_str(&run(dir.path(), args)).expect("Invalid JSON");
+        json[0]["words"]
+            .as_str()
+            .expect("words should be a string")
+            .to_string()
+    };
+    assert_eq!(
+        words(&["achievements", "--format", "json"]),
+        "1 year, happy anniversary!"
+    );
+    assert_eq!(
+        words(&["achievements", "--format", "json", "--compact"]),
+        "1 year"
+    );
+}
+
+#[test]
+fn config_show() {
+    let dir = temp_config(CONFIG);
+
+    let output = run(dir.path(), &["config", "show"]);
+    let (path, json) = output.split_once('\n').expect("Should have a path line");
+    assert_eq!(path, dir.path().join("config.json").display().to_string());
+
+    let config = Config::from_json_str(json).expect("Should print the config");
+    assert_eq!(config.days.len(), 3);
+}
+
+/// Config with labels needing CSV quoting
+const CSV_CONFIG: &str = r#"{
+  "days": [
+    {
+      "label": "Moon landing, Apollo 11",
+      "date": "1969-07-20T20:17:40+00:00"
+    },
+    {
+      "label": "The \"Wall\" fell",
+      "date": "1989-11-09T18:53:00+01:00"
+    }
+  ]
+}"#;
+
+#[test]
+fn achievements_csv() {
+    let dir = temp_config(CSV_CONFIG);
+
+    let output = run(dir.path(), &["achievements", "--format", "csv"]);
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "label,date,days,interval");
+    assert!(lines[1].starts_with("\"Moon landing, Apollo 11\",1969-07-20T20:17:40Z,"));
+    assert!(lines[2].starts_with("\"The \"\"Wall\"\" fell\",1989-11-09T18:53:00+01:00,"));
+
+    let no_header = run(
+        dir.path(),
+        &["achievements", "--format", "csv", "--no-header"],
+    );
+    assert_eq!(no_header.lines().collect::<Vec<_>>(), lines[1..]);
+}
+
+#[test]
+fn list_date_format() {
+    let dir = temp_config(CONFIG);
+
+    assert_eq!(
+        run(dir.path(), &["list", "--date-format", "long"]),
+        "'Moon landing': 20 July 1969\n\
+         'Berlin Wall Fall': 9 November 1989\n\
+         'apollo 11 launch': 16 July 1969\n"
+    );
+    assert_eq!(
+        run(dir.path(), &["list", "--date-format", "short"])
+            .lines()
+            .next(),
+        Some("'Moon landing': 1969-07-20")
+    );
+    assert_eq!(
+        run(
+            dir.path(),
+            &["--lang", "italian", "list", "--date-format", "medium"]
+        )
+        .lines()
+        .nth(1),
+        Some("'Berlin Wall Fall': 9 nov 1989")
+    );
+    let verbose = run(
+        dir.path(),
+        &["--lang", "italian", "list", "-v", "--date-format", "long"],
+    );
+    assert!(verbose.starts_with("Moon landing: 20 luglio 1969 20:17:40, domenica, "));
+    assert!(verbose
+        .lines()
+        .all(|line| line.ends_with(" giorni in tutto)")));
+
+    let output = achievements_command()
+        .args(["list", "--format", "csv", "--date-format", "long"])
+        .env("ACHIEVEMENTS_CONFIG", dir.path().join("config.json"))
+        .output()
+        .expect("Failed to run achievements");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn list_csv() {
+    let dir = temp_config(CSV_CONFIG);
+
+    let output = run(dir.path(), &["list", "--format", "csv"]);
+    assert_eq!(
+        output,
+        "label,date\n\
+         \"Moon landing, Apollo 11\",1969-07-20T20:17:40Z\n\
+         \"The \"\"Wall\"\" fell\",1989-11-09T18:53:00+01:00\n"
+    );
+
+    let no_header = run(dir.path(), &["list", "--format", "csv", "--no-header"]);
+    assert_eq!(
+        no_header,
+        "\"Moon landing, Apollo 11\",1969-07-20T20:17:40Z\n\
+         \"The \"\"Wall\"\" fell\",1989-11-09T18:53:00+01:00\n"
+    );
+}
+
+#[test]
+fn achievements_no_badges() {
+    let dir = temp_config(CONFIG);
+
+    let default = run(dir.path(), &["achievements", "--no-align"]);
+    let no_badges = run(dir.path(), &["achievements", "--no-align", "--no-badges"]);
+
+    assert_eq!(default.lines().count(), no_badges.lines().count());
+    for (line, no_badges_line) in default.lines().zip(no_badges.lines()) {
+        // same line, without the trailing badges
+        assert!(line.starts_with(&format!("{no_badges_line} 💎")));
+        assert!(!no_badges_line.contains('💎'));
+        assert_eq!(no_badges_line, no_badges_line.trim_end());
+    }
+}
+
+#[test]
+fn achievements_plain() {
+    let dir = temp_config(
+        r#"{
+  "days": [
+    {
+      "label": "🚀 Moon landing",
+      "date": "1969-07-20T20:17:40+00:00"
+    },
+    {
+      "label": "Anniversary",
+      "date": "2000-01-01T00:00:00+00:00"
+    }
+  ]
+}"#,
+    );
+
+    let output = run(dir.path(), &["achievements", "--plain"]);
+    assert!(output.is_ascii(), "{output}");
+    assert!(output.starts_with("? Moon landing: "));
+    assert!(output.lines().next().unwrap().ends_with(" #####"));
+}
+
+#[test]
+fn lang() {
+    let dir = temp_config(CONFIG);
+    let first_line = |output: Output| {
+        assert!(output.status.success(), "{output:?}");
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        stdout.lines().next().unwrap_or_default().to_string()
+    };
+    let with_env = |vars: &[(&str, &str)], args: &[&str]| {
+        let mut command = achievements_command();
+        command
+            .args(args)
+            .env("ACHIEVEMENTS_CONFIG", dir.path().join("config.json"))
+            .env("ACHIEVEMENTS_NOW", "2024-01-01T00:00:00Z");
+        for (var, value) in vars {
+            command.env(var, value);
+        }
+        first_line(command.output().expect("Failed to run achievements"))
+    };
+
+    assert_eq!(
+        with_env(&[], &["--plain"]),
+        "Moon landing:     2841 weeks #####"
+    );
+    assert_eq!(
+        with_env(&[], &["--plain", "--lang", "italian"]),
+        "Moon landing:     2841 settimane #####"
+    );
+    assert_eq!(
+        with_env(&[("LANG", "it_IT.UTF-8")], &["--plain"]),
+        "Moon landing:     2841 settimane #####"
+    );
+    assert_eq!(
+        with_env(&[("LANG", "it_IT.UTF-8"), ("LC_ALL", "C")], &["--plain"]),
+        "Moon landing:     2841 weeks #####"
+    );
+    assert_eq!(
+        with_env(
+            &[("LANG", "it_IT.UTF-8")],
+            &["--plain", "--lang", "english"]
+        ),
+        "Moon landing:     2841 weeks #####"
+    );
+
+    // And in JSON
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["--lang", "italian", "--format", "json"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Invalid JSON");
+    assert_eq!(json[0]["label"], "Moon landing");
+    assert_eq!(json[0]["words"], "2841 settimane");
+
+    // And in Markdown
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["--lang", "italian", "--format", "markdown"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("| Moon landing | 1969-07-20 | 2841 settimane | 💎💎💎💎💎 |\n"));
+
+    // Messages of the binary too
+    let empty = temp_config(r#"{"days": []}"#);
+    assert_eq!(
+        run(empty.path(), &["--lang", "italian", "summary"]),
+        "Ancora nessun traguardo — esegui 'achievements init' o \
+         'achievements add' per iniziare.\n"
+    );
+}
+
+#[test]
+fn achievements_progress() {
+    let dir = temp_config(CONFIG);
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["achievements", "--progress", "--sort", "label"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "\
+apollo 11 launch: 19891 days 💎💎💎💎💎 [██████░░░░] 62% to 20000 days
+Berlin Wall Fall: 12470 days 💎💎💎     [██░░░░░░░░] 16% to 35 years
+Moon landing:     2841 weeks 💎💎💎💎💎 [██████░░░░] 61% to 20000 days
+"
+    );
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &[
+            "achievements",
+            "--progress",
+            "--progress-width",
+            "4",
+            "--plain",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "\
+Moon landing:     2841 weeks ##### [##..] 61% to 20000 days
+Berlin Wall Fall: 12470 days ###   [#...] 16% to 35 years
+apollo 11 launch: 19891 days ##### [##..] 62% to 20000 days
+"
+    );
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["achievements", "--progress-width", "4"],
+    );
+    assert_eq!(output.status.code(), Some(2));
+}
+
+/// Runs the binary against the config in `dir` with the given stdin
+fn run_with_stdin(dir: &Path, args: &[&str], stdin: &str) -> Output {
+    let mut child = achievements_command()
+        .args(args)
+        .env("ACHIEVEMENTS_CONFIG", dir.join("config.json"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to run achievements");
+    // the process may exit before reading its stdin, ignore broken pipes
+    let _ = child
+        .stdin
+        .take()
+        .expect("Should have stdin")
+        .write_all(stdin.as_bytes());
+
+    child
+        .wait_with_output()
+        .expect("Failed to run achievements")
+}
+
+fn read_config(dir: &Path) -> Config {
+    let json = fs::read_to_string(dir.join("config.json")).expect("Failed to read config");
+    Config::from_json_str(&json).expect("Failed to parse config")
+}
+
+/// Labels of the days in the config, in order
+fn labels(config: &Config) -> Vec<&str> {
+    config.days.iter().map(|day| day.label.as_str()).collect()
+}
+
+#[test]
+fn config_reset() {
+    let dir = temp_config(CONFIG);
+
+    // not confirmed
+    let output = run_with_stdin(dir.path(), &["config", "reset"], "no\n");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Type 'yes' to continue"));
+    assert_eq!(read_config(dir.path()).days.len(), 3);
+
+    // confirmed
+    let output = run_with_stdin(dir.path(), &["config", "reset"], "yes\n");
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(read_config(dir.path()).days.len(), 0);
+    assert!(dir.path().join("config.json.bak").exists());
+
+    // restore from the backup, confirmation skipped
+    run(dir.path(), &["config", "reset", "--from-backup", "--yes"]);
+    assert_eq!(read_config(dir.path()).days.len(), 3);
+
+    // --force skips the confirmation too
+    run(dir.path(), &["config", "reset", "--force"]);
+    assert_eq!(read_config(dir.path()).days.len(), 0);
+}
+
+#[test]
+fn config_reset_without_backup() {
+    let dir = temp_config(CONFIG);
+
+    let output = run_with_stdin(dir.path(), &["config", "reset", "--from-backup"], "yes\n");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No backup found"));
+    assert_eq!(read_config(dir.path()).days.len(), 3);
+}
+
+#[test]
+fn achievements_align() {
+    let dir = temp_config(CONFIG);
+
+    let aligned = run(dir.path(), &["achievements"]);
+    let not_aligned = run(dir.path(), &["achievements", "--no-align"]);
+
+    // interval words start in the same column
+    let words_columns: Vec<usize> = aligned
+        .lines()
+        .map(|line| {
+            let (label, rest) = line.split_once(':').expect("Should have a label");
+            label.len() + 1 + rest.len() - rest.trim_start().len()
+        })
+        .collect();
+    assert_eq!(words_columns, ["apollo 11 launch: ".len(); 3]);
+
+    // same content, just different spacing
+    for (aligned, not_aligned) in aligned.lines().zip(not_aligned.lines()) {
+        assert_eq!(
+            aligned.split_whitespace().collect::<Vec<_>>(),
+            not_aligned.split_whitespace().collect::<Vec<_>>()
+        );
+    }
+    assert!(not_aligned.starts_with("Moon landing: "));
+}
+
+#[test]
+fn achievements_limit() {
+    let dir = temp_config(CONFIG);
+
+    let labels = |args: &[&str]| -> Vec<String> {
+        let output = run(dir.path(), args);
+        achievement_labels(&output)
+            .into_iter()
+            .map(String::from)
+            .collect()
+    };
+
+    assert_eq!(labels(&["achievements", "--limit", "1"]), ["Moon landing"]);
+    assert_eq!(labels(&["achievements", "--limit", "0"]).len(), 3);
+    assert_eq!(labels(&["achievements", "--limit", "5"]).len(), 3);
+    assert_eq!(
+        labels(&["achievements", "--sort", "label", "--limit", "2"]),
+        ["apollo 11 launch", "Berlin Wall Fall"]
+    );
+    assert_eq!(
+        labels(&[
+            "achievements",
+            "--sort",
+            "label",
+            "--reverse",
+            "--limit",
+            "2"
+        ]),
+        ["Moon landing", "Berlin Wall Fall"]
+    );
+    assert_eq!(
+        labels(&["achievements", "--oldest", "--limit", "2"]),
+        ["apollo 11 launch", "Moon landing"]
+    );
+    assert_eq!(
+        labels(&["achievements", "--newest", "--limit", "1"]),
+        ["Berlin Wall Fall"]
+    );
+    assert_eq!(
+        labels(&["achievements", "--newest"]),
+        ["Berlin Wall Fall", "Moon landing", "apollo 11 launch"]
+    );
+}
+
+#[test]
+fn random() {
+    let dir = temp_config(CONFIG);
+    let random = |args: &[&str]| -> String {
+        let mut args = args.to_vec();
+        args.insert(0, "random");
+        let output = run_at(dir.path(), "2024-01-01T00:00:00Z", &args);
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let output = random(&["--seed", "1", "--plain"]);
+    assert_eq!(output.lines().count(), 1);
+    let labels = achievement_labels(&output);
+    assert!(["Moon landing", "Berlin Wall Fall", "apollo 11 launch"].contains(&labels[0]));
+    assert_eq!(random(&["--seed", "1", "--plain"]), output);
+    let picked: HashSet<String> = (0..20)
+        .map(|seed| random(&["--seed", &seed.to_string(), "--template", "{label}"]))
+        .collect();
+    assert_eq!(picked.len(), 3);
+    assert_eq!(
+        random(&["--seed", "3", "--weighted", "--template", "{label}"]),
+        random(&["--seed", "3", "--weighted", "--template", "{label}"])
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&random(&["--format", "json"])).expect("Valid JSON");
+    assert_eq!(json.as_array().map(Vec::len), Some(1));
+
+    // Nothing at all without days to pick
+    let dir = temp_config(
+        r#"{"days": [{"label": "Old job", "date": "2010-01-01T00:00:00Z", "archived": true}]}"#,
+    );
+    let output = run_at(dir.path(), "2024-01-01T00:00:00Z", &["random"]);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    let dir = temp_config(r#"{"days": []}"#);
+    let output = run_at(dir.path(), "2024-01-01T00:00:00Z", &["random"]);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn summary() {
+    let dir = temp_config(CONFIG);
+
+    let output = run(dir.path(), &["summary"]);
+    assert_eq!(output.lines().count(), 1);
+    assert!(output.starts_with("3 achievements tracked · oldest: apollo 11 launch ("));
+    assert!(output.contains(" days) · newest: Berlin Wall Fall ("));
+    assert!(output.contains(" days) · average: "));
+    assert!(output.ends_with(" days\n"));
+
+    let empty = temp_config(r#"{"days": []}"#);
+    let output = run(empty.path(), &["summary"]);
+    assert_eq!(
+        output,
+        "No achievements tracked yet — run 'achievements init' or 'achievements add' to get started.\n"
+    );
+}
+
+#[test]
+fn achievements_template() {
+    let dir = temp_config(CONFIG);
+
+    let output = run(
+        dir.path(),
+        &["achievements", "--template", "{{{label}}} {date} {weekday}"],
+    );
+    assert_eq!(
+        output,
+        "{Moon landing} 1969-07-20 Sunday\n\
+         {Berlin Wall Fall} 1989-11-09 Thursday\n\
+         {apollo 11 launch} 1969-07-16 Wednesday\n"
+    );
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &[
+            "--lang",
+            "italian",
+            "--template",
+            "{label}: {words}",
+            "--limit",
+            "1",
+        ],
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Moon landing: 2841 settimane\n"
+    );
+
+    let output = run_with_stdin(dir.path(), &["achievements", "--template", "{nope}"], "");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown placeholder '{nope}'"));
+    assert!(stderr.contains("{words_short}"));
+
+    let output = run(
+        dir.path(),
+        &[
+            "achievements",
+            "--format-string",
+            "{label:.<18}|{date:^12}|",
+        ],
+    );
+    assert_eq!(
+        output,
+        "Moon landing......| 1969-07-20 |\n\
+         Berlin Wall Fall..| 1989-11-09 |\n\
+         apollo 11 launch..| 1969-07-16 |\n"
+    );
+    let output = run_with_stdin(
+        dir.path(),
+        &["achievements", "--format-string", "{days:x}"],
+        "",
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid format in '{days:x}'"));
+
+    let with_template = temp_config(
+        r#"{
+  "days": [{"label": "Moon landing", "date": "1969-07-20T20:17:40+00:00"}],
+  "template": "{label}: {date}"
+}"#,
+    );
+    assert_eq!(
+        run(with_template.path(), &["achievements"]),
+        "Moon landing: 1969-07-20\n"
+    );
+    assert_eq!(
+        run(
+            with_template.path(),
+            &["achievements", "--template", "{label}"]
+        ),
+        "Moon landing\n"
+    );
+}
+
+#[test]
+fn achievements_badge_style() {
+    let dir = temp_config(
+        r#"{
+  "days": [{"label": "Moon landing", "date": "1969-07-20T20:17:40+00:00"}],
+  "badge_style": "ascii"
+}"#,
+    );
+    let badges = |args: &[&str]| -> String {
+        let output = run(dir.path(), args);
+        output
+            .trim_end()
+            .rsplit(' ')
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    assert!(badges(&["achievements"]).starts_with('#'));
+    assert!(badges(&["achievements", "--template", "{badges}"]).starts_with('#'));
+    assert_eq!(badges(&["achievements", "--no-badges"]), "days");
+
+    fs::write(
+        dir.path().join("config.json"),
+        r#"{
+  "days": [{"label": "Moon landing", "date": "1969-07-20T20:17:40+00:00"}],
+  "badge_style": "none"
+}"#,
+    )
+    .unwrap();
+    assert_eq!(badges(&["achievements"]), "days");
+    assert!(badges(&["achievements", "--ascii"]).starts_with('#'));
+}
+
+#[test]
+fn today_milestones() {
+    let dir = temp_config(r#"{"days": []}"#);
+
+    let output = run(dir.path(), &["--today-milestones"]);
+    assert_eq!(output, "");
+
+    run(dir.path(), &["add", "Moved", "--days-ago", "14"]);
+    run(dir.path(), &["add", "Started running", "--days-ago", "15"]);
+    run(dir.path(), &["add", "Wedding", "--days-ago", "1825"]);
+    let output = run(dir.path(), &["--today-milestones"]);
+    assert_eq!(
+        output,
+        "🎉 Milestone reached: Moved — 2 weeks ★★\n\
+         🎉 Milestone reached: Wedding — 5 years 🌟🌟🌟🌟🌟\n"
+    );
+    assert_eq!(
+        run(dir.path(), &["achievements", "--today-milestones"]),
+        output
+    );
+}
+
+#[test]
+fn achievements_filter() {
+    let dir = temp_config(CONFIG);
+    let filtered = |args: &[&str]| -> Vec<String> {
+        let mut args = args.to_vec();
+        args.insert(0, "achievements");
+        achievement_labels(&run(dir.path(), &args))
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    };
+
+    assert_eq!(filtered(&["--before", "1969-07-17"]), ["apollo 11 launch"]);
+    assert_eq!(
+        filtered(&["--after", "1969-07-20"]),
+        ["Moon landing", "Berlin Wall Fall"]
+    );
+    assert_eq!(
+        filtered(&["--min-days", "15000"]),
+        ["Moon landing", "apollo 11 launch"]
+    );
+    assert_eq!(filtered(&["--max-days", "15000"]), ["Berlin Wall Fall"]);
+    assert!(filtered(&["--tag", "space"]).is_empty());
+    assert_eq!(
+        filtered(&[
+            "--exclude",
+            "moon landing ",
+            "--exclude",
+            "Apollo 11 Launch"
+        ]),
+        ["Berlin Wall Fall"]
+    );
+
+    // combined, before sorting and limiting
+    assert_eq!(
+        filtered(&[
+            "--after",
+            "1969-07-17",
+            "--min-days",
+            "15000",
+            "--oldest",
+            "--limit",
+            "1"
+        ]),
+        ["Moon landing"]
+    );
+
+    // same flags for list, stats and export
+    let output = run(dir.path(), &["list", "--before", "1969-07-17"]);
+    assert_eq!(list_labels(&output), ["apollo 11 launch"]);
+    let output = run(
+        dir.path(),
+        &["stats", "--after", "1969-07-17", "--format", "json"],
+    );
+    let json: serde_json::Value = serde_json::from_str(&output).expect("Invalid JSON");
+    assert_eq!(json["count"], 2);
+    let output = run(dir.path(), &["export", "--max-days", "15000"]);
+    let exported = Config::from_json_str(&output).expect("Invalid JSON");
+    assert_eq!(labels(&exported), ["Berlin Wall Fall"]);
+
+    let output = run_with_stdin(dir.path(), &["achievements", "--before", "someday"], "");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn upcoming() {
+    use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
+
+    let now = OffsetDateTime::now_utc();
+    let date = |days: i64| {
+        (now - Duration::days(days) - Duration::hours(1))
+            .format(&Rfc3339)
+            .expect("Failed to format date")
+    };
+    let dir = temp_config(&format!(
+        r#"{{
+  "days": [
+    {{"label": "Quit smoking", "date": "{}"}},
+    {{"label": "Moved", "date": "{}"}},
+    {{"label": "Born", "date": "{}"}}
+  ]
+}}"#,
+        date(988),
+        date(365),
+        date(200),
+    ));
+
+    assert_eq!(
+        run(dir.path(), &["upcoming"]),
+        "Moved: 1 year today [██████████] 100%\n\
+         Quit smoking: 1000 days in 12 days [██████████] 95%\n"
+    );
+    assert_eq!(
+        run(dir.path(), &["upcoming", "--days", "5", "--ascii"]),
+        "Moved: 1 year today [##########] 100%\n"
+    );
+    assert_eq!(
+        run(
+            dir.path(),
+            &["upcoming", "--bar-width", "4", "--bar-chars", "=-"]
+        ),
+        "Moved: 1 year today [====] 100%\nQuit smoking: 1000 days in 12 days [====] 95%\n"
+    );
+    assert_eq!(
+        run(dir.path(), &["--lang", "italian", "upcoming", "--ascii"]),
+        "Moved: 1 anno oggi [##########] 100%\n\
+         Quit smoking: 1000 giorni tra 12 giorni [##########] 95%\n"
+    );
+
+    let output = run(dir.path(), &["upcoming", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&output).expect("Invalid JSON");
+    assert_eq!(json[0]["label"], "Moved");
+    assert_eq!(json[0]["today"], true);
+    assert_eq!(json[1]["milestone"], "1000 days");
+    assert_eq!(json[1]["in_days"], 12);
+
+    let empty = temp_config(r#"{"days": []}"#);
+    assert_eq!(
+        run(empty.path(), &["upcoming", "--days", "7"]),
+        "Nothing coming up in the next 7 days 🌵\n"
+    );
+    assert_eq!(
+        run(empty.path(), &["upcoming", "--days", "1"]),
+        "Nothing coming up in the next 1 day 🌵\n"
+    );
+    assert_eq!(
+        run(
+            empty.path(),
+            &["--lang", "italian", "upcoming", "--days", "7"]
+        ),
+        "Niente in arrivo nei prossimi 7 giorni 🌵\n"
+    );
+
+    // Days in the future have no milestones yet
+    let future = temp_config(
+        r#"{"days": [
+    {"label": "Launch", "date": "2024-04-10T00:00:00Z"},
+    {"label": "Moved", "date": "2023-01-01T00:00:00Z"}
+]}"#,
+    );
+    let output = run_at(future.path(), "2024-01-01T00:00:00Z", &["upcoming"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Moved: 1 year today [██████████] 100%\n"
+    );
+    let output = run_at(future.path(), "2024-01-01T00:00:00Z", &["next"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Moved: 1 year today\n"
+    );
+}
+
+#[test]
+fn add() {
+    let dir = temp_config(r#"{"days": []}"#);
+
+    let output = run(dir.path(), &["add", "Moon landing", "--at", "1969-07-20"]);
+    assert_eq!(output, "Day with label 'Moon landing' set to 1969-07-20\n");
+    run(
+        dir.path(),
+        &[
+            "add",
+            "Berlin Wall Fall",
+            "--at",
+            "1989-11-09T18:53:00+01:00",
+        ],
+    );
+    run(dir.path(), &["add", "Today", "--today"]);
+    run(dir.path(), &["add", "Last week", "--days-ago", "7"]);
+
+    let config = read_config(dir.path());
+    let labels: Vec<&str> = config.days.iter().map(|day| day.label.as_str()).collect();
+    assert_eq!(
+        labels,
+        ["Moon landing", "Berlin Wall Fall", "Today", "Last week"]
+    );
+    let days: Vec<usize> = config
+        .days
+        .iter()
+        .map(|day| achievements::days_since(day.date))
+        .collect();
+    assert_eq!(days[2..], [0, 7]);
+
+    let output = run_with_stdin(dir.path(), &["add", "Bad", "--at", "someday"], "");
+    assert!(!output.status.success());
+
+    let output = run_with_stdin(dir.path(), &["add", "moon landing ", "--today"], "");
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Error: Day with label 'moon landing' already exists, use --force to update its date\n"
+    );
+    assert_eq!(read_config(dir.path()).days.len(), 4);
+
+    run(
+        dir.path(),
+        &["add", "Moon landing", "--at", "1969-07-21", "--force"],
+    );
+    let config = read_config(dir.path());
+    assert_eq!(config.days.len(), 4);
+    assert_eq!(
+        config.days[0].date,
+        time::macros::datetime!(1969-07-21 0:00 UTC)
+    );
+
+    // Updates the day with the label in another case, keeping its label
+    run(
+        dir.path(),
+        &["add", " MOON landing", "--at", "1969-07-20", "--force"],
+    );
+    let config = read_config(dir.path());
+    assert_eq!(config.days.len(), 4);
+    assert_eq!(config.days[0].label, "Moon landing");
+    assert_eq!(
+        config.days[0].date,
+        time::macros::datetime!(1969-07-20 0:00 UTC)
+    );
+}
+
+#[test]
+fn duplicate() {
+    let dir = temp_config(CONFIG);
+
+    let output = run(
+        dir.path(),
+        &["duplicate", "Moon landing", "Moon landing (UTC)"],
+    );
+    assert_eq!(
+        output,
+        "Day with label 'Moon landing (UTC)' added, same date as 'Moon landing': 1969-07-20\n"
+    );
+    let config = read_config(dir.path());
+    assert_eq!(config.days.len(), 4);
+    assert_eq!(config.days[3].label, "Moon landing (UTC)");
+    assert_eq!(config.days[3].date, config.days[0].date);
+
+    // doesn't overwrite an existing day
+    let output = run_with_stdin(
+        dir.path(),
+        &["duplicate", "Moon landing", "Berlin Wall Fall"],
+        "",
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("a day with label 'Berlin Wall Fall' is already in the config"));
+
+    let output = run_with_stdin(dir.path(), &["duplicate", "Nope", "New"], "");
+    assert!(!output.status.success());
+    let output = run_with_stdin(dir.path(), &["duplicate", "berln wall", "New"], "");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Error: No day with label 'berln wall', did you mean: 'Berlin Wall Fall'?\n"
+    );
+    assert_eq!(read_config(dir.path()).days.len(), 4);
+}
+
+#[test]
+fn rename() {
+    let dir = temp_config(CONFIG);
+
+    let output = run(
+        dir.path(),
+        &["rename", "apollo 11 launch ", "Apollo 11 launch"],
+    );
+    assert_eq!(
+        output,
+        "Day with label 'apollo 11 launch' renamed to 'Apollo 11 launch'\n"
+    );
+    let config = read_config(dir.path());
+    assert_eq!(config.days.len(), 3);
+    assert_eq!(config.days[2].label, "Apollo 11 launch");
+
+    let output = run_with_stdin(
+        dir.path(),
+        &["rename", "Moon landing", "berlin wall fall"],
+        "",
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("can't rename to 'berlin wall fall', another day has that label"));
+    let output = run_with_stdin(dir.path(), &["rename", "berln wall", "New"], "");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Error: No day with label 'berln wall', did you mean: 'Berlin Wall Fall'?\n"
+    );
+    assert_eq!(read_config(dir.path()).days[0].label, "Moon landing");
+
+    let output = run(dir.path(), &["mv", "Moon landing", "Moon Landing"]);
+    assert_eq!(
+        output,
+        "Day with label 'Moon landing' renamed to 'Moon Landing'\n"
+    );
+    assert_eq!(read_config(dir.path()).days[0].label, "Moon Landing");
+}
+
+#[test]
+fn remove() {
+    let dir = temp_config(CONFIG);
+
+    let output = run(dir.path(), &["remove", "berlin wall fall", "--yes"]);
+    assert_eq!(
+        output,
+        "Day with label 'Berlin Wall Fall' removed from config\n"
+    );
+    assert_eq!(read_config(dir.path()).days.len(), 2);
+
+    // stdin is not a terminal
+    let output = run_with_stdin(dir.path(), &["remove", "Moon landing"], "y\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("use --yes to remove"));
+    assert_eq!(read_config(dir.path()).days.len(), 2);
+
+    // not found, with suggestions
+    let output = run_with_stdin(dir.path(), &["remove", "Apollo 11", "--yes"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Error: No day with label 'Apollo 11', did you mean: 'apollo 11 launch'?\n"
+    );
+    let output = run_with_stdin(dir.path(), &["remove", "Graduation", "--yes"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Error: No day with label 'Graduation'\n"
+    );
+    assert_eq!(read_config(dir.path()).days.len(), 2);
+}
+
+#[test]
+fn remove_many() {
+    let dir = temp_config(CONFIG);
+
+    let output = run(
+        dir.path(),
+        &["remove", "Moon landing", "berlin wall fall", "--yes"],
+    );
+    assert_eq!(
+        output,
+        "Day with label 'Moon landing' removed from config\n\
+         Day with label 'Berlin Wall Fall' removed from config\n"
+    );
+    assert_eq!(labels(&read_config(dir.path())), ["apollo 11 launch"]);
+
+    // glob patterns, case-insensitive
+    let dir = temp_config(CONFIG);
+    run(dir.path(), &["remove", "--glob", "*L?NDING", "B*", "--yes"]);
+    assert_eq!(labels(&read_config(dir.path())), ["apollo 11 launch"]);
+
+    // partial failure removes the others, but exits with 1
+    let dir = temp_config(CONFIG);
+    let output = run_with_stdin(
+        dir.path(),
+        &["remove", "Moon landing", "Mars landing", "--yes"],
+        "",
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Error: No day with label 'Mars landing', did you mean: 'Moon landing'?\n"
+    );
+    assert_eq!(
+        labels(&read_config(dir.path())),
+        ["Berlin Wall Fall", "apollo 11 launch"]
+    );
+
+    // unless strict
+    let output = run_with_stdin(
+        dir.path(),
+        &[
+            "remove", "--glob", "--strict", "berlin *", "mars *", "--yes",
+        ],
+        "",
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Error: No day matches 'mars *'\n"
+    );
+    assert_eq!(read_config(dir.path()).days.len(), 2);
+}
+
+#[test]
+fn compare() {
+    let dir = temp_config(CONFIG);
+
+    // either order, earliest first
+    for args in [
+        ["compare", "Moon landing", "Berlin Wall Fall"],
+        ["compare", "berlin wall fall", "moon landing"],
+    ] {
+        let output = run(dir.path(), &args);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Moon landing:     1969-07-20, "));
+        assert!(lines[1].starts_with("Berlin Wall Fall: 1989-11-09, "));
+        assert_eq!(
+            lines[2],
+            "Moon landing was 20 years, 3 months, 3 weeks, 5 days before Berlin Wall Fall"
+        );
+    }
+
+    let output = run(
+        dir.path(),
+        &[
+            "compare",
+            "Moon landing",
+            "Moon landing",
+            "--format",
+            "json",
+        ],
+    );
+    let json: serde_json::Value = serde_json::from_str(&output).expect("Invalid JSON");
+    assert_eq!(json["earlier"]["label"], "Moon landing");
+    assert_eq!(json["gap"]["days"], 0);
+
+    let output = run_with_stdin(dir.path(), &["compare", "Moon landing", "Berln wall"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Error: No day with label 'Berln wall', did you mean: 'Berlin Wall Fall'?\n"
+    );
+}
+
+#[test]
+fn add_interactive() {
+    let dir = temp_config(r#"{"days": []}"#);
+
+    let output = run_with_stdin(dir.path(), &["add", "Moon landing"], "nope\n1969-07-20\n");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        "Enter date (YYYY-MM-DD or press Enter for today): \
+         Enter date (YYYY-MM-DD or press Enter for today): \
+         Day with label 'Moon landing' set to 1969-07-20\n"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("Invalid date 'nope'"));
+
+    let output = run_with_stdin(dir.path(), &["add", "Today"], "\n");
+    assert!(output.status.success());
+    let config = read_config(dir.path());
+    assert_eq!(achievements::days_since(config.days[1].date), 0);
+
+    let output = run_with_stdin(dir.path(), &["add", "Never"], "a\nb\nc\n1969-07-20\n");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.ends_with("Error: No valid date after 3 attempts\n"));
+    assert_eq!(read_config(dir.path()).days.len(), 2);
+}
+
+#[test]
+fn add_interactive_mode() {
+    let dir = temp_config(r#"{"days": []}"#);
+
+    let output = run_with_stdin(
+        dir.path(),
+        &["add", "--interactive"],
+        "\nWedding\n2021-06-15\nOur day\nfamily, party,\ny\n",
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Label: Label: Date (YYYY-MM-DD or 'today'): Description (optional): \
+         Tags (comma-separated, optional): Add 'Wedding' on 2021-06-15? [y/N]: \
+         Day with label 'Wedding' set to 2021-06-15\n"
+    );
+    let config = read_config(dir.path());
+    assert_eq!(config.days.len(), 1);
+    assert_eq!(config.days[0].description.as_deref(), Some("Our day"));
+    assert_eq!(config.days[0].tags, ["family", "party"]);
+
+    // Nothing written unless confirmed
+    let output = run_with_stdin(dir.path(), &["add", "-i", "Run"], "today\n\n\n\n");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).ends_with("Error: Aborted\n"));
+    assert_eq!(read_config(dir.path()).days.len(), 1);
+
+    // Existing labels are refused before asking for the rest
+    let output = run_with_stdin(dir.path(), &["add", "-i"], "wedding\n");
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "Label: ");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already exists"));
+
+    let output = run_with_stdin(dir.path(), &["add"], "");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn next() {
+    let dir = temp_config(
+        r#"{
+  "days": [
+    {"label": "Moon landing", "date": "1969-07-20T20:17:40+00:00"},
+    {"label": "First job", "date": "2010-03-01T09:00:00+00:00", "end": "2015-06-30T18:00:00+00:00"},
+    {"label": "Old hobby", "date": "2023-12-20T00:00:00+00:00", "archived": true},
+    {"label": "Berlin Wall Fall", "date": "1989-11-09T18:53:00+01:00"},
+    {"label": "Quit smoking", "date": "2021-04-18T00:00:00+00:00"}
+  ]
+}"#,
+    );
+
+    assert_eq!(
+        run(dir.path(), &["next", "--now", "2024-01-01"]),
+        "Quit smoking: 1000 days in 12 days
+Moon landing: 20000 days in 113 days
+Berlin Wall Fall: 35 years in 305 days
+First job: completed
+"
+    );
+    assert_eq!(
+        run(dir.path(), &["next", "--now", "2024-01-01", "--limit", "4"]),
+        run(dir.path(), &["next", "--now", "2024-01-01"])
+    );
+    assert_eq!(
+        run(dir.path(), &["next", "--now", "2024-01-01", "--limit", "2"]),
+        "Quit smoking: 1000 days in 12 days\nMoon landing: 20000 days in 113 days\n"
+    );
+    assert_eq!(
+        run(
+            dir.path(),
+            &["--lang", "italian", "next", "--now", "2024-01-01"]
+        ),
+        "Quit smoking: 1000 giorni tra 12 giorni
+Moon landing: 20000 giorni tra 113 giorni
+Berlin Wall Fall: 35 anni tra 305 giorni
+First job: completato
+"
+    );
+}
+
+#[test]
+fn top_upcoming() {
+    let dir = temp_config(CONFIG);
+
+    let output = run_at(dir.path(), "2024-01-01T00:00:00Z", &["top-upcoming"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "\
+apollo 11 launch: 19891 days now, 20000 days in 109 days
+Moon landing: 2841 weeks now, 20000 days in 113 days
+Berlin Wall Fall: 12470 days now, 35 years in 305 days
+"
+    );
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["top-upcoming", "--count", "1"],
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "apollo 11 launch: 19891 days now, 20000 days in 109 days\n"
+    );
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["--lang", "italian", "top-upcoming", "--count", "2"],
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "\
+apollo 11 launch: 19891 giorni finora, 20000 giorni tra 109 giorni
+Moon landing: 2841 settimane finora, 20000 giorni tra 113 giorni
+"
+    );
+
+    let dir = temp_config(r#"{"days": []}"#);
+    let output = run_at(dir.path(), "2024-01-01T00:00:00Z", &["top-upcoming"]);
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("No achievements tracked yet"));
+}
+
+#[test]
+fn stats() {
+    let dir = temp_config(CONFIG);
+
+    let output = run(dir.path(), &["stats"]);
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 6);
+    assert_eq!(lines[0], "Entries: 3");
+    assert!(lines[1].starts_with("Oldest:  apollo 11 launch, "));
+    assert!(lines[2].starts_with("Newest:  Berlin Wall Fall, "));
+    assert!(lines[5].starts_with("Tiers:   💎 3 · 🌟 0"));
+
+    let output = run(dir.path(), &["stats", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&output).expect("Invalid JSON");
+    assert_eq!(json["count"], 3);
+    assert_eq!(json["oldest"]["label"], "apollo 11 launch");
+    assert_eq!(json["newest"]["label"], "Berlin Wall Fall");
+    assert_eq!(json["tiers"]["decade"], 3);
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["--lang", "italian", "stats"],
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "\
+Entries: 3
+Oldest:  apollo 11 launch, 19891 giorni 💎💎💎💎💎 (dal 1969-07-16)
+Newest:  Berlin Wall Fall, 12470 giorni 💎💎💎 (dal 1989-11-09)
+Total:   52248 giorni
+Average: 17416 giorni
+Tiers:   💎 3 · 🌟 0 · ⭐ 0 · ★ 0 · ☆ 0
+"
+    );
+
+    let empty = temp_config(r#"{"days": []}"#);
+    assert_eq!(
+        run(empty.path(), &["stats"]),
+        "No achievements tracked yet — run 'achievements init' or 'achievements add' to get started.\n"
+    );
+}
+
+#[test]
+fn search() {
+    let dir = temp_config(
+        r#"{
+  "days": [
+    {"label": "Moon landing", "date": "1969-07-20T20:17:40Z", "tags": ["space"]},
+    {"label": "Berlin Wall Fall", "date": "1989-11-09T18:53:00+01:00", "description": "Fell on a Thursday"}
+  ]
+}"#,
+    );
+
+    let output = run(dir.path(), &["search", "WALL"]);
+    assert!(output.starts_with("Berlin Wall Fall: "), "{output}");
+    assert_eq!(output.lines().count(), 1);
+
+    let output = run(
+        dir.path(),
+        &["search", "thursday|space", "--regex", "--in", "notes,tags"],
+    );
+    assert_eq!(
+        achievement_labels(&output),
+        ["Moon landing", "Berlin Wall Fall"]
+    );
+
+    let output = run(dir.path(), &["search", "moon", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&output).expect("Invalid JSON");
+    assert_eq!(json[0]["label"], "Moon landing");
+
+    // no matches
+    let output = run_with_stdin(dir.path(), &["search", "thursday"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+
+    let output = run_with_stdin(dir.path(), &["search", "(", "--regex"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).starts_with("Error: Invalid regular expression")
+    );
+}
+
+#[test]
+fn import() {
+    let import = |on_conflict: &str, file: &str, content: &str| {
+        let dir = temp_config(CONFIG);
+        let path = dir.path().join(file);
+        fs::write(&path, content).expect("Failed to write import file");
+        let mut args = vec![
+            "import",
+            path.to_str().unwrap(),
+            "--on-conflict",
+            on_conflict,
+        ];
+        if file.ends_with(".csv") {
+            args.extend(["--format", "csv"]);
+        }
+        let output = run_with_stdin(dir.path(), &args, "");
+        (dir, output)
+    };
+    let json = r#"{"days": [
+        {"label": "Moon landing", "date": "2000-01-01T00:00:00Z"},
+        {"label": "New day", "date": "2020-02-02T00:00:00Z"}
+    ]}"#;
+
+    let (dir, output) = import("skip", "import.json", json);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Skipped 'Moon landing': already in the config\n1 added, 0 updated, 1 skipped\n"
+    );
+    let config = read_config(dir.path());
+    assert_eq!(config.days.len(), 4);
+    assert_eq!(config.days[0].date.year(), 1969);
+    assert_eq!(config.days[3].label, "New day");
+
+    let (dir, output) = import("overwrite", "import.json", json);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "1 added, 1 updated, 0 skipped\n"
+    );
+    assert_eq!(read_config(dir.path()).days[0].date.year(), 2000);
+
+    let (dir, output) = import("abort", "import.json", json);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'Moon landing'"));
+    assert_eq!(read_config(dir.path()).days.len(), 3);
+
+    let (dir, output) = import(
+        "overwrite",
+        "import.csv",
+        "label,date\nNew day,2020-02-02\nMoon landing,not a date\n",
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid day on row 2"));
+    let config = read_config(dir.path());
+    assert_eq!(config.days.len(), 3);
+    assert_eq!(config.days[0].date.year(), 1969);
+
+    let (dir, output) = import("skip", "import.csv", "label,date\nNew day,2020-02-02\n");
+    assert!(output.status.success());
+    assert_eq!(read_config(dir.path()).days[3].label, "New day");
+    // Same label but for case and whitespace
+    let (dir, output) = import(
+        "skip",
+        "import.json",
+        r#"{"days": [{"label": " moon LANDING", "date": "2000-01-01T00:00:00Z"}]}"#,
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Skipped 'Moon landing': already in the config\n0 added, 0 updated, 1 skipped\n"
+    );
+    assert_eq!(read_config(dir.path()).days.len(), 3);
+    assert_eq!(
+        run(dir.path(), &["config", "validate"]),
+        "Config is valid, 3 days\n"
+    );
+}
+
+#[test]
+fn export() {
+    let dir = temp_config(CSV_CONFIG);
+
+    for (format, file) in [
+        ("json", "export.json"),
+        ("toml", "export.toml"),
+        ("csv", "export.csv"),
+    ] {
+        let path = dir.path().join(file);
+        let path = path.to_str().unwrap();
+        let output = run(
+            dir.path(),
+            &["export", "--format", format, "--output", path],
+        );
+        assert_eq!(output, format!("Exported 2 days to {path}\n"));
+
+        // importing the export into an empty config gives the same config
+        let other = temp_config(r#"{"days": []}"#);
+        run(other.path(), &["import", "--format", format, path]);
+        assert_eq!(
+            read_config(other.path()),
+            read_config(dir.path()),
+            "{format} export doesn't round-trip"
+        );
+    }
+
+    let csv = fs::read_to_string(dir.path().join("export.csv")).unwrap();
+    assert!(csv.starts_with("label,date,description,tags\n"));
+
+    // stdout, JSON is exactly the config schema
+    let json = run(dir.path(), &["export"]);
+    assert_eq!(
+        Config::from_json_str(&json).unwrap(),
+        read_config(dir.path())
+    );
+}
+
+#[test]
+fn export_tag() {
+    let dir = temp_config(
+        r#"{
+  "days": [
+    {"label": "Moon landing", "date": "1969-07-20T20:17:40Z", "tags": ["space"]},
+    {"label": "Berlin Wall Fall", "date": "1989-11-09T18:53:00+01:00", "tags": ["europe"]},
+    {"label": "Run", "date": "2020-01-01T00:00:00Z"}
+  ]
+}"#,
+    );
+
+    let labels = |args: &[&str]| -> Vec<String> {
+        let json = run(dir.path(), args);
+        let config = Config::from_json_str(&json).expect("Invalid JSON");
+        config.days.into_iter().map(|day| day.label).collect()
+    };
+    assert_eq!(labels(&["export", "--tag", "Space"]), ["Moon landing"]);
+    assert_eq!(
+        labels(&["export", "--tag", "space", "--tag", "europe"]),
+        ["Moon landing", "Berlin Wall Fall"]
+    );
+    assert!(labels(&["export", "--tag", "nope"]).is_empty());
+}
+
+#[test]
+fn export_empty() {
+    let dir = temp_config(r#"{"days": []}"#);
+
+    assert_eq!(run(dir.path(), &["export"]), "{\n  \"days\": []\n}\n");
+    assert_eq!(
+        run(dir.path(), &["export", "--format", "csv"]),
+        "label,date,description,tags\n"
+    );
+    let toml = run(dir.path(), &["export", "--format", "toml"]);
+    assert_eq!(Config::from_toml_str(&toml).unwrap(), Config::default());
+}
+
+#[test]
+fn completions() {
+    let dir = temp_config(CONFIG);
+
+    for shell in ["bash", "zsh", "fish", "powershell"] {
+        let script = run(dir.path(), &["completions", shell]);
+        for subcommand in ["achievements", "list", "add", "remove", "config"] {
+            assert!(
+                script.contains(subcommand),
+                "{shell} completions without '{subcommand}'"
+            );
+        }
+    }
+    let fish = run(dir.path(), &["completions", "fish"]);
+    assert!(fish.contains("-a '(achievements __labels)'"));
+
+    assert_eq!(
+        run(dir.path(), &["__labels"]),
+        "Moon landing\nBerlin Wall Fall\napollo 11 launch\n"
+    );
+}
+
+#[test]
+fn man() {
+    let dir = temp_config(CONFIG);
+
+    let page = run(dir.path(), &["man"]);
+    for expected in [
+        ".TH achievements 1",
+        ".SH \"CONFIG FILE\"",
+        ".SH \"ENVIRONMENT\"",
+        "ACHIEVEMENTS_CONFIG",
+        "~/.config/achievements/config.json",
+        "upcoming",
+        "export",
+    ] {
+        assert!(page.contains(expected), "man page without '{expected}'");
+    }
+
+    // the same text is in the long help
+    let help = run(dir.path(), &["--help"]);
+    assert!(help.contains("Config file:\n  The days are stored as JSON in the first of:"));
+    assert!(help.contains("ACHIEVEMENTS_CONFIG  Path of the config file"));
+
+    let pages = dir.path().join("man");
+    fs::create_dir(&pages).unwrap();
+    run(
+        dir.path(),
+        &["man", "--output-dir", pages.to_str().unwrap()],
+    );
+    for page in [
+        "achievements.1",
+        "achievements-list.1",
+        "achievements-config-path.1",
+    ] {
+        assert!(pages.join(page).exists(), "{page} not generated");
+    }
+    assert!(!pages.join("achievements-__labels.1").exists());
+}
+
+#[test]
+fn init() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = dir.path().join("config.json");
+
+    let output = run(dir.path(), &[]);
+    assert!(output.starts_with("No achievements tracked yet — run 'achievements init'"));
+
+    let output = run(dir.path(), &["init"]);
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(
+        lines[0],
+        format!("Config created at {}", config_path.display())
+    );
+    assert_eq!(lines[1], "");
+    assert!(lines[2].starts_with("Moon landing: "));
+    assert!(lines[3].starts_with("Installed achievements: Recently"));
+    assert_eq!(read_config(dir.path()).days.len(), 2);
+
+    // refuses to overwrite
+    fs::write(&config_path, CONFIG).unwrap();
+    let output = run_with_stdin(dir.path(), &["init"], "");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("use --force"));
+    assert_eq!(read_config(dir.path()).days.len(), 3);
+
+    // unless forced, keeping a backup
+    let output = run(dir.path(), &["init", "--force", "--empty"]);
+    assert_eq!(
+        output,
+        format!("Config created at {}\n", config_path.display())
+    );
+    assert_eq!(read_config(dir.path()), Config::default());
+    let backup = fs::read_to_string(dir.path().join("config.json.bak")).unwrap();
+    assert_eq!(backup, CONFIG);
+}
+
+#[cfg(unix)]
+#[test]
+fn doctor() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = temp_config(CONFIG);
+    let config_path = dir.path().join("config.json");
+    fs::set_permissions(&config_path, fs::Permissions::from_mode(0o600)).unwrap();
+
+    let output = run(dir.path(), &["doctor"]);
+    assert_eq!(
+        output,
+        format!(
+            "✓ Config file: {} exists
+✓ Permissions: 600
+✓ JSON: valid, 3 days
+✓ Schema version: 1 (implicit)
+✓ Days: no issues
+",
+            config_path.display()
+        )
+    );
+
+    // duplicates, untrimmed labels and world-readable file
+    let config = r#"{
+  "days": [
+    {"label": "Moon landing", "date": "1969-07-20T20:17:40Z"},
+    {"label": "Moon landing ", "date": "1969-07-20T20:17:40Z"}
+  ]
+}"#;
+    fs::write(&config_path, config).unwrap();
+    fs::set_permissions(&config_path, fs::Permissions::from_mode(0o644)).unwrap();
+    let output = run_with_stdin(dir.path(), &["doctor"], "");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("! Permissions: 644, the config is readable by other users"));
+    assert!(stdout.contains("! Days: duplicate label 'Moon landing'"));
+
+    fs::set_permissions(&config_path, fs::Permissions::from_mode(0o600)).unwrap();
+    let output = run(dir.path(), &["doctor", "--fix"]);
+    assert!(
+        output.starts_with("Trimmed label 'Moon landing'\nRemoved duplicate of 'Moon landing'\n")
+    );
+    assert!(output.contains("✓ Days: no issues"));
+    assert_eq!(read_config(dir.path()).days.len(), 1);
+    let backup = fs::read_to_string(dir.path().join("config.json.bak")).unwrap();
+    assert_eq!(backup, config);
+
+    // invalid JSON can't be fixed
+    fs::write(&config_path, "not JSON").unwrap();
+    let output = run_with_stdin(dir.path(), &["doctor", "--fix"], "");
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("✗ JSON: invalid config JSON"));
+    assert!(!stdout.contains("Days:"));
+}
+
+#[test]
+fn achievements_on() {
+    let dir = temp_config(CONFIG);
+
+    let in_1989 = run(dir.path(), &["achievements", "--on", "1989-11-10"]);
+    assert_eq!(achievement_labels(&in_1989).len(), 3);
+
+    let output = run_with_stdin(dir.path(), &["--on", "1980-01-01", "achievements"], "");
+    assert!(output.status.success(), "{output:?}");
+    let in_1980 = String::from_utf8_lossy(&output.stdout);
+    assert_ne!(in_1980, in_1989);
+    assert!(!achievement_labels(&in_1980).contains(&"Berlin Wall Fall"));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Skipped 'Berlin Wall Fall', it's after 1980-01-01\n"
+    );
+
+    // relative dates, same as with `add --at`
+    let stats = run(dir.path(), &["stats", "--on", "1 year ago"]);
+    assert_ne!(stats, run(dir.path(), &["stats"]));
+}
+
+/// Same as `run()` with "now" pinned to `now`, see `ACHIEVEMENTS_NOW`
+fn run_at(dir: &Path, now: &str, args: &[&str]) -> Output {
+    achievements_command()
+        .args(args)
+        .env("ACHIEVEMENTS_CONFIG", dir.join("config.json"))
+        .env("ACHIEVEMENTS_NOW", now)
+        .output()
+        .expect("Failed to run achievements")
+}
+
+#[test]
+fn snapshot() {
+    let dir = temp_config(CONFIG);
+    let stdout = |args: &[&str]| {
+        let output = run_at(dir.path(), "2024-01-01T00:00:00Z", args);
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).expect("Output is not UTF-8")
+    };
+
+    assert_eq!(
+        stdout(&[]),
+        "\
+Moon landing:     2841 weeks 💎💎💎💎💎
+Berlin Wall Fall: 12470 days 💎💎💎
+apollo 11 launch: 19891 days 💎💎💎💎💎
+"
+    );
+    assert_eq!(
+        stdout(&["stats"]),
+        "\
+Entries: 3
+Oldest:  apollo 11 launch, 19891 days 💎💎💎💎💎 (since 1969-07-16)
+Newest:  Berlin Wall Fall, 12470 days 💎💎💎 (since 1989-11-09)
+Total:   52248 days
+Average: 17416 days
+Tiers:   💎 3 · 🌟 0 · ⭐ 0 · ★ 0 · ☆ 0
+"
+    );
+    assert_eq!(
+        stdout(&["next"]),
+        "\
+apollo 11 launch: 20000 days in 109 days
+Moon landing: 20000 days in 113 days
+Berlin Wall Fall: 35 years in 305 days
+"
+    );
+
+    let output = run_at(dir.path(), "yesterday", &["stats"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid ACHIEVEMENTS_NOW"));
+}
+
+#[test]
+fn achievements_color() {
+    let dir = temp_config(CONFIG);
+
+    // stdout is not a terminal here
+    assert!(!run(dir.path(), &["achievements"]).contains('\x1b'));
+    let output = run(dir.path(), &["achievements", "--color", "always"]);
+    assert!(output.starts_with("\x1b[1mMoon landing:\x1b[0m"));
+    assert!(!run(dir.path(), &["achievements", "--color", "never"]).contains('\x1b'));
+
+    for format in ["json", "csv"] {
+        let output = run(
+            dir.path(),
+            &["achievements", "--color", "always", "--format", format],
+        );
+        assert!(!output.contains('\x1b'));
+    }
+}
+
+#[test]
+fn calendar() {
+    let dir = temp_config(CONFIG);
+
+    let output = run_at(dir.path(), "2024-01-01T00:00:00Z", &["calendar"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0].trim(), "2024");
+    assert!(lines[24].starts_with("15  C 17 18 19  A 21"), "{stdout}");
+    assert!(stdout.ends_with(
+        "
+ A Moon landing (July 20)
+ B Berlin Wall Fall (November 9)
+ C apollo 11 launch (July 16)
+"
+    ));
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["calendar", "--year", "1970", "--plain"],
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).ends_with(
+        "
+ A Moon landing (July 20)
+ C apollo 11 launch (July 16)
+"
+    ));
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["calendar", "--year", "0"],
+    );
+    assert_eq!(output.status.code(), Some(2));
+
+    let dir = temp_config(r#"{"days": []}"#);
+    let output = run_at(dir.path(), "2024-01-01T00:00:00Z", &["calendar"]);
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("No achievements tracked yet"));
+}
+
+#[test]
+fn chart() {
+    let dir = temp_config(CONFIG);
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["chart", "--width", "60", "--sort", "days", "--plain"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "\
+################### Berlin Wall Fall (12470 days)
+############################## Moon landing (19887 days)
+############################## apollo 11 launch (19891 days)
+"
+    );
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["chart", "--width", "9"],
+    );
+    assert_eq!(output.status.code(), Some(2));
+
+    let dir = temp_config(r#"{"days": []}"#);
+    let output = run_at(dir.path(), "2024-01-01T00:00:00Z", &["chart"]);
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("No achievements tracked yet"));
+}
+
+#[test]
+fn timeline() {
+    let dir = temp_config(CONFIG);
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["timeline", "--width", "40"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "\
+* Moon landing * Berlin Wall Fall
+* apollo 11 launch
+*------+-------*------+-------------+---
+       1980           2000          2020
+"
+    );
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["timeline", "--width", "9"],
+    );
+    assert_eq!(output.status.code(), Some(2));
+
+    let dir = temp_config(r#"{"days": []}"#);
+    let output = run_at(dir.path(), "2024-01-01T00:00:00Z", &["timeline"]);
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("No achievements tracked yet"));
+}
+
+#[test]
+fn watch_interval_bounds() {
+    let dir = temp_config(CONFIG);
+
+    for interval in ["0", "86401", "soon"] {
+        let output = run_with_stdin(dir.path(), &["watch", "--interval", interval], "");
+        assert_eq!(output.status.code(), Some(2), "{output:?}");
+        assert!(String::from_utf8_lossy(&output.stderr).contains("--interval"));
+    }
+}
+
+#[test]
+fn show() {
+    let dir = temp_config(CONFIG);
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["show", " berlin wall fall"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Berlin Wall Fall: 9 November 1989 18:53:00, Thursday, 34 years, 2 months (12470 days total)\n"
+    );
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &[
+            "--lang",
+            "italian",
+            "show",
+            "Moon landing",
+            "--date-format",
+            "medium",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Moon landing: 20 lug 1969 20:17:40, domenica, \
+         54 anni, 5 mesi, 3 settimane, 6 giorni (19887 giorni in tutto)\n"
+    );
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["show", "Mars landing"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+
+    // No picker without a terminal
+    for args in [&["show"][..], &["remove"]] {
+        let output = run_with_stdin(dir.path(), args, "1\ny\n");
+        assert_eq!(output.status.code(), Some(1), "{output:?}");
+        assert_eq!(
+            String::from_utf8_lossy(&output.stderr),
+            "Error: No label given, one is required when stdin is not a terminal\n"
+        );
+    }
+    assert_eq!(
+        fs::read_to_string(dir.path().join("config.json")).unwrap(),
+        CONFIG
+    );
+}
+
+#[test]
+fn list_verbose() {
+    let dir = temp_config(
+        r#"{
+  "days": [
+    {
+      "label": "Moon landing",
+      "date": "1969-07-20T20:17:40+00:00",
+      "description": "One small step",
+      "tags": ["space"],
+      "archived": true
+    },
+    {
+      "label": "New year",
+      "date": "2024-01-01T00:00:00+00:00"
+    }
+  ]
+}"#,
+    );
+
+    let output = run_at(dir.path(), "2024-01-01T12:00:00Z", &["list", "--verbose"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "\
+Moon landing: 1969-07-20 20:17:40, Sunday, 54 years, 5 months, 3 weeks, 6 days (19887 days total)
+  tags: space
+  notes: One small step
+  archived
+New year: 2024-01-01, Monday, Recently (0 days total)
+"
+    );
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T12:00:00Z",
+        &["list", "--format", "json"],
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Invalid JSON");
+    assert_eq!(
+        json,
+        serde_json::json!([
+            {
+                "label": "Moon landing",
+                "date": "1969-07-20T20:17:40Z",
+                "description": "One small step",
+                "tags": ["space"],
+                "archived": true,
+                "days": 19887
+            },
+            {
+                "label": "New year",
+                "date": "2024-01-01T00:00:00Z",
+                "days": 0
+            }
+        ])
+    );
+
+    // the default output doesn't change
+    assert_eq!(
+        list_labels(&run(dir.path(), &["list"])),
+        ["Moon landing", "New year"]
+    );
+}
+
+#[test]
+fn config_flag() {
+    let dir = temp_config(CONFIG);
+    let other = tempfile::tempdir().expect("Failed to create temp dir");
+    let other_path = other.path().join("other.json");
+    let other_path = other_path.to_str().expect("Path is not UTF-8");
+
+    // over $ACHIEVEMENTS_CONFIG, before or after the subcommand
+    let output = run(dir.path(), &["--config", other_path, "config", "path"]);
+    assert_eq!(output, format!("{other_path}\n"));
+    run(
+        dir.path(),
+        &["add", "Launch", "--today", "--config", other_path],
+    );
+
+    let config = Config::read_from_path(Path::new(other_path)).expect("Failed to read config");
+    assert_eq!(labels(&config), ["Launch"]);
+    assert_eq!(labels(&read_config(dir.path())).len(), 3);
+}
+
+#[test]
+fn aliases_and_top_level_args() {
+    let dir = temp_config(CONFIG);
+
+    assert_eq!(run(dir.path(), &["ls"]), run(dir.path(), &["list"]));
+    run(dir.path(), &["new", "Launch", "--days-ago", "3"]);
+    run(dir.path(), &["rm", "Launch", "--yes"]);
+    assert_eq!(labels(&read_config(dir.path())).len(), 3);
+
+    // the achievements args without the subcommand
+    assert_eq!(
+        run(dir.path(), &["--sort", "label"]),
+        run(dir.path(), &["achievements", "--sort", "label"])
+    );
+    assert_eq!(
+        achievement_labels(&run(dir.path(), &["--sort", "label", "--limit", "1"])),
+        ["apollo 11 launch"]
+    );
+
+    let output = run_with_stdin(dir.path(), &["--sort", "label", "list"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("--sort can't be used with a subcommand")
+    );
+}
+
+#[test]
+fn list_with_elapsed() {
+    let dir = temp_config(CONFIG);
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["list", "--with-elapsed"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "\
+'Moon landing':     1969-07-20 20:17:40.0 +00:00:00 2841 weeks
+'Berlin Wall Fall': 1989-11-09 18:53:00.0 +01:00:00 12470 days
+'apollo 11 launch': 1969-07-16 13:32:00.0 +00:00:00 19891 days
+"
+    );
+    assert_eq!(
+        list_labels(&String::from_utf8_lossy(&output.stdout)).len(),
+        3
+    );
+}
+
+#[test]
+fn export_ics() {
+    let dir = temp_config(CONFIG);
+    let export = |args: &[&str]| {
+        let output = run_at(dir.path(), "2024-01-01T00:00:00Z", args);
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).expect("Output is not UTF-8")
+    };
+
+    let ics = export(&["export", "--format", "ics"]);
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    let summaries: Vec<&str> = ics
+        .lines()
+        .filter_map(|line| line.strip_prefix("SUMMARY:"))
+        .map(|line| line.trim_end_matches('\r'))
+        .collect();
+    assert_eq!(
+        summaries,
+        [
+            "apollo 11 launch — 55 years",
+            "Moon landing — 55 years",
+            "Berlin Wall Fall — 35 years"
+        ]
+    );
+    assert!(ics.contains("DTSTART;VALUE=DATE:20240720\r\n"));
+
+    // same UIDs in every export
+    let uids = |ics: &str| -> Vec<String> {
+        ics.lines()
+            .filter(|line| line.starts_with("UID:"))
+            .map(str::to_string)
+            .collect()
+    };
+    assert_eq!(uids(&ics), uids(&export(&["export", "--format", "ics"])));
+
+    let ics = export(&["export", "--format", "ics", "--years", "2"]);
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 6);
+}
+
+#[test]
+fn remove_matching() {
+    let dir = temp_config(CONFIG);
+
+    let output = run(
+        dir.path(),
+        &["remove", "--all", "--matching", "LA", "--dry-run"],
+    );
+    assert_eq!(
+        output,
+        "Would remove 'Moon landing' (1969-07-20)\n\
+         Would remove 'apollo 11 launch' (1969-07-16)\n"
+    );
+    assert_eq!(labels(&read_config(dir.path())).len(), 3);
+
+    run(
+        dir.path(),
+        &["remove", "--all", "--matching", "*fall", "--yes"],
+    );
+    assert_eq!(
+        labels(&read_config(dir.path())),
+        ["Moon landing", "apollo 11 launch"]
+    );
+
+    let output = run_with_stdin(
+        dir.path(),
+        &["remove", "--all", "--matching", "mars", "--yes"],
+        "",
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Error: No day matches 'mars'\n"
+    );
+
+    // an empty pattern would remove every day
+    for pattern in ["", "  "] {
+        let output = run_with_stdin(
+            dir.path(),
+            &["remove", "--all", "--matching", pattern, "--yes"],
+            "",
+        );
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(
+            String::from_utf8_lossy(&output.stderr),
+            "Error: Invalid --matching: the pattern is empty, it would match every day\n"
+        );
+    }
+    assert_eq!(labels(&read_config(dir.path())).len(), 2);
+
+    // --matching needs --all, and the other way around
+    let output = run_with_stdin(dir.path(), &["remove", "--matching", "moon", "--yes"], "");
+    assert_eq!(output.status.code(), Some(2));
+    let output = run_with_stdin(dir.path(), &["remove", "--all", "--yes"], "");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn config_import() {
+    let dir = temp_config(CONFIG);
+    let backup = dir.path().join("backup.json");
+    fs::write(
+        &backup,
+        r#"{"days": [
+            {"label": "Moon landing", "date": "2000-01-01T00:00:00Z"},
+            {"label": "New day", "date": "2020-02-02T00:00:00Z", "tags": ["new"]}
+        ]}"#,
+    )
+    .expect("Failed to write backup");
+    let path = backup.to_str().unwrap();
+
+    let output = run(dir.path(), &["config", "import", path]);
+    assert_eq!(
+        output,
+        "Skipped 'Moon landing': already in the config\n1 added, 0 updated, 1 skipped\n"
+    );
+    let config = read_config(dir.path());
+    assert_eq!(config.days.len(), 4);
+    assert_eq!(config.days[0].date.year(), 1969);
+    assert_eq!(config.days[3].tags, ["new"]);
+
+    let output = run(dir.path(), &["config", "import", path, "--overwrite"]);
+    assert_eq!(output, "0 added, 2 updated, 0 skipped\n");
+    let config = read_config(dir.path());
+    assert_eq!(config.days.len(), 4);
+    assert_eq!(config.days[0].date.year(), 2000);
+
+    let output = run_with_stdin(dir.path(), &["config", "import", "missing.json"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).starts_with("Error: Can't import missing.json: ")
+    );
+    assert_eq!(read_config(dir.path()).days.len(), 4);
+}
+
+#[test]
+fn import_ics() {
+    let dir = temp_config(CONFIG);
+    let path = dir.path().join("calendar.ics");
+    fs::write(
+        &path,
+        "BEGIN:VCALENDAR\r
+BEGIN:VEVENT\r
+SUMMARY:Moon landing\r
+DTSTART;VALUE=DATE:20000101\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+SUMMARY:First marathon\\, Rome\r
+DTSTART:20190407T083000Z\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+SUMMARY:Dentist appointment with a folded summ\r
+ ary\r
+DTSTART;TZID=Europe/Rome:20240102T093000\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+SUMMARY:No start\r
+END:VEVENT\r
+END:VCALENDAR\r
+",
+    )
+    .expect("Failed to write calendar");
+    let path = path.to_str().unwrap();
+
+    let output = run_with_stdin(
+        dir.path(),
+        &["import", "--format", "ics", path, "--filter", "MARATHON"],
+        "",
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Warning: skipped event 4: 'No start' has no DTSTART\n"
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "1 added, 0 updated, 0 skipped\n"
+    );
+
+    let output = run(
+        dir.path(),
+        &[
+            "import",
+            "--format",
+            "ics",
+            path,
+            "--on-conflict",
+            "overwrite",
+        ],
+    );
+    assert_eq!(output, "1 added, 2 updated, 0 skipped\n");
+    let config = read_config(dir.path());
+    assert_eq!(
+        labels(&config),
+        [
+            "Moon landing",
+            "Berlin Wall Fall",
+            "apollo 11 launch",
+            "First marathon, Rome",
+            "Dentist appointment with a folded summary"
+        ]
+    );
+    assert_eq!(config.days[0].date.year(), 2000);
+}
+
+#[test]
+fn achievements_short() {
+    let dir = temp_config(CONFIG);
+    let stdout = |args: &[&str]| {
+        let output = run_at(dir.path(), "2024-01-01T00:00:00Z", args);
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).expect("Output is not UTF-8")
+    };
+
+    assert_eq!(
+        stdout(&["--short"]),
+        "\
+Moon landing:     2841wk 💎💎💎💎💎
+Berlin Wall Fall: 12470d 💎💎💎
+apollo 11 launch: 19891d 💎💎💎💎💎
+"
+    );
+
+    let json = stdout(&["achievements", "--short", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&json).expect("Invalid JSON");
+    assert_eq!(json[0]["words"], "2841wk");
+
+    let csv = stdout(&["achievements", "--short", "--format", "csv"]);
+    assert!(csv.contains(",2841wk\n"));
+    let markdown = stdout(&["achievements", "--short", "--format", "markdown-list"]);
+    assert!(markdown.contains("2841wk"));
+}
+
+#[test]
+fn prompt() {
+    let dir = temp_config(
+        r#"{
+  "days": [
+    {"label": "Running", "date": "2022-11-15T12:00:00Z", "icon": "🏃"},
+    {"label": "Wedding", "date": "2021-01-01T00:00:00Z", "icon": "💍"},
+    {"label": "moon landing", "date": "1969-07-20T20:17:40Z"},
+    {"label": "Old job", "date": "2015-01-01T00:00:00Z", "archived": true}
+  ]
+}"#,
+    );
+    let prompt = |args: &[&str]| {
+        let mut args = args.to_vec();
+        args.insert(0, "prompt");
+        let output = run_at(dir.path(), "2024-01-01T12:00:00Z", &args);
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8(output.stdout).expect("Output is not UTF-8")
+    };
+
+    assert_eq!(prompt(&[]), "🏃 412d · 💍 3y · m 2841wk\n");
+    assert_eq!(prompt(&["--max", "2", "-n"]), "🏃 412d · 💍 3y");
+    assert_eq!(
+        prompt(&["--entries", "wedding,Old job,Mars", "-n"]),
+        "💍 3y · O 3287d"
+    );
+
+    // nothing at all without a config, nor creating its directory
+    let missing = dir.path().join("missing").join("config.json");
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T12:00:00Z",
+        &["prompt", "--config", missing.to_str().unwrap()],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert!(output.stdout.is_empty());
+    assert!(!dir.path().join("missing").exists());
+}
+
+#[test]
+fn bar() {
+    let dir = temp_config(CONFIG);
+    let bar = |args: &[&str]| -> serde_json::Value {
+        let mut args = args.to_vec();
+        args.insert(0, "bar");
+        let output = run_at(dir.path(), "2024-01-01T00:00:00Z", &args);
+        assert!(output.status.success(), "{output:?}");
+        let stdout = String::from_utf8(output.stdout).expect("Output is not UTF-8");
+        assert_eq!(stdout.lines().count(), 1);
+        serde_json::from_str(&stdout).expect("Invalid JSON")
+    };
+
+    let module = bar(&[]);
+    assert_eq!(module["text"], "a 19891d");
+    assert_eq!(module["class"], "achievements");
+    assert_eq!(
+        module["tooltip"],
+        "Moon landing: 2841 weeks 💎💎💎💎💎\n\
+         Berlin Wall Fall: 12470 days 💎💎💎\n\
+         apollo 11 launch: 19891 days 💎💎💎💎💎"
+    );
+    assert_eq!(bar(&["--entry", "moon landing"])["text"], "M 2841wk");
+
+    let dir = temp_config(r#"{"days": []}"#);
+    let output = run(dir.path(), &["bar"]);
+    assert_eq!(
+        output,
+        "{\"text\":\"\",\"tooltip\":\"\",\"class\":\"achievements\"}\n"
+    );
+}
+
+#[test]
+fn notify() {
+    let dir = temp_config(
+        r#"{
+  "days": [
+    {"label": "Wedding", "date": "2019-01-02T12:00:00Z"},
+    {"label": "Moved", "date": "2023-09-24T12:00:00Z"},
+    {"label": "Running", "date": "2021-04-06T12:00:00Z"},
+    {"label": "Old job", "date": "2023-01-01T12:00:00Z", "end": "2023-06-30T12:00:00Z"},
+    {"label": "Smoking", "date": "2023-09-23T12:00:00Z", "archived": true}
+  ]
+}"#,
+    );
+
+    let output = run_at(dir.path(), "2024-01-01T18:00:00Z", &["notify", "--dry-run"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Would notify: 🎉 Wedding — 5 years today\n\
+         Would notify: 🎉 Running — 1000 days today\n"
+    );
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T18:00:00Z",
+        &["--lang", "italian", "notify", "--dry-run"],
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Would notify: 🎉 Wedding — 5 anni oggi\n\
+         Would notify: 🎉 Running — 1000 giorni oggi\n"
+    );
+
+    // nothing to notify, not even without the notifications feature
+    let output = run_at(dir.path(), "2024-01-03T18:00:00Z", &["notify"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(output.stdout.is_empty());
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn webhook_nothing_to_send() {
+    let dir = temp_config(CONFIG);
+
+    // no milestone today, no request (the port is closed)
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["webhook", "http://127.0.0.1:9/hook"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert!(output.stdout.is_empty());
+
+    let output = run_with_stdin(
+        dir.path(),
+        &["webhook", "http://127.0.0.1:9/hook", "--header", "nope"],
+        "",
+    );
+    assert!(!output.status.success());
+}
+
+/// Accepts one request on a local port, replying with `status`, returns
+/// the URL and the handle to get the request head and body
+#[cfg(feature = "webhook")]
+fn webhook_stub(status: &str) -> (String, std::thread::JoinHandle<(String, String)>) {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+    };
+
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind");
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let status = status.to_string();
+
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("Failed to accept");
+        let mut reader = BufReader::new(stream);
+        let mut head = String::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).expect("Failed to read request");
+            if line == "\r\n" {
+                break;
+            }
+            head.push_str(&line);
+        }
+        let length: usize = head
+            .lines()
+            .find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.eq_ignore_ascii_case("content-length")
+                    .then(|| value.trim().parse().unwrap())
+            })
+            .expect("No Content-Length");
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).expect("Failed to read body");
+
+        let response =
+            format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+        let mut stream = reader.into_inner();
+        stream
+            .write_all(response.as_bytes())
+            .expect("Failed to reply");
+
+        (head, String::from_utf8(body).expect("Body is not UTF-8"))
+    });
+
+    (url, handle)
+}
+
+#[cfg(feature = "webhook")]
+#[test]
+fn webhook() {
+    let dir = temp_config(
+        r#"{
+  "days": [
+    {"label": "Wedding", "date": "2019-01-02T12:00:00Z"},
+    {"label": "Moved", "date": "2023-09-24T12:00:00Z"}
+  ]
+}"#,
+    );
+
+    let (url, stub) = webhook_stub("200 OK");
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T18:00:00Z",
+        &["webhook", &url, "--header", "Authorization: Bearer s3cret"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let (head, body) = stub.join().unwrap();
+    assert!(head.starts_with("POST /hook HTTP/1.1\r\n"), "{head}");
+    assert!(head.contains("Authorization: Bearer s3cret\r\n"), "{head}");
+    let json: serde_json::Value = serde_json::from_str(&body).expect("Invalid JSON");
+    assert_eq!(json["sent_at"], "2024-01-01T18:00:00Z");
+    assert_eq!(json["achievements"].as_array().unwrap().len(), 1);
+    assert_eq!(json["achievements"][0]["label"], "Wedding");
+    assert_eq!(json["achievements"][0]["milestone"], "5 years");
+
+    let (url, stub) = webhook_stub("503 Service Unavailable");
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T18:00:00Z",
+        &["webhook", &url, "--all"],
+    );
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Error: Webhook responded 503 Service Unavailable\n"
+    );
+    let (_, body) = stub.join().unwrap();
+    let json: serde_json::Value = serde_json::from_str(&body).expect("Invalid JSON");
+    assert_eq!(json["achievements"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn only_milestones() {
+    let dir = temp_config(
+        r#"{
+  "days": [
+    {"label": "Wedding", "date": "2019-01-02T12:00:00Z"},
+    {"label": "Moved", "date": "2023-12-18T12:00:00Z"},
+    {"label": "Running", "date": "2021-04-06T12:00:00Z"},
+    {"label": "Smoking", "date": "2023-09-23T12:00:00Z", "archived": true}
+  ]
+}"#,
+    );
+    let milestone_day = "2024-01-01T18:00:00Z";
+    let other_day = "2024-01-03T18:00:00Z";
+
+    // 2 weeks since moving is a whole unit but not a round milestone
+    let output = run_at(dir.path(), milestone_day, &["--only-milestones", "--plain"]);
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Wedding: 5 years   @@@@@\n\
+         Running: 1000 days @@\n"
+    );
+    let output = run_at(
+        dir.path(),
+        milestone_day,
+        &["--only-milestones", "--exit-code"],
+    );
+    assert!(output.status.success(), "{output:?}");
+
+    for args in [
+        &["--only-milestones"][..],
+        &["achievements", "--only-milestones"],
+    ] {
+        let output = run_at(dir.path(), other_day, args);
+        assert!(output.status.success(), "{output:?}");
+        assert!(output.stdout.is_empty());
+    }
+    let output = run_at(dir.path(), other_day, &["--only-milestones", "--exit-code"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+
+    let output = run_with_stdin(dir.path(), &["--exit-code"], "");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn achievements_html() {
+    let dir = temp_config(CONFIG);
+
+    let output = run_at(dir.path(), "2024-01-01T00:00:00Z", &["--output", "html"]);
+    assert!(output.status.success(), "{output:?}");
+    let page = String::from_utf8(output.stdout).expect("Output is not UTF-8");
+    assert!(page.starts_with("<!DOCTYPE html>"));
+    let rows = page
+        .lines()
+        .filter(|line| line.starts_with("<tr class=\"gold\">"));
+    assert_eq!(rows.count(), 3);
+    assert!(page.contains("<td>Berlin Wall Fall</td><td>1989-11-09</td>"));
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["achievements", "--format", "html"],
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), page);
+}
+
+#[test]
+fn svg() {
+    let dir = temp_config(CONFIG);
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["svg", "berlin wall fall"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let svg = String::from_utf8(output.stdout).expect("Output is not UTF-8");
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains(">Berlin Wall Fall</text>"));
+    assert!(svg.contains(">12470</text>"));
+    assert!(svg.contains("fill=\"#e05d44\""));
+
+    let path = dir.path().join("badge.svg");
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &[
+            "svg",
+            "Moon landing",
+            "--style",
+            "words",
+            "-o",
+            path.to_str().unwrap(),
+        ],
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("Badge of 'Moon landing' written to {}\n", path.display())
+    );
+    let svg = fs::read_to_string(&path).expect("Failed to read badge");
+    assert!(svg.contains(">2841 weeks</text>"));
+
+    let output = run_with_stdin(dir.path(), &["svg", "Moon"], "");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No day with label 'Moon'"));
+}
+
+#[test]
+fn config_validate() {
+    let dir = temp_config(CONFIG);
+    let output = run(dir.path(), &["config", "validate"]);
+    assert_eq!(output, "Config is valid, 3 days\n");
+
+    let dir = temp_config(
+        r#"{
+  "days": [
+    {"label": "Moon landing", "date": "1969-07-20T20:17:40Z"},
+    {"label": " ", "date": "1989-11-09T18:53:00+01:00"},
+    {"label": "moon landing", "date": "1969-07-20T20:17:40Z"},
+    {"label": "Launch", "date": "2030-01-01T00:00:00Z"},
+    {"label": "Typo", "date": "1024-01-01T00:00:00Z", "colour": "red"}
+  ],
+  "theme": "dark"
+}"#,
+    );
+    let output = run_at(dir.path(), "2024-01-01T00:00:00Z", &["config", "validate"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Warning: day 2 has an empty label\n\
+         Warning: duplicate label 'Moon landing'\n\
+         Warning: 'Launch' has a date in the future\n\
+         Warning: 'Typo' has a date before 1900-01-01\n\
+         Warning: unknown field 'days[4].colour' is ignored\n\
+         Warning: unknown field 'theme' is ignored\n"
+    );
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["config", "validate", "--strict", "--min-date", "1970-01-01"],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Error: 'Moon landing' has a date before 1970-01-01\n"));
+    assert!(!stderr.contains("Warning"));
+
+    let dir = temp_config("not JSON");
+    let output = run_with_stdin(dir.path(), &["config", "validate"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Error: invalid config JSON"));
+}
+
+#[test]
+fn export_html() {
+    let dir = temp_config(
+        r#"{
+  "days": [
+    {"label": "Moon landing", "date": "1969-07-20T20:17:40Z"},
+    {"label": "<script>alert(1)</script>", "date": "2020-01-01T12:00:00Z", "description": "<script>"},
+    {"label": "Moon", "date": "2023-10-01T12:00:00Z", "archived": true}
+  ]
+}"#,
+    );
+
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &["export", "--format", "html"],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let page = String::from_utf8(output.stdout).expect("Output is not UTF-8");
+    assert!(!page.contains("<script>"));
+    assert_eq!(page.matches("&lt;script&gt;").count(), 2);
+    assert!(page.contains("Generated at 2024-01-01 00:00"));
+    for label in [
+        "Moon landing",
+        "&lt;script&gt;alert(1)&lt;/script&gt;",
+        "Moon",
+    ] {
+        let cell = format!("<td>{label}<");
+        assert_eq!(page.matches(&cell).count(), 1, "{label}");
+    }
+
+    let path = dir.path().join("report.html");
+    let output = run_at(
+        dir.path(),
+        "2024-01-01T00:00:00Z",
+        &[
+            "export",
+            "--format",
+            "html",
+            "--output",
+            path.to_str().unwrap(),
+        ],
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("Exported 3 days to {}\n", path.display())
+    );
+    assert_eq!(
+        fs::read_to_string(&path).expect("Failed to read report"),
+        page
+    );
+}